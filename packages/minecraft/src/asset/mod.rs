@@ -51,10 +51,67 @@ pub mod consts {
     pub const BTI_CHEST_FRONT: usize = 11;
     pub const BTI_CHEST_SIDE: usize = 12;
     pub const BTI_CHEST_TOP_BOTTOM: usize = 13;
+    pub const BTI_SAND: usize = 14;
+    pub const BTI_GOLD_ORE: usize = 15;
+    pub const BTI_IRON_ORE: usize = 16;
+    pub const BTI_COAL_ORE: usize = 17;
+    pub const BTI_DIAMOND_ORE: usize = 18;
+    pub const BTI_REDSTONE_ORE: usize = 19;
+    pub const BTI_LEAVES: usize = 20;
+    pub const BTI_COBBLESTONE: usize = 21;
+    pub const BTI_GLOWSTONE: usize = 22;
+    pub const BTI_WATER: usize = 23;
+    pub const BTI_LAVA: usize = 24;
+    pub const BTI_GRAVEL: usize = 25;
+    pub const BTI_MOSSY_COBBLESTONE: usize = 26;
+    pub const BTI_BEDROCK: usize = 27;
+    pub const BTI_OBSIDIAN: usize = 28;
+    pub const BTI_SPONGE: usize = 29;
+    pub const BTI_WOOL: usize = 30;
+    pub const BTI_GOLD_BLOCK: usize = 31;
+    pub const BTI_IRON_BLOCK: usize = 32;
+    pub const BTI_DIAMOND_BLOCK: usize = 33;
+    pub const BTI_BOOKSHELF: usize = 34;
+    pub const BTI_TNT_SIDE: usize = 35;
+    pub const BTI_TNT_TOP: usize = 36;
+    pub const BTI_TNT_BOTTOM: usize = 37;
+    pub const BTI_SNOW: usize = 38;
+    pub const BTI_ICE: usize = 39;
+    pub const BTI_CLAY: usize = 40;
+    pub const BTI_NETHERRACK: usize = 41;
+    pub const BTI_SOUL_SAND: usize = 42;
+    /// First of the 10 consecutive crack overlay stages drawn on a block being dug.
+    pub const BTI_DESTROY_STAGE_0: usize = 43;
+    pub const BTI_CRAFTING_TABLE_TOP: usize = 53;
+    pub const BTI_CRAFTING_TABLE_SIDE: usize = 54;
+    pub const BTI_CRAFTING_TABLE_FRONT: usize = 55;
+    pub const BTI_FURNACE_FRONT: usize = 56;
+    pub const BTI_FURNACE_SIDE: usize = 57;
+    pub const BTI_FURNACE_TOP: usize = 58;
+    pub const BTI_STONE_SLAB_TOP: usize = 59;
+    pub const BTI_STONE_SLAB_SIDE: usize = 60;
+    pub const BTI_FLOWER: usize = 61;
+    pub const BTI_TORCH: usize = 62;
 
     // item texture indexes (ITIs):
 
-    pub const ITI_STICK: usize = 0; 
+    pub const ITI_STICK: usize = 0;
+    pub const ITI_WOODEN_SHOVEL: usize = 1;
+    pub const ITI_STONE_SHOVEL: usize = 2;
+    pub const ITI_IRON_SHOVEL: usize = 3;
+    pub const ITI_DIAMOND_SHOVEL: usize = 4;
+    pub const ITI_GOLDEN_SHOVEL: usize = 5;
+    pub const ITI_WOODEN_PICKAXE: usize = 6;
+    pub const ITI_STONE_PICKAXE: usize = 7;
+    pub const ITI_IRON_PICKAXE: usize = 8;
+    pub const ITI_DIAMOND_PICKAXE: usize = 9;
+    pub const ITI_GOLDEN_PICKAXE: usize = 10;
+    pub const ITI_WOODEN_AXE: usize = 11;
+    pub const ITI_STONE_AXE: usize = 12;
+    pub const ITI_IRON_AXE: usize = 13;
+    pub const ITI_DIAMOND_AXE: usize = 14;
+    pub const ITI_GOLDEN_AXE: usize = 15;
+    pub const ITI_WOODEN_DOOR: usize = 16;
 
     // item mesh indexes (IMIs):
 
@@ -71,6 +128,7 @@ pub mod consts {
 macro_rules! lang {
     ($( $item:ident, )*)=>{
         #[derive(Debug, Clone)]
+        #[allow(non_snake_case)]
         pub struct Lang {$(
             pub $item: String,
         )*}
@@ -127,8 +185,60 @@ lang!(
     tile_planks_name,
     tile_brick_name,
     tile_glass_name,
+    tile_sand_name,
+    tile_gravel_name,
+    tile_oreGold_name,
+    tile_oreIron_name,
+    tile_oreCoal_name,
+    tile_oreDiamond_name,
+    tile_oreRedstone_name,
+    tile_log_name,
+    tile_leaves_name,
+    tile_stonebrick_name,
+    tile_lightgem_name,
+    tile_water_name,
+    tile_lava_name,
+    tile_stoneMoss_name,
+    tile_bedrock_name,
+    tile_obsidian_name,
+    tile_sponge_name,
+    tile_cloth_name,
+    tile_blockGold_name,
+    tile_blockIron_name,
+    tile_blockDiamond_name,
+    tile_bookshelf_name,
+    tile_tnt_name,
+    tile_snow_name,
+    tile_ice_name,
+    tile_clay_name,
+    tile_hellrock_name,
+    tile_hellsand_name,
+    tile_chest_name,
+    tile_workbench_name,
+    tile_furnace_name,
+    tile_stoneSlab_name,
+    tile_stairsStone_name,
+    tile_flower_name,
+    tile_torch_name,
+    tile_fence_name,
 
     item_stick_name,
+    item_shovelWood_name,
+    item_shovelStone_name,
+    item_shovelIron_name,
+    item_shovelDiamond_name,
+    item_shovelGold_name,
+    item_pickaxeWood_name,
+    item_pickaxeStone_name,
+    item_pickaxeIron_name,
+    item_pickaxeDiamond_name,
+    item_pickaxeGold_name,
+    item_hatchetWood_name,
+    item_hatchetStone_name,
+    item_hatchetIron_name,
+    item_hatchetDiamond_name,
+    item_hatchetGold_name,
+    item_doorWood_name,
 );
 
 
@@ -161,6 +271,8 @@ pub struct Assets {
 
     pub gui_inventory: GpuImageArray,
     pub gui_chest: GpuImageArray,
+    pub gui_crafting: GpuImageArray,
+    pub gui_furnace: GpuImageArray,
 
     pub vignette: GpuImageArray,
     pub sun: GpuImageArray,
@@ -226,9 +338,74 @@ impl Assets {
                 [11, 1],
                 [10, 1],
                 [9, 1],
+                [2, 1], // 14: sand
+                [0, 2], // 15: gold ore
+                [1, 2], // 16: iron ore
+                [2, 2], // 17: coal ore
+                [2, 3], // 18: diamond ore
+                [3, 3], // 19: redstone ore
+                [4, 3], // 20: leaves
+                [0, 1], // 21: cobblestone
+                [9, 6], // 22: glowstone
+                [13, 12], // 23: water
+                [13, 14], // 24: lava
+                [3, 1], // 25: gravel
+                [4, 2], // 26: mossy cobblestone
+                [1, 1], // 27: bedrock
+                [5, 2], // 28: obsidian
+                [0, 3], // 29: sponge
+                [0, 4], // 30: wool
+                [7, 1], // 31: gold block
+                [6, 1], // 32: iron block
+                [8, 1], // 33: diamond block
+                [3, 2], // 34: bookshelf
+                [8, 0], // 35: tnt side
+                [9, 0], // 36: tnt top
+                [10, 0], // 37: tnt bottom
+                [2, 4], // 38: snow
+                [3, 4], // 39: ice
+                [8, 4], // 40: clay
+                [7, 6], // 41: netherrack
+                [8, 6], // 42: soul sand
+                [0, 15], // 43: destroy stage 0
+                [1, 15], // 44: destroy stage 1
+                [2, 15], // 45: destroy stage 2
+                [3, 15], // 46: destroy stage 3
+                [4, 15], // 47: destroy stage 4
+                [5, 15], // 48: destroy stage 5
+                [6, 15], // 49: destroy stage 6
+                [7, 15], // 50: destroy stage 7
+                [8, 15], // 51: destroy stage 8
+                [9, 15], // 52: destroy stage 9
+                [11, 2], // 53: crafting table top
+                [11, 3], // 54: crafting table side
+                [12, 3], // 55: crafting table front
+                [12, 2], // 56: furnace front
+                [13, 2], // 57: furnace side
+                [14, 3], // 58: furnace top
+                [6, 0], // 59: stone slab top
+                [5, 0], // 60: stone slab side
+                [13, 0], // 61: flower
+                [0, 5], // 62: torch
             ]),
             items: items.load_sprite_array([
                 [5, 3], // 0: stick
+                [0, 5], // 1: wooden shovel
+                [1, 5], // 2: stone shovel
+                [2, 5], // 3: iron shovel
+                [3, 5], // 4: diamond shovel
+                [4, 5], // 5: golden shovel
+                [0, 6], // 6: wooden pickaxe
+                [1, 6], // 7: stone pickaxe
+                [2, 6], // 8: iron pickaxe
+                [3, 6], // 9: diamond pickaxe
+                [4, 6], // 10: golden pickaxe
+                [0, 7], // 11: wooden axe
+                [1, 7], // 12: stone axe
+                [2, 7], // 13: iron axe
+                [3, 7], // 14: diamond axe
+                [4, 7], // 15: golden axe
+                [11, 2], // 16: wooden door
             ]),
             mob_char: loader.load_image_array(&["mob/char.png"]).await,
             click_sound: loader.load_sound_effect("sound3/random/click.ogg").await,
@@ -247,6 +424,8 @@ impl Assets {
             
             gui_inventory: loader.load_image_clipper("gui/inventory.png", 256).await.load_clip([0, 0], [176, 166]),
             gui_chest: loader.load_image_clipper("gui/container.png", 256).await.load_clip([0, 0], [176, 222]),
+            gui_crafting: loader.load_image_clipper("gui/crafting.png", 256).await.load_clip([0, 0], [176, 166]),
+            gui_furnace: loader.load_image_array(&["gui/furnace.png"]).await,
             
             vignette: load_vignette(loader).await,
             sun: load_sun_moon(loader, "terrain/sun.png").await,
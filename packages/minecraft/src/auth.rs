@@ -0,0 +1,109 @@
+//! Players proving their identity when logging in.
+//!
+//! A client has an ed25519 keypair for each username it logs in with. When logging in, it sends
+//! the server the username and public key, the server responds with a random challenge, and the
+//! client responds with a signature of the challenge and username. The first public key the
+//! server sees for a username is stored in that player's save entry, after which only clients
+//! which can sign with the corresponding private key may log in as that player.
+//!
+//! Over TLS, the signed message also includes the fingerprint of the server's certificate, as the
+//! client saw it, and the server only accepts it if that's its own. This stops a malicious server
+//! from relaying a challenge from another server to its clients so as to log in to the other
+//! server as them. Connections without TLS can't be protected against this, as nothing stops
+//! whoever is in the middle of them from impersonating the server anyways.
+
+use ed25519_dalek::{
+    SigningKey,
+    VerifyingKey,
+    Signature,
+    Signer,
+};
+use rand::{
+    rngs::OsRng,
+    RngCore,
+};
+
+
+/// Ed25519 public key with which a player logs in.
+pub type PublicKey = [u8; 32];
+
+/// Random bytes the server challenges a logging in client to sign.
+pub type Challenge = [u8; 32];
+
+/// Ed25519 signature of a challenge.
+pub type ChallengeSignature = [u8; 64];
+
+// prefix of the signed message, so that signatures can't be used for anything else
+const SIGNED_MESSAGE_PREFIX: &[u8] = b"not minecraft log in challenge\0";
+
+
+/// Generate a new random challenge.
+pub fn new_challenge() -> Challenge {
+    let mut challenge = [0; 32];
+    OsRng.fill_bytes(&mut challenge);
+    challenge
+}
+
+/// Whether the signature is a valid signature of the challenge and username by the public key, for
+/// a connection encrypted with a TLS certificate with the given fingerprint, if any.
+pub fn verify_challenge(
+    public_key: &PublicKey,
+    challenge: &Challenge,
+    username: &str,
+    tls_fingerprint: Option<&str>,
+    signature: &ChallengeSignature,
+) -> bool {
+    let Ok(public_key) = VerifyingKey::from_bytes(public_key) else { return false };
+    let signature = Signature::from_bytes(signature);
+    let message = signed_message(challenge, username, tls_fingerprint);
+    public_key.verify_strict(&message, &signature).is_ok()
+}
+
+// the message which is signed to respond to a challenge
+fn signed_message(challenge: &Challenge, username: &str, tls_fingerprint: Option<&str>) -> Vec<u8> {
+    let tls_fingerprint = tls_fingerprint.unwrap_or_default();
+    let mut message = Vec::new();
+    message.extend_from_slice(SIGNED_MESSAGE_PREFIX);
+    message.extend_from_slice(challenge);
+    message.extend_from_slice(&(tls_fingerprint.len() as u32).to_le_bytes());
+    message.extend_from_slice(tls_fingerprint.as_bytes());
+    message.extend_from_slice(username.as_bytes());
+    message
+}
+
+
+/// Keypair with which a client logs in as a player.
+pub struct PlayerKeypair(SigningKey);
+
+impl PlayerKeypair {
+    /// Generate a new random keypair.
+    pub fn generate() -> Self {
+        PlayerKeypair(SigningKey::generate(&mut OsRng))
+    }
+
+    /// Construct from bytes previously returned by `to_secret_bytes`.
+    pub fn from_secret_bytes(bytes: &[u8; 32]) -> Self {
+        PlayerKeypair(SigningKey::from_bytes(bytes))
+    }
+
+    /// Convert to bytes for storage. These must be kept secret.
+    pub fn to_secret_bytes(&self) -> [u8; 32] {
+        self.0.to_bytes()
+    }
+
+    /// Get the public key.
+    pub fn public_key(&self) -> PublicKey {
+        self.0.verifying_key().to_bytes()
+    }
+
+    /// Sign a challenge received from the server when logging in with the given username, over a
+    /// connection encrypted with a TLS certificate with the given fingerprint, if any.
+    pub fn sign_challenge(
+        &self,
+        challenge: &Challenge,
+        username: &str,
+        tls_fingerprint: Option<&str>,
+    ) -> ChallengeSignature {
+        self.0.sign(&signed_message(challenge, username, tls_fingerprint)).to_bytes()
+    }
+}
@@ -1,9 +1,14 @@
+//! See `BlockUpdateQueue`.
 
 use chunk_data::*;
 use std::collections::VecDeque;
 use vek::*;
 
 
+/// Queue of tiles pending a block update, with each tile being queued at most once at a time.
+///
+/// The server pushes a tile and its neighbors whenever a tile's block is changed, and drains the
+/// queue each tick, dispatching each update to the tile's `BlockUpdateLogic`.
 #[derive(Debug, Clone, Default)]
 pub struct BlockUpdateQueue {
     tile_queued: PerChunk<PerTileBool>,
@@ -11,10 +16,12 @@ pub struct BlockUpdateQueue {
 }
 
 impl BlockUpdateQueue {
+    /// Construct empty.
     pub fn new() -> Self {
         Self::default()
     }
 
+    /// Push a tile to the back of the queue, unless it is already queued.
     pub fn push(&mut self, tile: TileKey) {
         if !tile.get(&self.tile_queued) {
             tile.set(&mut self.tile_queued, true);
@@ -22,12 +29,14 @@ impl BlockUpdateQueue {
         }
     }
 
+    /// Push the tile at the given gtc, if it is loaded.
     pub fn push_gtc(&mut self, gtc: Vec3<i64>, getter: &Getter) {
         if let Some(tile) = getter.gtc_get(gtc) {
             self.push(tile);
         }
     }
 
+    /// Push the tile at the given gtc and its 6 face neighbors, for those which are loaded.
     pub fn push_gtc_and_neighbors(&mut self, gtc: Vec3<i64>, getter: &Getter) {
         self.push_gtc(gtc, getter);
         for face in FACES {
@@ -35,6 +44,7 @@ impl BlockUpdateQueue {
         }
     }
 
+    /// Pop the tile from the front of the queue.
     pub fn pop(&mut self) -> Option<TileKey> {
         if let Some(tile) = self.queue.pop_front() {
             tile.set(&mut self.tile_queued, false);
@@ -44,12 +54,24 @@ impl BlockUpdateQueue {
         }
     }
 
+    /// Number of tiles currently queued.
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Whether no tiles are currently queued.
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    /// Call upon a chunk being added to the world.
     pub fn add_chunk(&mut self, cc: Vec3<i64>, ci: usize) {
         self.tile_queued.add(cc, ci, PerTileBool::new());
     }
 
+    /// Call upon a chunk being removed from the world. Discards its queued tiles.
     pub fn remove_chunk(&mut self, cc: Vec3<i64>, ci: usize) {
-        assert!(self.queue.is_empty(), "remove_chunk to non-empty block update queue");
+        self.queue.retain(|tile| tile.ci != ci);
         self.tile_queued.remove(cc, ci);
     }
 }
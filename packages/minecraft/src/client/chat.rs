@@ -0,0 +1,175 @@
+//! The chat overlay, showing lines of chat received from the server.
+
+use crate::gui::prelude::*;
+use vek::*;
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    time::{Instant, Duration},
+};
+
+
+/// Maximum number of lines of chat kept for scrolling back through.
+pub const MAX_CHAT_HISTORY: usize = 100;
+
+/// Number of lines of chat shown when the chat menu is open.
+pub const CHAT_OPEN_LINES: usize = 20;
+
+/// Maximum number of recent lines of chat shown when the chat menu is closed.
+pub const CHAT_CLOSED_LINES: usize = 10;
+
+/// Duration a line of chat is shown for when the chat menu is closed, including fading out.
+pub const CHAT_LINE_SHOW: Duration = Duration::from_secs(10);
+
+/// Duration over which a line of chat fades out at the end of being shown.
+pub const CHAT_LINE_FADE: Duration = Duration::from_secs(1);
+
+/// Logical width of the chat overlay.
+pub const CHAT_WIDTH: f32 = 640.0;
+
+/// Logical distance of the chat overlay from the bottom of the screen, leaving room for the hotbar
+/// and the chat menu's text box.
+pub const CHAT_BOTTOM: f32 = 100.0;
+
+
+/// The chat overlay, showing lines of chat received from the server.
+#[derive(Debug, Default)]
+pub struct Chat {
+    // lines of chat, newest last
+    lines: VecDeque<ChatLine>,
+    // number of lines scrolled back from the newest when the chat menu is open
+    scroll: usize,
+    // number of lines to scroll back by upon gui effect processing
+    pending_scroll: Cell<i32>,
+}
+
+#[derive(Debug)]
+struct ChatLine {
+    text: String,
+    // built lazily, as it needs the gui context
+    text_block: Option<GuiTextBlock<true>>,
+    added: Instant,
+}
+
+impl Chat {
+    /// Construct with no lines of chat.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a line of chat, forgetting the oldest if there's too many.
+    pub fn add_line(&mut self, text: String) {
+        if self.lines.len() >= MAX_CHAT_HISTORY {
+            self.lines.pop_front();
+        }
+        self.lines.push_back(ChatLine {
+            text,
+            text_block: None,
+            added: Instant::now(),
+        });
+        // keep scrolled back lines in view
+        if self.scroll > 0 {
+            self.scroll_by(1);
+        }
+    }
+
+    /// Scroll back by the given number of lines, or forward if negative.
+    pub fn scroll_by(&mut self, lines: i32) {
+        let max_scroll = self.lines.len().saturating_sub(CHAT_OPEN_LINES);
+        self.scroll = (self.scroll as i64 + lines as i64).clamp(0, max_scroll as i64) as usize;
+    }
+
+    /// Scroll forward to the newest lines.
+    pub fn reset_scroll(&mut self) {
+        self.scroll = 0;
+    }
+
+    /// Get as a gui block. When the chat menu is open, this shows scrolled lines of chat and
+    /// lets the mouse scroll them, and otherwise shows recent lines fading out.
+    pub fn gui<'a>(
+        &'a mut self,
+        ctx: &GuiGlobalContext<'a>,
+        open: bool,
+    ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> {
+        let now = Instant::now();
+        let (skip, max_lines) =
+            if open { (self.scroll, CHAT_OPEN_LINES) }
+            else { (0, CHAT_CLOSED_LINES) };
+        let mut lines = self.lines.iter_mut()
+            .rev()
+            .skip(skip)
+            .take(max_lines)
+            .filter_map(|line| {
+                let alpha =
+                    if open { 1.0 }
+                    else {
+                        let left = CHAT_LINE_SHOW.saturating_sub(now - line.added);
+                        (left.as_secs_f32() / CHAT_LINE_FADE.as_secs_f32()).min(1.0)
+                    };
+                if alpha <= 0.0 {
+                    return None;
+                }
+                let text_block = line.text_block.get_or_insert_with(|| {
+                    GuiTextBlock::new(&GuiTextBlockConfig {
+                        text: &line.text,
+                        font: ctx.assets.font,
+                        logical_font_size: 16.0,
+                        color: Rgba::white(),
+                        h_align: HAlign::Left,
+                        v_align: VAlign::Top,
+                        shadow: true,
+                    })
+                });
+                Some(modify(Rgba::new(1.0, 1.0, 1.0, alpha),
+                    before_after(
+                        (solid([0.0, 0.0, 0.0, 0.5]),),
+                        h_margin(4.0, 4.0,
+                            text_block
+                        ),
+                        (),
+                    )
+                ))
+            })
+            .collect::<Vec<_>>();
+        lines.reverse();
+        layer((
+            open.then_some(ChatScroller(&self.pending_scroll)),
+            v_margin(0.0, CHAT_BOTTOM,
+                v_align(1.0,
+                    h_margin(4.0, 0.0,
+                        h_align(0.0,
+                            logical_width(CHAT_WIDTH,
+                                v_stack(0.0, lines)
+                            )
+                        )
+                    )
+                )
+            ),
+        ))
+    }
+
+    /// Handle chat gui effects.
+    pub fn process_gui_effects(&mut self) {
+        let lines = self.pending_scroll.take();
+        if lines != 0 {
+            self.scroll_by(lines);
+        }
+    }
+}
+
+
+// gui block that scrolls the chat when the mouse is scrolled
+#[derive(Debug)]
+struct ChatScroller<'a>(&'a Cell<i32>);
+
+impl<'a> GuiNode<'a> for SimpleGuiBlock<ChatScroller<'a>> {
+    never_blocks_cursor_impl!();
+
+    fn on_cursor_scroll(self, _: GuiSpatialContext, _: bool, amount: ScrolledAmount) {
+        let lines = match amount {
+            ScrolledAmount::Lines(amount) => amount.y.round() as i32,
+            ScrolledAmount::Pixels(amount) => (amount.y / 16.0).round() as i32,
+        };
+        self.inner.0.set(self.inner.0.get() + lines);
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
         *,
     },
     thread_pool::*,
+    lighting::ChunkLight,
 };
 use graphics::{
     frame_content::Mesh,
@@ -143,13 +144,15 @@ impl ChunkMeshMgr {
         }
     }
 
-    /// Call upon a chunk being added to the world. May interally call `self.mark_dirty()`.
+    /// Call upon a chunk being added to the world, after its light levels are computed. May
+    /// interally call `self.mark_dirty()`.
     pub fn add_chunk(
         &mut self,
         cc: Vec3<i64>,
         ci: usize,
         chunks: &ClientLoadedChunks,
         tile_blocks: &PerChunk<ChunkBlocks>,
+        tile_light: &PerChunk<ChunkLight>,
     ) {
         // install into world
         let aborted_1 = AbortGuard::new();
@@ -160,7 +163,7 @@ impl ChunkMeshMgr {
         self.chunk_dirty_meshed_idx.add(cc, ci, None);
         
         // trigger relevant meshing
-        self.trigger_init_mesh(cc, ci, chunks, tile_blocks, aborted_2);
+        self.trigger_init_mesh(cc, ci, chunks, tile_blocks, tile_light, aborted_2);
         self.mark_chunk_adj_dirty(cc, ci, chunks);
     }
 
@@ -197,13 +200,14 @@ impl ChunkMeshMgr {
         chunk_mesh: ChunkMesh,
         chunks: &ClientLoadedChunks,
         tile_blocks: &PerChunk<ChunkBlocks>,
+        tile_light: &PerChunk<ChunkLight>,
     ) {
         // install it
         *self.chunk_mesh_state.get_mut(cc, ci) = MeshState::Meshed(chunk_mesh);
 
         // if it's dirty, just patch it again right away
         if !self.chunk_dirty_tiles.get(cc, ci).is_empty() {
-            self.patch_chunk(cc, ci, chunks, tile_blocks);
+            self.patch_chunk(cc, ci, chunks, tile_blocks, tile_light);
         }
     }
 
@@ -258,6 +262,7 @@ impl ChunkMeshMgr {
         &mut self,
         chunks: &ClientLoadedChunks,
         tile_blocks: &PerChunk<ChunkBlocks>,
+        tile_light: &PerChunk<ChunkLight>,
     ) {
         // poll patch thread
         while let Ok(msg) = self.patch_thread_recv_res.try_recv() {
@@ -272,14 +277,14 @@ impl ChunkMeshMgr {
 
                 // if it's dirty, just patch it again right away
                 if !self.chunk_dirty_tiles.get(cc, ci).is_empty() {
-                    self.patch_chunk(cc, ci, chunks, tile_blocks);
+                    self.patch_chunk(cc, ci, chunks, tile_blocks, tile_light);
                 }
             }
         }
 
         // patch all dirty meshed chunks, and mark them as clean
         while let Some((cc, ci)) = self.dirty_meshed_chunks.pop() {
-            self.patch_chunk(cc, ci, chunks, tile_blocks);
+            self.patch_chunk(cc, ci, chunks, tile_blocks, tile_light);
             *self.chunk_dirty_meshed_idx.get_mut(cc, ci) = None;
         }
     }
@@ -295,6 +300,7 @@ impl ChunkMeshMgr {
         &mut self,
         chunks: &ClientLoadedChunks,
         tile_blocks: &PerChunk<ChunkBlocks>,
+        tile_light: &PerChunk<ChunkLight>,
     ) {
         debug_assert!(
             self.dirty_meshed_chunks.is_empty(),
@@ -315,7 +321,7 @@ impl ChunkMeshMgr {
 
                 // if it's dirty, just send it right back in to be patched
                 if !self.chunk_dirty_tiles.get(cc, ci).is_empty() {
-                    self.patch_chunk(cc, ci, chunks, tile_blocks);
+                    self.patch_chunk(cc, ci, chunks, tile_blocks, tile_light);
                 }
             }
         }
@@ -346,6 +352,7 @@ impl ChunkMeshMgr {
         ci: usize,
         chunks: &ClientLoadedChunks,
         tile_blocks: &PerChunk<ChunkBlocks>,
+        tile_light: &PerChunk<ChunkLight>,
     ) {
         // transition state
         let aborted_1 = AbortGuard::new();
@@ -368,6 +375,7 @@ impl ChunkMeshMgr {
                 &self.init_mesh_job_ctx.game,
                 &getter,
                 tile_blocks,
+                tile_light,
             );
             self.mesh_buf.translate(lti_to_ltc(lti).map(|n| n as f32));
             if let Some(&submesh_key) = chunk_mesh.tile_submesh_key.get(lti) {
@@ -395,6 +403,7 @@ impl ChunkMeshMgr {
         ci: usize,
         chunks: &ClientLoadedChunks,
         tile_blocks: &PerChunk<ChunkBlocks>,
+        tile_light: &PerChunk<ChunkLight>,
         aborted: AbortHandle,
     ) {
         // mesh all the border tiles while here, without applying the patch
@@ -418,6 +427,7 @@ impl ChunkMeshMgr {
                 &self.init_mesh_job_ctx.game,
                 &getter,
                 tile_blocks,
+                tile_light,
             );
             self.mesh_buf.translate(ltc.map(|n| n as f32));
             if !self.mesh_buf.is_empty() {
@@ -430,6 +440,7 @@ impl ChunkMeshMgr {
         // tiles then apply the patch
         let chunk_tile_blocks = self.init_mesh_job_ctx.game
             .clone_chunk_blocks(tile_blocks.get(cc, ci));
+        let chunk_light = tile_light.get(cc, ci).clone();
         let ctx = Arc::clone(&self.init_mesh_job_ctx);
         self.thread_pool.submit(WorkPriority::Client, aborted, move |aborted| {
             // build a fake world
@@ -438,6 +449,8 @@ impl ChunkMeshMgr {
             let ci2 = chunks.add(cc);
             debug_assert_eq!(ci2, 0);
             tile_blocks.add(cc, ci2, chunk_tile_blocks);
+            let mut tile_light = PerChunk::new();
+            tile_light.add(cc, ci2, chunk_light);
 
             // mesh every tile not on the border
             let mut mesh_buf = MeshData::new();
@@ -451,7 +464,7 @@ impl ChunkMeshMgr {
                 let lti = ltc_to_lti(ltc);
                 debug_assert!(mesh_buf.is_empty());
                 let tile = TileKey { cc, ci: ci2, lti };
-                mesh_tile(&mut mesh_buf, tile, &ctx.game, &getter, &tile_blocks);
+                mesh_tile(&mut mesh_buf, tile, &ctx.game, &getter, &tile_blocks, &tile_light);
                 
                 // add the tile mesh to the chunk mesh
                 if !mesh_buf.is_empty() {
@@ -0,0 +1,85 @@
+//! Crafting grid gui shared by menus which have one.
+
+use crate::{
+    client::{
+        menu_mgr::*,
+        item_grid::*,
+    },
+    gui::prelude::*,
+    item::ItemStack,
+    message::*,
+};
+use vek::*;
+
+
+/// Gui state for a crafting grid and its output slot within a menu.
+///
+/// The grid's slots are the player's crafting slots. The output slot shows what crafting with them
+/// would produce, as computed client-side, and clicking it asks the server to craft.
+#[derive(Debug)]
+pub struct CraftingGrid {
+    width: usize,
+    slot_text_caches: Vec<ItemSlotTextCache>,
+    output_slot_text_cache: [ItemSlotTextCache; 1],
+    output: [Option<ItemStack>; 1],
+}
+
+impl CraftingGrid {
+    /// Construct for a square grid of the given width.
+    pub fn new(width: usize) -> Self {
+        CraftingGrid {
+            width,
+            slot_text_caches: (0..width * width).map(|_| Default::default()).collect(),
+            output_slot_text_cache: Default::default(),
+            output: Default::default(),
+        }
+    }
+
+    /// Gui block for the grid and output slot, positioned within the menu at the given positions
+    /// in pixels of its background texture, which are one pixel up and left of their first slot's
+    /// item.
+    pub fn gui<'a>(
+        &'a mut self,
+        ctx: &GuiGlobalContext<'a>,
+        client: &MenuGuiClientBorrows<'a>,
+        grid_pos: Vec2<f32>,
+        output_pos: Vec2<f32>,
+    ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> {
+        let grid = &client.inventory_slots.crafting_slots[..self.width * self.width];
+        self.output = [ctx.game.recipes.craft(grid, self.width)];
+        layer((
+            margin(grid_pos.x * 2.0, 0.0, grid_pos.y * 2.0, 0.0,
+                align(0.0,
+                    item_grid_gui_block(
+                        grid,
+                        ItemGridDefaultLayout::new(self.width),
+                        item_grid_default_render_logic(
+                            client.item_mesh,
+                            &client.inventory_slots.held_slot,
+                            self.slot_text_caches.iter_mut(),
+                        ),
+                        item_grid_default_click_logic(
+                            client.connection,
+                            &client.inventory_slots.held_slot,
+                            |i| UpItemSlotRef::Crafting(i.try_into().unwrap()),
+                        ),
+                    )
+                )
+            ),
+            margin(output_pos.x * 2.0, 0.0, output_pos.y * 2.0, 0.0,
+                align(0.0,
+                    item_grid_gui_block(
+                        &self.output,
+                        ItemGridDefaultLayout::new(1),
+                        item_grid_default_render_logic(
+                            client.item_mesh,
+                            &client.inventory_slots.held_slot,
+                            self.output_slot_text_cache.iter_mut(),
+                        ),
+                        item_grid_craft_click_logic(client.connection),
+                    )
+                )
+            ),
+        ))
+    }
+}
@@ -8,25 +8,51 @@ use crate::{
         },
         client_loaded_chunks::ClientLoadedChunks,
         chunk_mesh_mgr::ChunkMeshMgr,
-        menu_mgr::MenuGuiClientBorrows,
+        menu_mgr::{MenuGuiClientBorrows, MenuKeyPressClientBorrows},
         menu_esc::EscMenu,
         menu_inventory::InventoryMenu,
+        menu_container::ContainerMenu,
+        menu_chat::ChatMenu,
+        hud::HOTBAR_SLOTS,
+        mesh_block::DESTROY_STAGES,
         *,
     },
-    server::tick_mgr::TICK,
-    sync_state_entities::{steve_physics_continuous, steve_physics_discrete},
+    server::{
+        tick_mgr::TICK,
+        process_player_msg::BLOCK_REACH,
+    },
+    sync_state_entities::{steve_physics_continuous, steve_physics_discrete, ITEM_SIZE},
     message::*,
-    physics::prelude::*,
+    physics::{
+        prelude::*,
+        looking_at::LookingAt,
+    },
     gui::prelude::*,
     sync_state_entities,
+    game_data::{
+        per_block::PerBlock,
+        logic::{
+            dig_logic::dig_ticks,
+            place_logic::ItemPlaceLogic,
+            use_logic::BlockUseLogic,
+            item_mesh_logic::ItemMeshLogic,
+        },
+    },
+    day_night::day_night_time,
+    util_usize_lt::UsizeLt,
+    gui_state_disconnected::DisconnectedMenu,
+};
+use graphics::{
+    prelude::*,
+    frame_content::{DrawObj2, DrawSky},
 };
-use graphics::prelude::*;
 use chunk_data::*;
 use std::{
     fmt::{self, Formatter, Debug},
     time::{Instant, Duration},
     cmp::min,
     mem::take,
+    f32::consts::PI,
 };
 use vek::*;
 use anyhow::*;
@@ -34,6 +60,34 @@ use anyhow::*;
 
 pub const MAX_CATCHUP: Duration = Duration::from_millis(30);
 
+/// Radians the camera turns per pixel of captured mouse movement.
+pub const MOUSE_SENSITIVITY: f32 = 0.003;
+
+/// Keys which select each hotbar slot.
+pub const HOTBAR_KEYS: [KeyCode; HOTBAR_SLOTS] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+/// Distance within which the player can target tiles, short enough that the centers of the tile
+/// targeted and the tile against its face are within the server's `BLOCK_REACH`.
+pub const TARGET_DIST: f32 = BLOCK_REACH - 1.0;
+
+/// Ticks after finishing digging a tile before digging can start again.
+pub const DIG_COOLDOWN_TICKS: u32 = 5;
+
+/// Distance at which fog starts increasing from 0%, if fog is enabled.
+pub const FOG_START: f32 = 180.0;
+/// Distance at which fog reaches 100%, if fog is enabled.
+pub const FOG_END: f32 = 240.0;
+
 
 /// Wrapper around client that implements `GuiStateFrame`.
 pub struct ClientGuiState(pub Client);
@@ -49,10 +103,18 @@ pub struct WorldGuiBlock<'a> {
     pub steve_mesh: &'a Mesh,
     pub chunk_steves: &'a PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::SteveEntityState, sync_state_entities::SteveEntityClientState>>>,
     pub chunk_pigs: &'a PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::PigEntityState, sync_state_entities::PigEntityClientState>>>,
+    pub chunk_falling_blocks: &'a PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::FallingBlockEntityState, ()>>>,
+    pub chunk_items: &'a PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::ItemEntityState, ()>>>,
+    pub block_mesh: &'a PerBlock<Mesh>,
+    pub destroy_stage_mesh: &'a [Mesh],
+    /// Tile being dug and the stage of the crack overlay to draw on it, if any.
+    pub dig_stage: Option<(Vec3<i64>, usize)>,
+    pub time_of_day: u64,
 }
 
 impl ClientGuiState {
-    /// Process an asynchronous client event. The client promptly exits on error.
+    /// Process an asynchronous client event. The client promptly exits on error, showing it to the
+    /// user as the reason for being disconnected.
     pub fn process_event(&mut self, event: ClientEvent) -> Result<()> {
         trace!(?event, "client event");
         match event {
@@ -61,13 +123,16 @@ impl ClientGuiState {
             // network event
             ClientEvent::Network(event) => match event {
                 NetworkEvent::Received(msg) => match msg {
+                    DownMsg::AuthChallenge(_) => bail!("server protocol violation"),
                     DownMsg::AcceptLogIn(_) => bail!("server protocol violation"),
                     DownMsg::PreJoin(msg) => process_pre_join_msg(&mut self.0.pre_join, msg)?,
                     DownMsg::ShouldJoinGame => bail!("server protocol violation"),
                     DownMsg::FinalizeJoinGame(_) => bail!("server protocol violation"),
                     DownMsg::PostJoin(msg) => process_post_join_msg(&mut self.0, msg)?,
+                    DownMsg::Close(DownMsgClose { reason }) => bail!("{}", reason),
                 }
-                NetworkEvent::Closed(msg) => bail!("server connection closed: {:?}", msg),
+                NetworkEvent::Closed(Some(msg)) => bail!("connection lost: {}", msg),
+                NetworkEvent::Closed(None) => bail!("connection lost"),
             },
             // chunk meshed for the first time
             ClientEvent::ChunkMeshed { cc, ci, chunk_mesh } => {
@@ -79,17 +144,93 @@ impl ClientGuiState {
                         chunk_mesh,
                         &self.0.pre_join.chunks,
                         &self.0.pre_join.tile_blocks,
+                        self.0.pre_join.tile_light.chunk_light(),
                     );
             }
         }
         Ok(())
     }
 
+    /// Compute what tile the player is looking at, if any.
+    fn looking_at(&self) -> Option<LookingAt> {
+        let getter = self.0.pre_join.chunks.getter();
+        let rot = Quaternion::rotation_y(self.0.yaw) * Quaternion::rotation_x(self.0.pitch);
+        compute_looking_at(
+            self.0.pos,
+            rot * Vec3::new(0.0, 0.0, 1.0),
+            TARGET_DIST,
+            &getter,
+            &self.0.pre_join.tile_blocks,
+            &self.0.pre_join.game,
+        )
+    }
+
+    /// Start, finish, or cancel digging depending on whether left click is held and what tile is
+    /// being looked at.
+    fn update_dig(&mut self, ctx: &GuiWindowContext, now: Instant) {
+        let held = !self.0.menu_mgr.is_open_menu()
+            && ctx.global().focus_level == FocusLevel::MouseCaptured
+            && ctx.global().pressed_mouse_buttons.contains(&MouseButton::Left);
+        let target = held.then(|| self.looking_at()).flatten().map(|looking_at| looking_at.tile);
+
+        // continue digging the current tile only if still digging it with the same slot
+        if let Some(dig) = self.0.dig {
+            if target.map(|tile| tile.gtc()) != Some(dig.gtc)
+                || self.0.selected_hotbar_slot != dig.hotbar_slot
+            {
+                self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::CancelDig(
+                    PlayerMsgCancelDig
+                )));
+                self.0.dig = None;
+            } else if now >= dig.start + dig.duration {
+                self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::FinishDig(
+                    PlayerMsgFinishDig
+                )));
+                self.0.dig = None;
+                self.0.next_dig = now + TICK * DIG_COOLDOWN_TICKS;
+            }
+        }
+
+        // start digging the tile if able
+        if self.0.dig.is_some() || now < self.0.next_dig {
+            return;
+        }
+        let Some(tile) = target else { return };
+        let bid = tile.get(&self.0.pre_join.tile_blocks).get();
+        let iid = self.0.inventory_slots.inventory_slots[self.0.selected_hotbar_slot]
+            .as_ref()
+            .map(|stack| stack.iid);
+        let Some(ticks) = dig_ticks(&self.0.pre_join.game, bid, iid) else { return };
+        self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::StartDig(
+            PlayerMsgStartDig {
+                gtc: tile.gtc(),
+                hotbar_slot: UsizeLt::new(self.0.selected_hotbar_slot),
+            }
+        )));
+        self.0.dig = Some(ClientDig {
+            gtc: tile.gtc(),
+            hotbar_slot: self.0.selected_hotbar_slot,
+            start: now,
+            duration: TICK * ticks as u32,
+        });
+    }
+
     /// Get as a gui block.
     pub fn gui<'a>(
         &'a mut self,
         ctx: &'a GuiWindowContext,
     ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> {
+        let now = Instant::now();
+        let dig_stage = self.0.dig.map(|dig| {
+            let progress =
+                if dig.duration.is_zero() { 1.0 }
+                else {
+                    now.saturating_duration_since(dig.start).as_secs_f32()
+                        / dig.duration.as_secs_f32()
+                };
+            (dig.gtc, min((progress * DESTROY_STAGES as f32) as usize, DESTROY_STAGES - 1))
+        });
+        let chat_open = self.0.menu_mgr.is_open_chat_menu();
         layer((
             WorldGuiBlock {
                 chunks: &self.0.pre_join.chunks,
@@ -101,11 +242,27 @@ impl ClientGuiState {
                 steve_mesh: &self.0.steve_mesh,
                 chunk_steves: &self.0.pre_join.chunk_steves,
                 chunk_pigs: &self.0.pre_join.chunk_pigs,
+                chunk_falling_blocks: &self.0.pre_join.chunk_falling_blocks,
+                chunk_items: &self.0.pre_join.chunk_items,
+                block_mesh: &self.0.pre_join.block_mesh,
+                destroy_stage_mesh: &self.0.pre_join.destroy_stage_mesh,
+                dig_stage,
+                time_of_day: self.0.pre_join.time_of_day,
             },
+            (!self.0.menu_mgr.is_open_menu()).then(mouse_capturer),
+            self.0.hud.gui(
+                ctx.global(),
+                &self.0.inventory_slots.inventory_slots,
+                &self.0.pre_join.item_mesh,
+                self.0.selected_hotbar_slot,
+            ),
+            self.0.chat.gui(ctx.global(), chat_open),
             self.0.menu_mgr.gui(ctx, MenuGuiClientBorrows {
                 connection: &self.0.pre_join.connection,
                 inventory_slots: &self.0.inventory_slots,
                 item_mesh: &self.0.pre_join.item_mesh,
+                chunks: &self.0.pre_join.chunks,
+                tile_blocks: &self.0.pre_join.tile_blocks,
             }),
         ))
     }
@@ -427,14 +584,19 @@ impl GuiStateFrame for ClientGuiState {
             self.0.pitch -= lookment.y;
         }
 
+        // digging
+        self.update_dig(ctx, now);
+
         // fully synchronize chunk meshes so they're ready to render
         self.0.pre_join.chunk_mesh_mgr.flush_dirty(
             &self.0.pre_join.chunks,
             &self.0.pre_join.tile_blocks,
+            self.0.pre_join.tile_light.chunk_light(),
         );
         self.0.pre_join.chunk_mesh_mgr.synchronize(
             &self.0.pre_join.chunks,
             &self.0.pre_join.tile_blocks,
+            self.0.pre_join.tile_light.chunk_light(),
         );
     }
 
@@ -447,44 +609,20 @@ impl GuiStateFrame for ClientGuiState {
         trace!(?key, "key press");
         if self.0.menu_mgr.is_open_menu() {
             // have menu handle
-            self.0.menu_mgr.on_key_press(ctx, key, typing);
+            self.0.menu_mgr.on_key_press(ctx, key, typing, MenuKeyPressClientBorrows {
+                connection: &self.0.pre_join.connection,
+                chat: &mut self.0.chat,
+            });
             return;
-        } else if key == KeyCode::KeyP || key == KeyCode::KeyL || key == KeyCode::KeyO {
-            let getter = self.0.pre_join.chunks.getter();
-            let rot = Quaternion::rotation_y(self.0.yaw) * Quaternion::rotation_x(self.0.pitch);
-            let looking_at = compute_looking_at(
-                self.0.pos,
-                rot * Vec3::new(0.0, 0.0, 1.0),
-                50.0,
-                &getter,
-                &self.0.pre_join.tile_blocks,
-                &self.0.pre_join.game,
-            );
-            if let Some(looking_at) = looking_at {
-                if key == KeyCode::KeyP || key == KeyCode::KeyL {
-                    let (gtc, bid_meta) =
-                        if key == KeyCode::KeyP {
-                            (
-                                looking_at.tile.gtc() +
-                                    looking_at.face.map(|face| face.to_vec()).unwrap_or(0.into()),
-                                ErasedBidMeta::new(self.0.pre_join.game.content.stone.bid_stone, ()),
-                            )
-                        } else {
-                            (
-                                looking_at.tile.gtc(),
-                                ErasedBidMeta::new(AIR, ())
-                            )
-                        };
-                    self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::SetTileBlock(
-                        PlayerMsgSetTileBlock { gtc, bid_meta }
-                    )));
-                } else {
-                    self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::SpawnSteve(
-                        looking_at.pos
-                        //looking_at.tile.gtc().map(|n| n as f32)
-                    )));
-                }
+        } else if key == KeyCode::KeyO {
+            if let Some(looking_at) = self.looking_at() {
+                self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::SpawnSteve(
+                    looking_at.pos
+                    //looking_at.tile.gtc().map(|n| n as f32)
+                )));
             }
+        } else if let Some(i) = HOTBAR_KEYS.iter().position(|&hotbar_key| key == hotbar_key) {
+            self.0.selected_hotbar_slot = i;
         } else if key == KeyCode::KeyK {
             self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::ClearSteves));
         } else if key == KeyCode::Escape {
@@ -492,6 +630,12 @@ impl GuiStateFrame for ClientGuiState {
         } else if key == KeyCode::KeyE {
             self.0.menu_mgr.set_menu(InventoryMenu::new(ctx.global()));
         } else if key == KeyCode::KeyT {
+            self.0.chat.reset_scroll();
+            self.0.menu_mgr.set_menu(ChatMenu::new(""));
+        } else if key == KeyCode::Slash {
+            self.0.chat.reset_scroll();
+            self.0.menu_mgr.set_menu(ChatMenu::new("/"));
+        } else if key == KeyCode::F3 {
             let now = Instant::now();
             self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::ClockDebug(
                 self.0.pre_join.connection.rel_time(now)
@@ -499,6 +643,60 @@ impl GuiStateFrame for ClientGuiState {
         }
     }
 
+    fn on_captured_mouse_click(&mut self, ctx: &GuiWindowContext, button: MouseButton) {
+        if self.0.menu_mgr.is_open_menu() {
+            return;
+        }
+        if button == MouseButton::Right {
+            let Some(looking_at) = self.looking_at() else { return };
+
+            // open the menu of the block looked at, if it has one
+            let gtc = looking_at.tile.gtc();
+            let bid = looking_at.tile.get(&self.0.pre_join.tile_blocks).get();
+            if let Some(menu) = ContainerMenu::new(ctx.global(), gtc, bid) {
+                self.0.menu_mgr.set_menu(menu);
+                return;
+            }
+
+            // otherwise use it, if it can be used
+            if !matches!(ctx.game().blocks_use_logic[bid], BlockUseLogic::Nothing) {
+                self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::UseBlock(
+                    PlayerMsgUseBlock { gtc }
+                )));
+                return;
+            }
+
+            // place a block from the selected hotbar slot against the face looked at
+            let Some(face) = looking_at.face else { return };
+            self.0.pre_join.connection.send(UpMsg::PlayerMsg(PlayerMsg::PlaceBlock(
+                PlayerMsgPlaceBlock {
+                    gtc: looking_at.tile.gtc() + face.to_vec(),
+                    hotbar_slot: UsizeLt::new(self.0.selected_hotbar_slot),
+                }
+            )));
+        }
+    }
+
+    fn on_captured_mouse_move(&mut self, _: &GuiWindowContext, amount: Vec2<f32>) {
+        if self.0.menu_mgr.is_open_menu() {
+            return;
+        }
+        self.0.yaw += amount.x * MOUSE_SENSITIVITY;
+        self.0.pitch = (self.0.pitch + amount.y * MOUSE_SENSITIVITY).clamp(-PI / 2.0, PI / 2.0);
+    }
+
+    fn on_captured_mouse_scroll(&mut self, _: &GuiWindowContext, amount: ScrolledAmount) {
+        if self.0.menu_mgr.is_open_menu() {
+            return;
+        }
+        // scrolling up selects the previous hotbar slot, and down the next, wrapping around
+        let (ScrolledAmount::Pixels(amount) | ScrolledAmount::Lines(amount)) = amount;
+        if amount.y != 0.0 {
+            let step = if amount.y > 0.0 { HOTBAR_SLOTS - 1 } else { 1 };
+            self.0.selected_hotbar_slot = (self.0.selected_hotbar_slot + step) % HOTBAR_SLOTS;
+        }
+    }
+
     fn poll_user_events(
         &mut self,
         ctx: &GuiWindowContext,
@@ -513,6 +711,7 @@ impl GuiStateFrame for ClientGuiState {
             if let Err(e) = result {
                 error!(%e, "error processing event (client closing)");
                 ctx.global().pop_state_frame();
+                ctx.global().push_state_frame(DisconnectedMenu::new(ctx.global(), &e.to_string()));
                 return;
             }
 
@@ -527,6 +726,7 @@ impl GuiStateFrame for ClientGuiState {
                 self.0.pre_join.chunk_mesh_mgr.flush_dirty(
                     &self.0.pre_join.chunks,
                     &self.0.pre_join.tile_blocks,
+                    self.0.pre_join.tile_light.chunk_light(),
                 );
                 flushed = now;
             }
@@ -536,11 +736,13 @@ impl GuiStateFrame for ClientGuiState {
         self.0.pre_join.chunk_mesh_mgr.flush_dirty(
             &self.0.pre_join.chunks,
             &self.0.pre_join.tile_blocks,
+            self.0.pre_join.tile_light.chunk_light(),
         );
     }
 
     fn process_gui_effects(&mut self, ctx: &GuiWindowContext) {
         self.0.menu_mgr.process_gui_effects(ctx, &self.0.pre_join.connection);
+        self.0.chat.process_gui_effects();
     }
 }
 
@@ -556,9 +758,45 @@ impl<'a> GuiNode<'a> for SimpleGuiBlock<WorldGuiBlock<'a>> {
             f32::to_radians(120.0),
             self.size,
         );
+
+        // the sky stays at mid day if the day night cycle is disabled in the settings
+        let day_night_time =
+            if ctx.settings().day_night { day_night_time(self.inner.time_of_day) }
+            else { 0.25 };
+        let fog =
+            if ctx.settings().fog {
+                Fog::Earth { start: FOG_START, end: FOG_END, day_night_time }
+            } else { Fog::None };
+
+        // sky
+        canvas.reborrow()
+            .scale(self.size)
+            .draw(DrawObj2::Sky(DrawSky { view_proj: vp, day_night_time }));
+
+        // sun and moon, which rotate around the camera
+        {
+            // intensity of it being day as opposed to night
+            let day = (f32::sin(day_night_time * PI * 2.0) + 0.6).clamp(0.0, 1.0);
+            let sun_oversat = (day + 1.0).powf(2.0) - 0.8;
+            let sun_moon_transl = Vec3::new(-0.5, -0.5, 1.6);
+            let mut canvas = canvas.reborrow()
+                .scale(self.size)
+                .begin_3d(vp, Fog::None)
+                .translate(self.inner.pos)
+                .rotate(Quaternion::rotation_x(-day_night_time * PI * 2.0));
+            canvas.reborrow()
+                .translate(sun_moon_transl)
+                .color([sun_oversat, sun_oversat, sun_oversat, 1.0])
+                .draw_image(&ctx.assets().sun, 0, 0.0, 1.0);
+            canvas.reborrow()
+                .rotate(Quaternion::rotation_x(PI))
+                .translate(sun_moon_transl)
+                .draw_image(&ctx.assets().moon, 0, 0.0, 1.0);
+        }
+
         let mut canvas = canvas.reborrow()
             .scale(self.size)
-            .begin_3d(vp, Fog::None);
+            .begin_3d(vp, fog);
         for (cc, ci, _getter) in self.inner.chunks.iter() {
             let bbox_pos = (cc * CHUNK_EXTENT).map(|n| n as f32);
             let bbox_ext = CHUNK_EXTENT.map(|n| n as f32);
@@ -589,12 +827,52 @@ impl<'a> GuiNode<'a> for SimpleGuiBlock<WorldGuiBlock<'a>> {
                     .color(pig.entity.state.color)
                     .draw_mesh(self.inner.steve_mesh, &ctx.assets().blocks);
             }
+
+            for falling_block in self.inner.chunk_falling_blocks.get(cc, ci) {
+                canvas.reborrow()
+                    .translate((cc * CHUNK_EXTENT).map(|n| n as f32))
+                    .translate(falling_block.entity.rel_pos)
+                    .translate([-0.5, 0.0, -0.5])
+                    .draw_mesh(
+                        &self.inner.block_mesh[falling_block.entity.state.bid_meta.bid],
+                        &ctx.assets().blocks,
+                    );
+            }
+
+            for item in self.inner.chunk_items.get(cc, ci) {
+                // items which place blocks look like small blocks, and flat items like sprites
+                // which turn to face the camera
+                let iid = item.entity.state.stack.iid;
+                let mut canvas = canvas.reborrow()
+                    .translate((cc * CHUNK_EXTENT).map(|n| n as f32))
+                    .translate(item.entity.rel_pos);
+                if let ItemPlaceLogic::Block(ref bid_meta) = ctx.game().items_place_logic[iid] {
+                    canvas.reborrow()
+                        .translate([-ITEM_SIZE / 2.0, 0.0, -ITEM_SIZE / 2.0])
+                        .scale(ITEM_SIZE)
+                        .draw_mesh(&self.inner.block_mesh[bid_meta.bid], &ctx.assets().blocks);
+                } else if let ItemMeshLogic::Flat { tex_index } = ctx.game().items_mesh_logic[iid] {
+                    canvas.reborrow()
+                        .rotate(Quaternion::rotation_y(self.inner.yaw))
+                        .translate([-ITEM_SIZE / 2.0, 0.0, 0.0])
+                        .scale(ITEM_SIZE)
+                        .draw_image(&ctx.assets().items, tex_index, [0.0, 1.0], [1.0, -1.0]);
+                }
+            }
+        }
+        if let Some((gtc, stage)) = self.inner.dig_stage {
+            // slightly enlarged to not z-fight with the block itself
+            const EXPAND: f32 = 0.002;
+            canvas.reborrow()
+                .translate(gtc.map(|n| n as f32) - EXPAND)
+                .scale(1.0 + 2.0 * EXPAND)
+                .draw_mesh(&self.inner.destroy_stage_mesh[stage], &ctx.assets().blocks);
         }
         let getter = self.inner.chunks.getter();
         if let Some(looking_at) = compute_looking_at(
             self.inner.pos,
             dir,
-            50.0,
+            TARGET_DIST,
             &getter,
             self.inner.tile_blocks,
             ctx.game(),
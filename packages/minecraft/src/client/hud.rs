@@ -0,0 +1,73 @@
+//! The heads-up display drawn over the world when playing, such as the hotbar and crosshair.
+
+use crate::{
+    client::item_grid::*,
+    game_data::per_item::PerItem,
+    item::*,
+    gui::prelude::*,
+};
+use graphics::prelude::*;
+
+
+/// Number of hotbar slots, which are the first inventory slots.
+pub const HOTBAR_SLOTS: usize = 9;
+
+
+/// State for the heads-up display.
+#[derive(Debug, Default)]
+pub struct Hud {
+    hotbar_slot_text_caches: [ItemSlotTextCacheNonhoverable; HOTBAR_SLOTS],
+}
+
+impl Hud {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    pub fn gui<'a>(
+        &'a mut self,
+        ctx: &GuiGlobalContext<'a>,
+        inventory_slots: &'a [Option<ItemStack>; 36],
+        item_mesh: &'a PerItem<Mesh>,
+        selected_hotbar_slot: usize,
+    ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> {
+        layer((
+            align(0.5,
+                logical_size([30.0, 30.0],
+                    &ctx.assets.hud_crosshair
+                )
+            ),
+            align([0.5, 1.0],
+                logical_size([182.0 * 2.0, 22.0 * 2.0],
+                    layer((
+                        &ctx.assets.hud_hotbar,
+                        logical_translate([selected_hotbar_slot as f32 * 40.0 - 2.0, -2.0],
+                            align(0.0,
+                                logical_size([24.0 * 2.0, 24.0 * 2.0],
+                                    &ctx.assets.hud_hotbar_selected
+                                )
+                            )
+                        ),
+                        margin(2.0, 2.0, 2.0, 2.0,
+                            align(0.0,
+                                item_grid_gui_block(
+                                    &inventory_slots[..HOTBAR_SLOTS],
+                                    ItemGridDefaultLayout {
+                                        pad: 4.0,
+                                        ..ItemGridDefaultLayout::new(HOTBAR_SLOTS)
+                                    },
+                                    item_grid_default_render_logic(
+                                        item_mesh,
+                                        &None,
+                                        self.hotbar_slot_text_caches.iter_mut(),
+                                    ),
+                                    (),
+                                )
+                            )
+                        ),
+                    ))
+                )
+            ),
+        ))
+    }
+}
@@ -0,0 +1,36 @@
+
+use super::*;
+use crate::{
+    client::network::*,
+    item::*,
+    message::*,
+};
+
+
+/// `ItemGridClickLogic` implementation for the output slot of a crafting grid, which asks the
+/// server to craft when clicked.
+pub fn item_grid_craft_click_logic<'a>(
+    connection: &'a Connection,
+) -> impl ItemGridClickLogic<Option<ItemStack>> + 'a {
+    ItemGridCraftClickLogic { connection }
+}
+
+struct ItemGridCraftClickLogic<'a> {
+    connection: &'a Connection,
+}
+
+impl<'a> ItemGridClickLogic<Option<ItemStack>> for ItemGridCraftClickLogic<'a> {
+    fn handle_click(
+        self,
+        _: usize,
+        item_slot: &Option<ItemStack>,
+        button: MouseButton,
+        _: &Arc<GameData>,
+    ) {
+        if item_slot.is_some() && (button == MouseButton::Left || button == MouseButton::Right) {
+            self.connection.send(UpMsg::PlayerMsg(PlayerMsg::SyncMenuMsg(
+                SyncMenuMsg::Craft(SyncMenuMsgCraft)
+            )));
+        }
+    }
+}
@@ -3,6 +3,7 @@
 mod layout_logic_default;
 mod render_logic_default;
 mod click_logic_default;
+mod click_logic_craft;
 
 pub use self::{
     layout_logic_default::{
@@ -16,6 +17,7 @@ pub use self::{
         ItemSlotRenderer,
     },
     click_logic_default::item_grid_default_click_logic,
+    click_logic_craft::item_grid_craft_click_logic,
 };
 
 use crate::{
@@ -95,6 +97,11 @@ pub trait ItemGridClickLogic<I> {
     );
 }
 
+/// Ignores clicks.
+impl<I> ItemGridClickLogic<I> for () {
+    fn handle_click(self, _: usize, _: &I, _: MouseButton, _: &Arc<GameData>) {}
+}
+
 
 // ==== gui block implementation ====
 
@@ -1,7 +1,10 @@
 
 use super::*;
 use crate::{
-    game_data::per_item::PerItem,
+    game_data::{
+        per_item::PerItem,
+        item_mesh_logic::ItemMeshLogic,
+    },
     item::*,
     gui::prelude::*,
 };
@@ -37,6 +40,8 @@ pub struct ItemSlotTextCache {
 }
 
 /// Cache for the layed-out text for rendering an item slot, excluding hover text.
+///
+/// Mut ref implements `ItemSlotRenderer`.
 #[derive(Debug, Default)]
 pub struct ItemSlotTextCacheNonhoverable {
     count_text: CountTextCache,
@@ -147,6 +152,34 @@ impl<'a> ItemSlotRenderer<'a> for &'a mut ItemSlotTextCache {
     }
 }
 
+impl<'a> ItemSlotRenderer<'a> for &'a mut ItemSlotTextCacheNonhoverable {
+    fn draw(
+        self,
+        ctx: GuiSpatialContext<'a>,
+        canvas: &mut Canvas2<'a, '_>,
+        _item_slot_idx: usize,
+        item_slot: &'a Option<ItemStack>,
+        size: f32,
+        scale: f32,
+        _is_cursor_over: bool,
+        item_mesh: &'a PerItem<Mesh>,
+        _held_item: &'a Option<ItemStack>,
+    ) {
+        let count_text = self.count_text.validate(item_slot, ctx);
+        draw_item_slot(
+            count_text,
+            None,
+            ctx,
+            canvas,
+            item_slot,
+            size,
+            scale,
+            item_mesh,
+            false,
+        );
+    }
+}
+
 // actual function that draws it after all text validation and everything else is dealt with
 fn draw_item_slot<'a>(
     count_text: Option<&'a mut GuiTextBlockInner>,
@@ -161,26 +194,36 @@ fn draw_item_slot<'a>(
 ) {
     // draw model
     if let Some(stack) = item_slot.as_ref() {
-        canvas.reborrow()
-            .translate(size / 2.0)
-            .scale(size * 0.616)
-            .translate(-0.5)
-            .begin_3d(
-                Mat4::new(
-                    1.0,  0.0,  0.0, 0.5,
-                    0.0, -1.0,  0.0, 0.5,
-                    0.0,  0.0, 0.01, 0.5,
-                    0.0,  0.0,  0.0, 1.0,
-                ),
-                Fog::None,
-            )
-            .rotate(Quaternion::rotation_x(-PI * 0.17))
-            .rotate(Quaternion::rotation_y(PI / 4.0))
-            .translate(-0.5)
-            .draw_mesh(
-                &item_mesh[stack.iid],
-                &ctx.assets().blocks,
-            );
+        if let &ItemMeshLogic::Flat { tex_index } = &ctx.game().items_mesh_logic[stack.iid] {
+            canvas.reborrow()
+                .draw_image(&ctx.assets().items, tex_index, size);
+        } else if let &ItemMeshLogic::FlatBlock { tex_index } =
+            &ctx.game().items_mesh_logic[stack.iid]
+        {
+            canvas.reborrow()
+                .draw_image(&ctx.assets().blocks, tex_index, size);
+        } else {
+            canvas.reborrow()
+                .translate(size / 2.0)
+                .scale(size * 0.616)
+                .translate(-0.5)
+                .begin_3d(
+                    Mat4::new(
+                        1.0,  0.0,  0.0, 0.5,
+                        0.0, -1.0,  0.0, 0.5,
+                        0.0,  0.0, 0.01, 0.5,
+                        0.0,  0.0,  0.0, 1.0,
+                    ),
+                    Fog::None,
+                )
+                .rotate(Quaternion::rotation_x(-PI * 0.17))
+                .rotate(Quaternion::rotation_y(PI / 4.0))
+                .translate(-0.5)
+                .draw_mesh(
+                    &item_mesh[stack.iid],
+                    &ctx.assets().blocks,
+                );
+        }
     }
     // draw count text
     if let Some(count_text) = count_text {
@@ -5,20 +5,25 @@ use crate::{
         channel::*,
         network::*,
         process_msg::process_pre_join_msg,
+        player_keys::player_keypair,
         gui_state::ClientGuiState,
         mesh_item::create_item_meshes,
+        mesh_block::{create_block_meshes, create_destroy_stage_meshes},
         *,
     },
     server::{
-        runner::ServerThread,
+        runner::{ServerThread, WorldMeta},
         save_db::SaveDb,
+        generate_chunk::WorldGenSettings,
         tick_mgr::TICK,
     },
     message::*,
+    auth::PlayerKeypair,
     gui::prelude::*,
     gui_state_loading::LoadingOneshot,
     gui_state_loading_failure::LoadingFailureMenu,
     sync_state_steve,
+    util_array::array_default,
 };
 use get_assets::DataDir;
 use std::{
@@ -53,7 +58,8 @@ pub enum ServerLocation {
     },
 }
 
-/// Spawn a thread to fully connect to and join a server in the background as a client.
+/// Spawn a thread to fully connect to and join a server in the background as a client, logging in
+/// with the given username and the client's keypair for it from the data dir.
 ///
 /// The returned handle can be used to poll for the constructed gui state, and aborts
 /// the joining process and disconnects from the server if dropped.
@@ -61,7 +67,8 @@ pub fn spawn_join_server_thread(
     server_location: ServerLocation,
     game: &Arc<GameData>,
     thread_pool: &ThreadPool,
-    log_in_msg: UpMsgLogIn,
+    username: String,
+    data_dir: DataDir,
     gpu_vec_ctx: AsyncGpuVecContext,
 ) -> Box<dyn LoadingOneshot> {
     let (client_send_1, client_recv) = channel();
@@ -77,7 +84,8 @@ pub fn spawn_join_server_thread(
             server_location,
             game,
             thread_pool,
-            log_in_msg,
+            username,
+            data_dir,
             client_send_1,
             client_recv,
             gpu_vec_ctx,
@@ -115,19 +123,29 @@ pub fn spawn_join_server_thread(
 }
 
 // fully connect to and join a server, blocking until success or error
+#[allow(clippy::too_many_arguments)]
 fn join_server(
     server_location: ServerLocation,
     game: Arc<GameData>,
     thread_pool: ThreadPool,
-    log_in_msg: UpMsgLogIn,
+    username: String,
+    data_dir: DataDir,
     client_send: ClientSender,
     client_recv: ClientReceiver,
     gpu_vec_ctx: AsyncGpuVecContext,
     cancel_connect: Arc<Notify>,
 ) -> Result<Client> {
+    let keypair = player_keypair(&data_dir, &username).context("failed to load player keypair")?;
     let (connection, server) =
-        connect_to_server(server_location, &game, &thread_pool, &client_send, cancel_connect)?;
-    let msg = log_in(&connection, &client_recv, log_in_msg)?;
+        connect_to_server(
+            server_location,
+            &game,
+            &thread_pool,
+            &client_send,
+            &data_dir,
+            cancel_connect,
+        )?;
+    let msg = log_in(&connection, &client_recv, username, &keypair)?;
     let mut client = construct_pre_join_client(
         msg,
         game,
@@ -148,6 +166,7 @@ fn connect_to_server(
     game: &Arc<GameData>,
     thread_pool: &ThreadPool,
     client_send: &ClientSender,
+    data_dir: &DataDir,
     cancel_connect: Arc<Notify>,
 ) -> Result<(Connection, Option<ServerThread>)> {
     Ok(match server_location {
@@ -156,7 +175,14 @@ fn connect_to_server(
             info!(?save_name, "starting internal server");
             let save_db = SaveDb::open(&save_name, &data_dir, game)
                 .context("failed to open save file")?;
-            let server = ServerThread::start(thread_pool.clone(), save_db, Arc::clone(game));
+            let world_meta = WorldMeta::load(&save_db, WorldGenSettings::new_random())
+                .context("failed to load world metadata")?;
+            let server = ServerThread::start(
+                thread_pool.clone(),
+                save_db,
+                world_meta,
+                Arc::clone(game),
+            );
             let connection = server.network_handle().in_mem_client(client_send.clone());
             (Connection::in_mem(connection), Some(server))
         }
@@ -165,7 +191,8 @@ fn connect_to_server(
             info!(?url, "connecting to server");
             let rt_2 = rt_1.clone();
             let connection = rt_1.block_on(async move {
-                let connect = Connection::connect(&url, client_send.clone(), &rt_2, game);
+                let connect =
+                    Connection::connect(&url, client_send.clone(), &rt_2, game, data_dir);
                 select_biased! {
                     _ = cancel_connect.notified().fuse() => Err(anyhow!("cancelled")),
                     result = connect.fuse() => result,
@@ -176,14 +203,20 @@ fn connect_to_server(
     })
 }
 
-// try send the LogIn message and receive the AcceptLogIn message
+// try send the LogIn message, respond to the AuthChallenge message, and receive the AcceptLogIn
+// message
 fn log_in(
     connection: &Connection,
     client_recv: &ClientReceiver,
-    log_in_msg: UpMsgLogIn,
+    username: String,
+    keypair: &PlayerKeypair,
 ) -> Result<DownMsgAcceptLogIn> {
-    info!("logging in");
-    connection.send(UpMsg::LogIn(log_in_msg));
+    info!(?username, "logging in");
+    let mut challenged = false;
+    connection.send(UpMsg::LogIn(UpMsgLogIn {
+        username: username.clone(),
+        public_key: keypair.public_key(),
+    }));
     Ok(loop {
         let event = client_recv.poll_blocking();
         trace!(?event, "client event (logging in)");
@@ -191,7 +224,19 @@ fn log_in(
             ClientEvent::AbortInit => bail!("client initialization aborted"),
             ClientEvent::Network(event) => match event {
                 NetworkEvent::Received(msg) => match msg {
-                    DownMsg::AcceptLogIn(msg) => break msg, 
+                    DownMsg::AuthChallenge(DownMsgAuthChallenge { challenge }) => {
+                        ensure!(!challenged, "server protocol violation");
+                        challenged = true;
+                        connection.send(UpMsg::AuthResponse(UpMsgAuthResponse {
+                            signature: keypair.sign_challenge(
+                                &challenge,
+                                &username,
+                                connection.tls_fingerprint(),
+                            ),
+                        }));
+                    }
+                    DownMsg::AcceptLogIn(msg) if challenged => break msg,
+                    DownMsg::Close(DownMsgClose { reason }) => bail!("disconnected: {}", reason),
                     _ => bail!("server protocol violation"),
                 }
                 NetworkEvent::Closed(msg) => bail!("server connection closed: {:?}", msg),
@@ -224,6 +269,8 @@ fn construct_pre_join_client(
         gpu_vec_ctx: gpu_vec_ctx.clone(),
         next_tick_num,
         next_tick_instant,
+        time_of_day: 0,
+        daylight_cycle: false,
         //just_finished_tick: None,
 
         caught_up_to: next_tick_instant - TICK,
@@ -232,8 +279,11 @@ fn construct_pre_join_client(
         chunk_newly_added: Default::default(),
 
         item_mesh: create_item_meshes(&game, &gpu_vec_ctx),
+        block_mesh: create_block_meshes(&game, &gpu_vec_ctx),
+        destroy_stage_mesh: create_destroy_stage_meshes(&gpu_vec_ctx),
         chunks: Default::default(),
         tile_blocks: Default::default(),
+        tile_light: Default::default(),
         chunk_mesh_mgr: ChunkMeshMgr::new(game, client_send, thread_pool, gpu_vec_ctx),
         players: Default::default(),
         player_username: Default::default(),
@@ -243,6 +293,8 @@ fn construct_pre_join_client(
         entities: Default::default(),
         chunk_steves: Default::default(),
         chunk_pigs: Default::default(),
+        chunk_falling_blocks: Default::default(),
+        chunk_items: Default::default(),
     }
 }
 
@@ -258,14 +310,22 @@ fn load_world(client: &mut PreJoinClient) -> Result<DownMsgFinalizeJoinGame> {
             .unwrap_or_else(|| {
                 // fall back to blocking, but always flush chunk mesh before doing so
                 trace!("flushing chunk mesh (about to block)");
-                client.chunk_mesh_mgr.flush_dirty(&client.chunks, &client.tile_blocks);
+                client.chunk_mesh_mgr.flush_dirty(
+                    &client.chunks,
+                    &client.tile_blocks,
+                    client.tile_light.chunk_light(),
+                );
                 flushed = now;
                 client.client_recv.poll_blocking()
             });
         // make sure to also flush intermittently even (especially) if never blocking for events
         if now - flushed > Duration::from_millis(10) {
             trace!("flushing chunk mesh (period elapsed");
-            client.chunk_mesh_mgr.flush_dirty(&client.chunks, &client.tile_blocks);
+            client.chunk_mesh_mgr.flush_dirty(
+                &client.chunks,
+                &client.tile_blocks,
+                client.tile_light.chunk_light(),
+            );
             flushed = now;
         }
 
@@ -287,14 +347,21 @@ fn load_world(client: &mut PreJoinClient) -> Result<DownMsgFinalizeJoinGame> {
                         ensure!(joining, "server protocol vioaltion");
                         break msg;
                     }
+                    DownMsg::Close(DownMsgClose { reason }) => bail!("disconnected: {}", reason),
                     _ => bail!("server protocol violation"),
                 }
                 NetworkEvent::Closed(msg) => bail!("server connection closed: {:?}", msg),
             }
             ClientEvent::ChunkMeshed { cc, ci, chunk_mesh } => {
                 client.connection.send(UpMsg::PreJoin(PreJoinUpMsg::AcceptMoreChunks(1)));
-                client.chunk_mesh_mgr
-                    .on_chunk_meshed(cc, ci, chunk_mesh, &client.chunks, &client.tile_blocks);
+                client.chunk_mesh_mgr.on_chunk_meshed(
+                    cc,
+                    ci,
+                    chunk_mesh,
+                    &client.chunks,
+                    &client.tile_blocks,
+                    client.tile_light.chunk_light(),
+                );
             }
         }
     })
@@ -307,6 +374,9 @@ fn finalize_join_game(
 ) -> Result<Client> {
     let DownMsgFinalizeJoinGame {
         self_player_idx,
+        pos,
+        yaw,
+        pitch,
         inventory_slots,
         held_slot,
     } = finalize_join_game_msg;
@@ -338,14 +408,20 @@ fn finalize_join_game(
     Ok(Client {
         pre_join: client,
         self_pk,
-        pos: Vec3::new(16.0, 48.0, 16.0),
-        yaw: f32::to_radians(45.0),
-        pitch: f32::to_radians(45.0),
+        pos,
+        yaw,
+        pitch,
         menu_mgr: Default::default(),
         inventory_slots: sync_state_inventory_slots::PlayerInventorySlots {
             inventory_slots,
             held_slot,
+            crafting_slots: array_default(),
         },
+        hud: Hud::new(),
+        chat: Chat::new(),
+        selected_hotbar_slot: 0,
+        dig: None,
+        next_dig: Instant::now(),
         steve_mesh,
     })
 }
@@ -0,0 +1,157 @@
+//! The chat menu, for typing chat messages.
+
+use crate::{
+    client::{
+        menu_mgr::*,
+        chat::Chat,
+    },
+    gui::prelude::*,
+    util_hex_color::hex_color,
+    message::*,
+};
+use std::time::Instant;
+
+
+/// Seconds the text cursor blinks on or off for.
+const BLINKEY: f32 = 1.0 / 3.0;
+
+/// Number of lines of chat scrolled by pressing page up or page down.
+const PAGE_LINES: i32 = 10;
+
+
+/// The chat menu, for typing chat messages. While it's open, the chat overlay shows scrollable
+/// chat history.
+#[derive(Debug)]
+pub struct ChatMenu {
+    text: String,
+    opened: Instant,
+    // text block for the text with the cursor blinker state it was built with, if built
+    text_block: Option<(GuiTextBlock<false>, bool)>,
+}
+
+impl ChatMenu {
+    /// Construct with the given text already typed.
+    pub fn new(text: &str) -> Self {
+        ChatMenu {
+            text: text.to_owned(),
+            opened: Instant::now(),
+            text_block: None,
+        }
+    }
+
+    pub fn gui<'a>(
+        &'a mut self,
+        ctx: &GuiGlobalContext<'a>,
+    ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> {
+        let blinker = self.opened.elapsed().as_secs_f32() % (BLINKEY * 2.0) < BLINKEY;
+        if self.text_block.as_ref().map(|&(_, built_blinker)| built_blinker) != Some(blinker) {
+            let mut text = self.text.clone();
+            if blinker {
+                text.push('_');
+            }
+            self.text_block = Some((GuiTextBlock::new(&GuiTextBlockConfig {
+                text: &text,
+                font: ctx.assets.font,
+                logical_font_size: 16.0,
+                color: hex_color(0xe0e0e0ff),
+                h_align: HAlign::Left,
+                v_align: VAlign::Center,
+                shadow: true,
+            }), blinker));
+        }
+        margin(4.0, 4.0, 0.0, 4.0,
+            v_align(1.0,
+                logical_height(24.0,
+                    layer((
+                        solid([0.0, 0.0, 0.0, 0.5]),
+                        h_margin(4.0, 4.0,
+                            align([0.0, 0.5],
+                                &mut self.text_block.as_mut().unwrap().0
+                            )
+                        ),
+                    ))
+                )
+            )
+        )
+    }
+
+    pub fn on_key_press(
+        &mut self,
+        ctx: &GuiWindowContext,
+        key: PhysicalKey,
+        typing: Option<TypingInput>,
+        menu_setter: MenuSetter,
+        client: MenuKeyPressClientBorrows,
+    ) {
+        if key == KeyCode::Escape {
+            menu_setter.clear_menu();
+        } else if key == KeyCode::Enter {
+            let text = self.text.trim();
+            if !text.is_empty() {
+                client.connection.send(UpMsg::PlayerMsg(PlayerMsg::Chat(PlayerMsgChat {
+                    text: text.to_owned(),
+                })));
+            }
+            menu_setter.clear_menu();
+        } else if key == KeyCode::Tab {
+            client.connection.send(UpMsg::PlayerMsg(PlayerMsg::TabComplete(PlayerMsgTabComplete {
+                text: self.text.clone(),
+            })));
+        } else if key == KeyCode::PageUp {
+            client.chat.scroll_by(PAGE_LINES);
+        } else if key == KeyCode::PageDown {
+            client.chat.scroll_by(-PAGE_LINES);
+        } else if key == KeyCode::KeyV && ctx.global().is_command_key_pressed() {
+            let pasted = ctx.global().clipboard.get();
+            self.type_text(&pasted);
+        } else if let Some(typing) = typing {
+            match typing {
+                TypingInput::Text(text) => self.type_text(text),
+                TypingInput::Control(TypingControl::Backspace) => {
+                    self.text.pop();
+                    self.text_block = None;
+                }
+                TypingInput::Control(_) => (),
+            }
+        }
+    }
+
+    /// Handle the server responding to a tab complete request. Ignored if the text has been edited
+    /// since the request was sent.
+    pub fn on_tab_completions(&mut self, text: &str, completions: Vec<String>, chat: &mut Chat) {
+        if text != self.text || completions.is_empty() {
+            return;
+        }
+        // replace the last word, not including the slash before a command name, with as much as
+        // all the completions have in common
+        let word_start = self.text.rfind(char::is_whitespace)
+            .map(|i| i + 1)
+            .unwrap_or(if self.text.starts_with('/') { 1 } else { 0 });
+        let mut common = completions[0].as_str();
+        for completion in &completions[1..] {
+            let len = common.chars().zip(completion.chars())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a.len_utf8())
+                .sum();
+            common = &common[..len];
+        }
+        self.text.truncate(word_start);
+        let room = MAX_CHAT_CHARS.saturating_sub(self.text.chars().count());
+        self.text.extend(common.chars().take(room));
+        if completions.len() == 1 {
+            if self.text.chars().count() < MAX_CHAT_CHARS {
+                self.text.push(' ');
+            }
+        } else {
+            chat.add_line(completions.join(" "));
+        }
+        self.text_block = None;
+    }
+
+    // append typed text, dropping characters which can't be sent and anything past the limit
+    fn type_text(&mut self, text: &str) {
+        let room = MAX_CHAT_CHARS.saturating_sub(self.text.chars().count());
+        self.text.extend(text.chars().filter(|&c| is_allowed_chat_char(c)).take(room));
+        self.text_block = None;
+    }
+}
@@ -0,0 +1,273 @@
+//! The menu for a container block, such as a chest or crafting table.
+
+use crate::{
+    client::{
+        menu_mgr::*,
+        item_grid::*,
+        crafting_grid::CraftingGrid,
+    },
+    game_data::container_logic::*,
+    gui::prelude::*,
+    util_hex_color::*,
+    util_array::*,
+    util_usize_lt::UsizeLt,
+    message::*,
+};
+use graphics::prelude::*;
+use chunk_data::*;
+use vek::*;
+
+
+/// The menu for a container block the player has open, laid out by its container logic.
+#[derive(Debug)]
+pub struct ContainerMenu {
+    gtc: Vec3<i64>,
+    bid: RawBlockId,
+    layout: ContainerMenuLayout,
+    title: GuiTextBlock<false>,
+    inventory: GuiTextBlock<false>,
+    grid_slot_text_caches: Vec<Vec<ItemSlotTextCache>>,
+    crafting_grid: Option<Box<CraftingGrid>>,
+    inventory_slot_text_caches: Box<[ItemSlotTextCache; 36]>,
+    held_slot_text_cache: ItemSlotTextCacheNonhoverable,
+}
+
+impl ContainerMenu {
+    /// Construct for the container block at the given tile. Returns `None` if it's not a
+    /// container.
+    pub fn new(ctx: &GuiGlobalContext, gtc: Vec3<i64>, bid: RawBlockId) -> Option<Self> {
+        let container = ctx.game.blocks_container_logic[bid].as_ref()?;
+        let layout = container.menu_layout();
+        let label = |text| GuiTextBlock::new(&GuiTextBlockConfig {
+            text,
+            font: ctx.assets.font,
+            logical_font_size: 16.0,
+            color: hex_color(0x404040FF),
+            h_align: HAlign::Left,
+            v_align: VAlign::Bottom,
+            shadow: false,
+        });
+        Some(ContainerMenu {
+            gtc,
+            bid,
+            title: label(layout.title),
+            inventory: label("Inventory"),
+            grid_slot_text_caches: layout.slot_grids.iter()
+                .map(|grid| grid.slots.clone().map(|_| Default::default()).collect())
+                .collect(),
+            crafting_grid: container.crafting_grid_width()
+                .map(|width| Box::new(CraftingGrid::new(width))),
+            layout,
+            inventory_slot_text_caches: Box::new(array_default()),
+            held_slot_text_cache: Default::default(),
+        })
+    }
+
+    /// Tile of the container this is the menu for.
+    pub fn gtc(&self) -> Vec3<i64> {
+        self.gtc
+    }
+
+    pub fn gui<'a>(
+        &'a mut self,
+        ctx: &GuiGlobalContext<'a>,
+        client: MenuGuiClientBorrows<'a>,
+    ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> {
+        // the container may be broken before the server invalidates the menu, in which case we
+        // show no container slots in the meantime
+        let container_tile = client.chunks.getter().gtc_get(self.gtc)
+            .map(|tile| tile.get(client.tile_blocks))
+            .filter(|tile| tile.get() == self.bid)
+            .zip(ctx.game.blocks_container_logic[self.bid].as_ref());
+        let container_slots = container_tile
+            .map(|(tile, container)| container.slots(tile))
+            .unwrap_or(&[]);
+        let container_properties = container_tile
+            .map(|(tile, container)| container.properties(tile))
+            .unwrap_or_default();
+        let (
+            hotbar_slot_text_caches,
+            non_hotbar_slot_text_caches,
+        ) = self.inventory_slot_text_caches.split_at_mut(9);
+        let size = self.layout.size;
+        let inventory_pos = self.layout.inventory_pos;
+        let title_x = self.layout.title_x;
+        let crafting_grid = self.crafting_grid.as_mut()
+            .zip(self.layout.crafting_grid.as_ref())
+            .map(|(crafting_grid, pos)|
+                crafting_grid.gui(ctx, &client, pos.grid_pos, pos.output_pos));
+        let slot_grids = self.layout.slot_grids.iter()
+            .zip(self.grid_slot_text_caches.iter_mut())
+            .map(|(grid, text_caches)| {
+                let start = grid.slots.start;
+                margin(grid.pos.x * 2.0, 0.0, grid.pos.y * 2.0, 0.0,
+                    align(0.0,
+                        item_grid_gui_block(
+                            container_slots.get(grid.slots.clone()).unwrap_or(&[]),
+                            ItemGridDefaultLayout::new(grid.columns),
+                            item_grid_default_render_logic(
+                                client.item_mesh,
+                                &client.inventory_slots.held_slot,
+                                text_caches.iter_mut(),
+                            ),
+                            item_grid_default_click_logic(
+                                client.connection,
+                                &client.inventory_slots.held_slot,
+                                move |i| UpItemSlotRef::Container(UsizeLt::new(start + i)),
+                            ),
+                        )
+                    )
+                )
+            })
+            .collect::<Vec<_>>();
+        align(0.5,
+            logical_size(size * 2.0,
+                layer((
+                    ContainerMenuBg {
+                        size,
+                        pieces: &self.layout.background,
+                    },
+                    ContainerMenuProgressBars {
+                        size,
+                        bars: &self.layout.progress_bars,
+                        properties: container_properties,
+                    },
+                    margin(title_x * 2.0, 0.0, 0.0, size.h * 2.0 - 28.0,
+                        align([0.0, 1.0],
+                            &mut self.title
+                        )
+                    ),
+                    margin(16.0, 0.0, 0.0, (size.h - inventory_pos.y) * 2.0 + 6.0,
+                        align([0.0, 1.0],
+                            &mut self.inventory
+                        )
+                    ),
+                    layer(slot_grids),
+                    crafting_grid,
+                    margin(inventory_pos.x * 2.0, 0.0, inventory_pos.y * 2.0, 0.0,
+                        align(0.0,
+                            item_grid_gui_block(
+                                &client.inventory_slots.inventory_slots[9..],
+                                ItemGridDefaultLayout::new(9),
+                                item_grid_default_render_logic(
+                                    client.item_mesh,
+                                    &client.inventory_slots.held_slot,
+                                    non_hotbar_slot_text_caches.iter_mut(),
+                                ),
+                                item_grid_default_click_logic(
+                                    client.connection,
+                                    &client.inventory_slots.held_slot,
+                                    |i| UpItemSlotRef::Inventory((i + 9).try_into().unwrap()),
+                                ),
+                            )
+                        )
+                    ),
+                    margin(inventory_pos.x * 2.0, 0.0, (inventory_pos.y + 58.0) * 2.0, 0.0,
+                        align(0.0,
+                            item_grid_gui_block(
+                                &client.inventory_slots.inventory_slots[..9],
+                                ItemGridDefaultLayout::new(9),
+                                item_grid_default_render_logic(
+                                    client.item_mesh,
+                                    &client.inventory_slots.held_slot,
+                                    hotbar_slot_text_caches.iter_mut(),
+                                ),
+                                item_grid_default_click_logic(
+                                    client.connection,
+                                    &client.inventory_slots.held_slot,
+                                    |i| UpItemSlotRef::Inventory(i.try_into().unwrap()),
+                                ),
+                            )
+                        )
+                    ),
+                    self.held_slot_text_cache.held_item_gui_block(
+                        client.item_mesh,
+                        &client.inventory_slots.held_slot,
+                    ),
+                ))
+            )
+        )
+    }
+
+    pub fn on_key_press(&mut self, key: PhysicalKey, menu_setter: MenuSetter) {
+        if key == KeyCode::Escape || key == KeyCode::KeyE {
+            menu_setter.clear_menu();
+        }
+    }
+}
+
+
+// gui block for the background of a container menu
+#[derive(Debug)]
+struct ContainerMenuBg<'a> {
+    // size of the menu in background texture pixels
+    size: Extent2<f32>,
+    pieces: &'a [ContainerMenuBgPiece],
+}
+
+impl<'a> GuiNode<'a> for SimpleGuiBlock<ContainerMenuBg<'a>> {
+    simple_blocks_cursor_impl!();
+
+    fn draw(self, ctx: GuiSpatialContext<'a>, canvas: &mut Canvas2<'a, '_>) {
+        let scale = self.size / self.inner.size;
+        for piece in self.inner.pieces {
+            draw_bg_piece(piece, scale, ctx, canvas);
+        }
+    }
+}
+
+// gui block for the progress bars of a container menu
+#[derive(Debug)]
+struct ContainerMenuProgressBars<'a> {
+    // size of the menu in background texture pixels
+    size: Extent2<f32>,
+    bars: &'a [ContainerProgressBar],
+    properties: Vec<u16>,
+}
+
+impl<'a> GuiNode<'a> for SimpleGuiBlock<ContainerMenuProgressBars<'a>> {
+    simple_blocks_cursor_impl!();
+
+    fn draw(self, ctx: GuiSpatialContext<'a>, canvas: &mut Canvas2<'a, '_>) {
+        // the container may be broken before the server invalidates the menu
+        if self.inner.properties.is_empty() {
+            return;
+        }
+        let scale = self.size / self.inner.size;
+        for bar in self.inner.bars {
+            // draw the filled part of the full bar's piece
+            let fraction = (bar.fraction)(&self.inner.properties).clamp(0.0, 1.0);
+            let mut piece = bar.piece.clone();
+            match bar.direction {
+                ProgressBarDirection::Right => {
+                    piece.src_size.w *= fraction;
+                }
+                ProgressBarDirection::Up => {
+                    let unfilled = piece.src_size.h * (1.0 - fraction);
+                    piece.src_pos.y += unfilled;
+                    piece.dst_pos.y += unfilled;
+                    piece.src_size.h -= unfilled;
+                }
+            }
+            draw_bg_piece(&piece, scale, ctx, canvas);
+        }
+    }
+}
+
+// draw a piece of a background texture, scaled from texture pixels
+fn draw_bg_piece<'a>(
+    piece: &ContainerMenuBgPiece,
+    scale: Extent2<f32>,
+    ctx: GuiSpatialContext<'a>,
+    canvas: &mut Canvas2<'a, '_>,
+) {
+    canvas.reborrow()
+        .translate(piece.dst_pos * Vec2::<f32>::from(scale))
+        .draw_image_uv(
+            (piece.image)(ctx.assets()),
+            0,
+            piece.src_size * scale,
+            piece.src_pos / Vec2::<f32>::from(piece.image_size),
+            piece.src_size / piece.image_size,
+        );
+}
@@ -4,6 +4,7 @@ use crate::{
     client::{
         menu_mgr::*,
         item_grid::*,
+        crafting_grid::CraftingGrid,
     },
     gui::prelude::*,
     util_hex_color::*,
@@ -16,8 +17,8 @@ use crate::{
 #[derive(Debug)]
 pub struct InventoryMenu {
     crafting: GuiTextBlock<false>,
-    hotbar_slot_text_caches: [ItemSlotTextCache; 9],
-    non_hotbar_slot_text_caches: [ItemSlotTextCache; 27],
+    crafting_grid: CraftingGrid,
+    inventory_slot_text_caches: Box<[ItemSlotTextCache; 36]>,
     held_slot_text_cache: ItemSlotTextCacheNonhoverable,
 }
 
@@ -34,8 +35,8 @@ impl InventoryMenu {
         });
         InventoryMenu {
             crafting,
-            hotbar_slot_text_caches: array_default(),
-            non_hotbar_slot_text_caches: array_default(),
+            crafting_grid: CraftingGrid::new(2),
+            inventory_slot_text_caches: Box::new(array_default()),
             held_slot_text_cache: Default::default(),
         }
     }
@@ -45,6 +46,12 @@ impl InventoryMenu {
         ctx: &GuiGlobalContext<'a>,
         client: MenuGuiClientBorrows<'a>,
     ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> {
+        let (
+            hotbar_slot_text_caches,
+            non_hotbar_slot_text_caches,
+        ) = self.inventory_slot_text_caches.split_at_mut(9);
+        let crafting_grid = self.crafting_grid
+            .gui(ctx, &client, [87.0, 25.0].into(), [143.0, 35.0].into());
         align(0.5,
             logical_size([176.0 * 2.0, 166.0 * 2.0],
                 layer((
@@ -54,6 +61,7 @@ impl InventoryMenu {
                             &mut self.crafting
                         )
                     ),
+                    crafting_grid,
                     margin(7.0 * 2.0, 0.0, 141.0 * 2.0, 0.0,
                         align(0.0,
                             item_grid_gui_block(
@@ -62,7 +70,7 @@ impl InventoryMenu {
                                 item_grid_default_render_logic(
                                     client.item_mesh,
                                     &client.inventory_slots.held_slot,
-                                    self.hotbar_slot_text_caches.iter_mut(),
+                                    hotbar_slot_text_caches.iter_mut(),
                                 ),
                                 item_grid_default_click_logic(
                                     client.connection,
@@ -80,7 +88,7 @@ impl InventoryMenu {
                                 item_grid_default_render_logic(
                                     client.item_mesh,
                                     &client.inventory_slots.held_slot,
-                                    self.non_hotbar_slot_text_caches.iter_mut(),
+                                    non_hotbar_slot_text_caches.iter_mut(),
                                 ),
                                 item_grid_default_click_logic(
                                     client.connection,
@@ -5,6 +5,10 @@ use crate::{
         network::Connection,
         menu_esc::EscMenu,
         menu_inventory::InventoryMenu,
+        menu_container::ContainerMenu,
+        menu_chat::ChatMenu,
+        chat::Chat,
+        client_loaded_chunks::ClientLoadedChunks,
     },
     gui::prelude::*,
     message::*,
@@ -12,6 +16,7 @@ use crate::{
     game_data::per_item::PerItem,
 };
 use graphics::prelude::*;
+use chunk_data::*;
 use std::cell::Cell;
 use anyhow::*;
 
@@ -21,6 +26,8 @@ use anyhow::*;
 pub enum Menu {
     EscMenu(EscMenu),
     InventoryMenu(InventoryMenu),
+    ContainerMenu(ContainerMenu),
+    ChatMenu(ChatMenu),
 }
 
 /// Client borrows that are given to the currently open menu when projecting the client as a gui.
@@ -28,6 +35,14 @@ pub struct MenuGuiClientBorrows<'a> {
     pub connection: &'a Connection,
     pub inventory_slots: &'a sync_state_inventory_slots::PlayerInventorySlots,
     pub item_mesh: &'a PerItem<Mesh>,
+    pub chunks: &'a ClientLoadedChunks,
+    pub tile_blocks: &'a PerChunk<ChunkBlocks>,
+}
+
+/// Client borrows that are given to the currently open menu when it handles a key press.
+pub struct MenuKeyPressClientBorrows<'a> {
+    pub connection: &'a Connection,
+    pub chat: &'a mut Chat,
 }
 
 /// Manager for the client having a menu open.
@@ -78,6 +93,11 @@ impl MenuMgr {
         self.menu.is_some()
     }
 
+    /// Whether the chat menu is open.
+    pub fn is_open_chat_menu(&self) -> bool {
+        matches!(self.menu, Some(Menu::ChatMenu(_)))
+    }
+
     /// Get the gui for any menu that may be open.
     pub fn gui<'a>(
         &'a mut self,
@@ -86,22 +106,26 @@ impl MenuMgr {
     ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> {
         self.menu.as_mut().map(|menu| {
             // darkened background
-            let darkened_bg = match menu {
-                _ => true,
-            };
+            let darkened_bg = !matches!(*menu, Menu::ChatMenu(_));
             let darkened_bg = if darkened_bg {
                 Some(solid([0.0, 0.0, 0.0, 1.0 - 0x2a as f32 / 0x97 as f32]))
             } else { None };
 
             // delegate
             let menu_setter = MenuSetter(&self.set_to);
-            let inner = match menu {
-                &mut Menu::EscMenu(ref mut inner) => GuiEither::A(
+            let inner = match *menu {
+                Menu::EscMenu(ref mut inner) => GuiEither::A(
                     inner.gui(menu_setter)
                 ),
-                &mut Menu::InventoryMenu(ref mut inner) => GuiEither::B(
+                Menu::InventoryMenu(ref mut inner) => GuiEither::B(GuiEither::A(
                     inner.gui(ctx.global(), client)
-                ),
+                )),
+                Menu::ContainerMenu(ref mut inner) => GuiEither::B(GuiEither::B(GuiEither::A(
+                    inner.gui(ctx.global(), client)
+                ))),
+                Menu::ChatMenu(ref mut inner) => GuiEither::B(GuiEither::B(GuiEither::B(
+                    inner.gui(ctx.global())
+                ))),
             };
 
             // compose
@@ -115,19 +139,36 @@ impl MenuMgr {
     /// Have the open menu handle this gui state event, if a menu is open.
     pub fn on_key_press(
         &mut self,
-        _: &GuiWindowContext,
+        ctx: &GuiWindowContext,
         key: PhysicalKey,
-        _: Option<TypingInput>,
+        typing: Option<TypingInput>,
+        client: MenuKeyPressClientBorrows,
     ) {
         if let Some(menu) = self.menu.as_mut() {
             let menu_setter = MenuSetter(&self.set_to);
-            match menu {
-                &mut Menu::EscMenu(ref mut inner) => inner.on_key_press(key, menu_setter),
-                &mut Menu::InventoryMenu(ref mut inner) => inner.on_key_press(key, menu_setter),
+            match *menu {
+                Menu::EscMenu(ref mut inner) => inner.on_key_press(key, menu_setter),
+                Menu::InventoryMenu(ref mut inner) => inner.on_key_press(key, menu_setter),
+                Menu::ContainerMenu(ref mut inner) => inner.on_key_press(key, menu_setter),
+                Menu::ChatMenu(ref mut inner) =>
+                    inner.on_key_press(ctx, key, typing, menu_setter, client),
             }
         }
     }
 
+    /// Handle the receipt of a TabCompletions message from the server. Ignored if the chat menu
+    /// is no longer open.
+    pub fn on_tab_completions_msg(
+        &mut self,
+        text: &str,
+        completions: Vec<String>,
+        chat: &mut Chat,
+    ) {
+        if let Some(Menu::ChatMenu(ref mut inner)) = self.menu {
+            inner.on_tab_completions(text, completions, chat);
+        }
+    }
+
     /// Handle the receipt of an InvalidateSyncMenu message from the server.
     ///
     /// Error indicates server protocol violation.
@@ -142,12 +183,20 @@ impl MenuMgr {
     }
 
     /// Handle menu gui effects.
-    pub fn process_gui_effects(&mut self, _: &GuiWindowContext, connection: &Connection) {
+    pub fn process_gui_effects(&mut self, ctx: &GuiWindowContext, connection: &Connection) {
         // setting the open menu (or lack thereof)
         if let Some(set_to) = self.set_to.take() {
-            let open_sync_menu_msg = set_to.as_ref().and_then(|menu| match menu {
-                &Menu::EscMenu(_) => None,
-                &Menu::InventoryMenu(_) => Some(PlayerMsgOpenSyncMenu::Inventory),
+            // free the mouse for the menu to use
+            if set_to.is_some() {
+                ctx.global().uncapture_mouse();
+            }
+
+            let open_sync_menu_msg = set_to.as_ref().and_then(|menu| match *menu {
+                Menu::EscMenu(_) => None,
+                Menu::InventoryMenu(_) => Some(PlayerMsgOpenSyncMenu::Inventory),
+                Menu::ContainerMenu(ref inner) =>
+                    Some(PlayerMsgOpenSyncMenu::Container(inner.gtc())),
+                Menu::ChatMenu(_) => None,
             });
 
             // set the open menu client side
@@ -196,3 +245,15 @@ impl From<InventoryMenu> for Menu {
         Menu::InventoryMenu(inner)
     }
 }
+
+impl From<ContainerMenu> for Menu {
+    fn from(inner: ContainerMenu) -> Self {
+        Menu::ContainerMenu(inner)
+    }
+}
+
+impl From<ChatMenu> for Menu {
+    fn from(inner: ChatMenu) -> Self {
+        Menu::ChatMenu(inner)
+    }
+}
@@ -0,0 +1,113 @@
+//! Meshing block models outside of the world, such as for falling blocks.
+
+use crate::{
+    game_data::{
+        logic::block_mesh_logic::*,
+        per_block::PerBlock,
+        content::door::DoorBlockMeta,
+        *,
+    },
+    client::mesh_tile::{axis_lighting, box_face_quad, cross_quads},
+    asset::consts::BTI_DESTROY_STAGE_0,
+};
+use graphics::prelude::*;
+use mesh_data::*;
+use chunk_data::*;
+use std::sync::Arc;
+use vek::*;
+
+
+/// Number of stages of the crack overlay drawn on a block being dug.
+pub const DESTROY_STAGES: usize = 10;
+
+
+/// Generate and upload the meshes for all block models.
+pub fn create_block_meshes(
+    game: &Arc<GameData>,
+    gpu_vec_ctx: &impl GpuVecContext,
+) -> PerBlock<Mesh> {
+    let mut block_meshes = PerBlock::new_no_default();
+    let mut mesh_buf = MeshData::new();
+    for bid in game.blocks.iter() {
+        mesh_block(&mut mesh_buf, bid, game);
+        block_meshes.set(bid, mesh_buf.upload(gpu_vec_ctx));
+        mesh_buf.clear();
+    }
+    block_meshes
+}
+
+/// Generate the mesh for a single block model, occupying the unit cube, unlit except for axis
+/// lighting.
+pub fn mesh_block(
+    mesh_buf: &mut MeshData,
+    bid: RawBlockId,
+    game: &Arc<GameData>,
+) {
+    match game.blocks_mesh_logic[bid] {
+        BlockMeshLogic::NoMesh => (),
+        BlockMeshLogic::Fluid(_) => (),
+        BlockMeshLogic::FullCube(BlockMeshLogicFullCube { tex_indices, .. }) =>
+            mesh_cube(mesh_buf, tex_indices),
+        BlockMeshLogic::Boxes(ref boxes) => mesh_boxes(mesh_buf, boxes),
+        // without a tile to read the meta of, draw the lower half of a closed door
+        BlockMeshLogic::Door => mesh_boxes(mesh_buf, &[DoorBlockMeta::default().mesh_box()]),
+        BlockMeshLogic::Cross(BlockMeshLogicCross { tex_index }) => {
+            for quad in cross_quads(tex_index, [Rgba::white(); 4]) {
+                mesh_buf.add_quad(&quad);
+            }
+        }
+    }
+}
+
+// mesh boxes within the unit cube, unlit except for axis lighting
+fn mesh_boxes(mesh_buf: &mut MeshData, boxes: &[BlockMeshLogicBox]) {
+    for mesh_box in boxes {
+        for face in FACES {
+            let brightness = axis_lighting(face);
+            mesh_buf.add_quad(&box_face_quad(
+                mesh_box.aa_box,
+                face,
+                mesh_box.tex_indices[face],
+                [Rgba::new(brightness, brightness, brightness, 1.0); 4],
+            ));
+        }
+    }
+}
+
+/// Generate the mesh for a unit cube with the given block texture on each face, unlit except for
+/// axis lighting.
+pub fn mesh_cube(mesh_buf: &mut MeshData, tex_indices: PerFace<usize>) {
+    for face in FACES {
+        let (pos_start, pos_exts) = face.quad_start_extents();
+        let pos_start = pos_start.to_poles().map(|pole| match pole {
+            Pole::Neg => 0.0,
+            Pole::Pos => 1.0,
+        });
+        let [
+            pos_ext_1,
+            pos_ext_2,
+        ] = pos_exts.map(|pos_ext| pos_ext.to_vec().map(|n| n as f32));
+        let brightness = axis_lighting(face);
+        mesh_buf.add_quad(&Quad {
+            pos_start,
+            pos_ext_1: pos_ext_1.into(),
+            pos_ext_2: pos_ext_2.into(),
+            tex_start: 0.0.into(),
+            tex_extent: 1.0.into(),
+            vert_colors: [Rgba::new(brightness, brightness, brightness, 1.0); 4],
+            tex_index: tex_indices[face],
+        });
+    }
+}
+
+/// Generate and upload the meshes for each stage of the crack overlay drawn on a block being dug.
+pub fn create_destroy_stage_meshes(gpu_vec_ctx: &impl GpuVecContext) -> Vec<Mesh> {
+    let mut mesh_buf = MeshData::new();
+    (0..DESTROY_STAGES)
+        .map(|stage| {
+            mesh_buf.clear();
+            mesh_cube(&mut mesh_buf, PerFace::repeat(BTI_DESTROY_STAGE_0 + stage));
+            mesh_buf.upload(gpu_vec_ctx)
+        })
+        .collect()
+}
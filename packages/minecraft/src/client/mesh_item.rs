@@ -7,7 +7,9 @@ use crate::{
         *,
     },
     item::*,
+    client::mesh_tile::box_face_quad,
 };
+use chunk_data::Face;
 use graphics::prelude::*;
 use mesh_data::*;
 use std::sync::Arc;
@@ -29,6 +31,11 @@ pub fn create_item_meshes(
     item_meshes
 }
 
+// shading of the faces of item models facing the left and right of the item slot
+const LEFT_SHADE: f32 = 0x48 as f32 / 0x8f as f32;
+const RIGHT_SHADE: f32 = 0x39 as f32 / 0x8f as f32;
+
+
 /// Generate the mesh for a single item model. 
 pub fn mesh_item(
     mesh_buf: &mut MeshData,
@@ -41,9 +48,6 @@ pub fn mesh_item(
             left_tex_index,
             right_tex_index,
         } => {
-            const LEFT_SHADE: f32 = 0x48 as f32 / 0x8f as f32;
-            const RIGHT_SHADE: f32 = 0x39 as f32 / 0x8f as f32;
-
             mesh_buf.add_quad(&Quad {
                 pos_start: [1.0, 1.0, 0.0].into(),
                 pos_ext_1: [-1.0, 0.0, 0.0].into(),
@@ -72,5 +76,22 @@ pub fn mesh_item(
                 tex_index: right_tex_index,
             });
         }
+        ItemMeshLogic::Boxes(boxes) => for mesh_box in boxes {
+            // only the faces which can face the camera in an item slot
+            for (face, shade) in [
+                (Face::PosY, 1.0),
+                (Face::NegZ, LEFT_SHADE),
+                (Face::PosX, RIGHT_SHADE),
+            ] {
+                mesh_buf.add_quad(&box_face_quad(
+                    mesh_box.aa_box,
+                    face,
+                    mesh_box.tex_indices[face],
+                    [Rgba::new(shade, shade, shade, 1.0); 4],
+                ));
+            }
+        },
+        // drawn as an image instead
+        ItemMeshLogic::Flat { .. } | ItemMeshLogic::FlatBlock { .. } => (),
     }
 }
@@ -1,8 +1,16 @@
 //! Meshing a tile.
 
-use crate::game_data::{
-    logic::block_mesh_logic::*,
-    *,
+use crate::{
+    game_data::{
+        logic::block_mesh_logic::*,
+        content::{
+            fluid::{FluidBlockMeta, FLUID_MAX_LEVEL},
+            door::DoorBlockMeta,
+        },
+        *,
+    },
+    lighting::{ChunkLight, MAX_LIGHT},
+    physics::aa_box::AaBox,
 };
 use mesh_data::*;
 use chunk_data::*;
@@ -12,6 +20,33 @@ use vek::*;
 
 const OCCLUSION: f32 = 0.25;
 
+// brightness of the darkest light level
+const MIN_BRIGHTNESS: f32 = 0.05;
+
+// opacity of translucent fluid faces
+const FLUID_ALPHA: f32 = 0.7;
+
+// distance cross quads are inset from the tile's corners along each horizontal axis
+const CROSS_INSET: f32 = 0.05;
+
+
+// convert a light level to a brightness multiplier, with the same curve as beta 1.0.2
+fn light_brightness(level: f32) -> f32 {
+    let darkness = 1.0 - level / MAX_LIGHT as f32;
+    (1.0 - darkness) / (darkness * 3.0 + 1.0) * (1.0 - MIN_BRIGHTNESS) + MIN_BRIGHTNESS
+}
+
+/// Brightness multiplier for faces facing in the given direction.
+pub fn axis_lighting(face: Face) -> f32 {
+    let axis_lighting = match face {
+        Face::PosY => 0,
+        Face::PosX | Face::NegX => 1,
+        Face::PosZ | Face::NegZ => 2,
+        Face::NegY => 3,
+    };
+    1.0 - axis_lighting as f32 * 0.07
+}
+
 
 /// Mesh a single tile in isolation, relative to its gtc.
 pub fn mesh_tile(
@@ -20,24 +55,63 @@ pub fn mesh_tile(
     game: &Arc<GameData>,
     getter: &Getter,
     tile_blocks: &PerChunk<ChunkBlocks>,
+    tile_light: &PerChunk<ChunkLight>,
 ) {
     let bid1 = tile.get(tile_blocks).get();
     match &game.blocks_mesh_logic[bid1] {
         &BlockMeshLogic::NoMesh => (),
+        &BlockMeshLogic::Fluid(mesh_logic) => mesh_fluid(
+            mesh_buf,
+            tile,
+            mesh_logic,
+            game,
+            getter,
+            tile_blocks,
+            tile_light,
+        ),
+        BlockMeshLogic::Boxes(boxes) => mesh_boxes(
+            mesh_buf,
+            tile,
+            boxes,
+            game,
+            getter,
+            tile_blocks,
+            tile_light,
+        ),
+        &BlockMeshLogic::Door => {
+            let meta = *tile.get(tile_blocks).raw_meta::<DoorBlockMeta>();
+            mesh_boxes(
+                mesh_buf,
+                tile,
+                &[meta.mesh_box()],
+                game,
+                getter,
+                tile_blocks,
+                tile_light,
+            );
+        }
+        &BlockMeshLogic::Cross(mesh_logic) => {
+            let light = tile_light.get(tile.cc, tile.ci).get(tile.lti);
+            let brightness = light_brightness(light as f32);
+            let vert_colors = [Rgba::new(brightness, brightness, brightness, 1.0); 4];
+            for quad in cross_quads(mesh_logic.tex_index, vert_colors) {
+                mesh_buf.add_quad(&quad);
+            }
+        }
         &BlockMeshLogic::FullCube(BlockMeshLogicFullCube { tex_indices, .. }) => {
+            let light = |tile: TileKey| tile_light.get(tile.cc, tile.ci).get(tile.lti) as f32;
+
             // mesh each face
             let gtc1 = tile.gtc();
             for face in FACES {
                 // skip if obscured
                 let gtc2 = gtc1 + face.to_vec();
-                if getter
-                    .gtc_get(gtc2)
-                    .map(|tile2| {
-                        let bid2 = tile2.get(tile_blocks).get();
-                        game.blocks_mesh_logic[bid2].obscures(-face)
-                    })
-                    .unwrap_or(true)
-                {
+                let tile2 = match getter.gtc_get(gtc2) {
+                    Some(tile2) => tile2,
+                    None => continue,
+                };
+                let bid2 = tile2.get(tile_blocks).get();
+                if game.blocks_mesh_logic[bid2].obscures(-face) {
                     continue;
                 }
 
@@ -45,7 +119,7 @@ pub fn mesh_tile(
                 let (pos_start, pos_exts) = face.quad_start_extents();
                 let mut vert_rgbs = [Rgb::white(); 4];
 
-                // ambient occlusion
+                // ambient occlusion and smooth lighting
                 for (corner, ext_coefs) in [
                     (0, [Pole::Neg, Pole::Neg]),
                     (1, [Pole::Pos, Pole::Neg]),
@@ -54,15 +128,15 @@ pub fn mesh_tile(
                 ] {
                     // calculate occlusion level from 0 through 3
                     let sides = [0, 1].map(|i| ext_coefs[i] * pos_exts[i]);
-                    let [a, b] = sides.map(|side| getter
-                        .gtc_get(gtc2 + side.to_vec())
+                    let side_tiles = sides.map(|side| getter.gtc_get(gtc2 + side.to_vec()));
+                    let corner_tile = getter.gtc_get(gtc2 + sides[0].to_vec() + sides[1].to_vec());
+                    let [a, b] = [0, 1].map(|i| side_tiles[i]
                         .map(|tile3| {
                             let bid3 = tile3.get(tile_blocks).get();
-                            game.blocks_mesh_logic[bid3].obscures(-side) as i32
+                            game.blocks_mesh_logic[bid3].obscures(-sides[i]) as i32
                         })
                         .unwrap_or(0));
-                    let c = getter
-                        .gtc_get(gtc2 + sides[0].to_vec() + sides[1].to_vec())
+                    let c = corner_tile
                         .map(|tile3| {
                             let bid3 = tile3.get(tile_blocks).get();
                             sides.into_iter()
@@ -72,19 +146,28 @@ pub fn mesh_tile(
                     let ab = a * b;
                     let occlusion = 3 * ab + (a + b + c) * (1 - ab);
 
+                    // average the light levels of the unobscured tiles touching the vertex
+                    let mut light_sum = light(tile2);
+                    let mut light_count = 1.0;
+                    for (tile3, obscured) in [
+                        (side_tiles[0], a),
+                        (side_tiles[1], b),
+                        (corner_tile, c.max(ab)),
+                    ] {
+                        if let (Some(tile3), 0) = (tile3, obscured) {
+                            light_sum += light(tile3);
+                            light_count += 1.0;
+                        }
+                    }
+
                     // light accordingly
+                    vert_rgbs[corner] *= light_brightness(light_sum / light_count);
                     vert_rgbs[corner] *= 1.0 - occlusion as f32 / 3.0 * OCCLUSION;
                 }
 
                 // axis lighting
-                let axis_lighting = match face {
-                    Face::PosY => 0,
-                    Face::PosX | Face::NegX => 1,
-                    Face::PosZ | Face::NegZ => 2,
-                    Face::NegY => 3,
-                };
                 for vert_rgb in &mut vert_rgbs {
-                    *vert_rgb *= 1.0 - axis_lighting as f32 * 0.07;
+                    *vert_rgb *= axis_lighting(face);
                 }
 
                 // add quad to mesh
@@ -116,3 +199,153 @@ pub fn mesh_tile(
         }
     }
 }
+
+// mesh a tile of a block meshed as boxes, relative to its gtc. faces are lit by the tile they face
+// without smooth lighting or ambient occlusion.
+fn mesh_boxes(
+    mesh_buf: &mut MeshData,
+    tile: TileKey,
+    boxes: &[BlockMeshLogicBox],
+    game: &Arc<GameData>,
+    getter: &Getter,
+    tile_blocks: &PerChunk<ChunkBlocks>,
+    tile_light: &PerChunk<ChunkLight>,
+) {
+    let light = |tile: TileKey| tile_light.get(tile.cc, tile.ci).get(tile.lti);
+    let gtc1 = tile.gtc();
+    for face in FACES {
+        // faces on the side of the tile are skipped if the neighbor isn't loaded, like full cubes'
+        let tile2 = getter.gtc_get(gtc1 + face.to_vec());
+        let obscured = tile2
+            .map(|tile2| game.blocks_mesh_logic[tile2.get(tile_blocks).get()].obscures(-face))
+            .unwrap_or(true);
+        let light2 = tile2.map(light).unwrap_or(0);
+        let brightness = light_brightness(light(tile).max(light2) as f32) * axis_lighting(face);
+        let vert_colors = [Rgba::new(brightness, brightness, brightness, 1.0); 4];
+        for mesh_box in boxes {
+            // skip faces on the side of the tile if the neighbor covers it
+            if obscured && mesh_box.aa_box.tile_side_area(face) > 0.0 {
+                continue;
+            }
+            let tex_index = mesh_box.tex_indices[face];
+            mesh_buf.add_quad(&box_face_quad(mesh_box.aa_box, face, tex_index, vert_colors));
+        }
+    }
+}
+
+/// Quad for the face of a box within the unit cube in the given direction, textured with the part
+/// of the texture corresponding to the part of the unit cube's side it covers.
+pub fn box_face_quad(
+    aa_box: AaBox,
+    face: Face,
+    tex_index: usize,
+    vert_colors: [Rgba<f32>; 4],
+) -> Quad {
+    let (pos_start, pos_exts) = face.quad_start_extents();
+    let size = Vec3::from(aa_box.ext);
+    let cube_start = pos_start.to_poles().map(|pole| match pole {
+        Pole::Neg => 0.0,
+        Pole::Pos => 1.0,
+    });
+    let pos_start = aa_box.pos + cube_start * size;
+    let exts = pos_exts.map(|pos_ext| pos_ext.to_vec().map(|n| n as f32));
+
+    // offset and length of the quad along each extent, relative to the unit cube's side
+    let [offset_1, offset_2] = exts.map(|ext| (pos_start - cube_start).dot(ext));
+    let [length_1, length_2] = exts.map(|ext| size.dot(ext.map(f32::abs)));
+    Quad {
+        pos_start,
+        pos_ext_1: (exts[0] * size).into(),
+        pos_ext_2: (exts[1] * size).into(),
+        tex_start: Vec2::new(offset_2, 1.0 - offset_1 - length_1),
+        tex_extent: Extent2::new(length_2, length_1),
+        vert_colors,
+        tex_index,
+    }
+}
+
+/// Quads for both sides of two quads crossing diagonally through the unit cube.
+pub fn cross_quads(tex_index: usize, vert_colors: [Rgba<f32>; 4]) -> [Quad; 4] {
+    const NEAR: f32 = CROSS_INSET;
+    const FAR: f32 = 1.0 - CROSS_INSET;
+    const WIDTH: f32 = FAR - NEAR;
+    [
+        ([NEAR, 0.0, NEAR], [WIDTH, 0.0, WIDTH]),
+        ([FAR, 0.0, FAR], [-WIDTH, 0.0, -WIDTH]),
+        ([NEAR, 0.0, FAR], [WIDTH, 0.0, -WIDTH]),
+        ([FAR, 0.0, NEAR], [-WIDTH, 0.0, WIDTH]),
+    ].map(|(pos_start, pos_ext_2)| Quad {
+        pos_start: pos_start.into(),
+        pos_ext_1: [0.0, 1.0, 0.0].into(),
+        pos_ext_2: pos_ext_2.into(),
+        tex_start: 0.0.into(),
+        tex_extent: 1.0.into(),
+        vert_colors,
+        tex_index,
+    })
+}
+
+// mesh a fluid tile, relative to its gtc
+fn mesh_fluid(
+    mesh_buf: &mut MeshData,
+    tile: TileKey,
+    mesh_logic: BlockMeshLogicFluid,
+    game: &Arc<GameData>,
+    getter: &Getter,
+    tile_blocks: &PerChunk<ChunkBlocks>,
+    tile_light: &PerChunk<ChunkLight>,
+) {
+    let bid1 = tile.get(tile_blocks).get();
+    let meta = *tile.get(tile_blocks).raw_meta::<FluidBlockMeta>();
+    let light = |tile: TileKey| tile_light.get(tile.cc, tile.ci).get(tile.lti);
+    let same_fluid = |tile2: TileKey| tile2.get(tile_blocks).get() == bid1;
+
+    // top is lowered the further the fluid has flowed, unless more of the fluid is above it
+    let gtc1 = tile.gtc();
+    let height =
+        if getter.gtc_get(gtc1 + Vec3::unit_y()).is_some_and(same_fluid) { 1.0 }
+        else {
+            let level = if meta.falling { 0 } else { meta.level };
+            (FLUID_MAX_LEVEL + 1 - level) as f32 / (FLUID_MAX_LEVEL + 2) as f32
+        };
+    let scale = Vec3::new(1.0, height, 1.0);
+    let alpha = if mesh_logic.translucent { FLUID_ALPHA } else { 1.0 };
+
+    // mesh each face
+    for face in FACES {
+        // skip if against the same fluid or obscured, unless it's a lowered top
+        let tile2 = match getter.gtc_get(gtc1 + face.to_vec()) {
+            Some(tile2) => tile2,
+            None => continue,
+        };
+        if same_fluid(tile2) {
+            continue;
+        }
+        let bid2 = tile2.get(tile_blocks).get();
+        if game.blocks_mesh_logic[bid2].obscures(-face) && !(face == Face::PosY && height < 1.0) {
+            continue;
+        }
+
+        // add quad to mesh
+        let (pos_start, pos_exts) = face.quad_start_extents();
+        let pos_start = pos_start.to_poles().map(|pole| match pole {
+            Pole::Neg => 0.0,
+            Pole::Pos => 1.0,
+        }) * scale;
+        let [
+            pos_ext_1,
+            pos_ext_2,
+        ] = pos_exts.map(|pos_ext| pos_ext.to_vec().map(|n| n as f32) * scale);
+        let brightness = light_brightness(light(tile).max(light(tile2)) as f32) * axis_lighting(face);
+        let quad = Quad {
+            pos_start,
+            pos_ext_1: pos_ext_1.into(),
+            pos_ext_2: pos_ext_2.into(),
+            tex_start: 0.0.into(),
+            tex_extent: 1.0.into(),
+            vert_colors: [Rgba::new(brightness, brightness, brightness, alpha); 4],
+            tex_index: mesh_logic.tex_index,
+        };
+        mesh_buf.extend(quad.to_vertices(), QUAD_INDICES);
+    }
+}
@@ -6,6 +6,7 @@ pub mod per_player;
 pub mod client_loaded_chunks;
 pub mod mesh_tile;
 pub mod mesh_item;
+pub mod mesh_block;
 pub mod chunk_mesh_mgr;
 pub mod join_server;
 pub mod process_msg;
@@ -14,6 +15,12 @@ pub mod item_grid;
 pub mod menu_mgr;
 pub mod menu_esc;
 pub mod menu_inventory;
+pub mod menu_container;
+pub mod menu_chat;
+pub mod crafting_grid;
+pub mod hud;
+pub mod chat;
+pub mod player_keys;
 
 use self::{
     channel::*,
@@ -28,12 +35,16 @@ use self::{
         ChunkMesh,
     },
     menu_mgr::MenuMgr,
+    hud::Hud,
+    chat::Chat,
 };
 use crate::{
     server::runner::ServerThread,
     thread_pool::ThreadPool,
+    lighting::LightEngine,
     game_data::{
         per_item::PerItem,
+        per_block::PerBlock,
         *,
     },
     sync_state_inventory_slots,
@@ -43,7 +54,7 @@ use chunk_data::*;
 use graphics::prelude::*;
 use std::{
     sync::Arc,
-    time::Instant,
+    time::{Instant, Duration},
 };
 use vek::*;
 
@@ -88,6 +99,10 @@ pub struct PreJoinClient {
     /// Scheduled start instant of next tick that the server will complete (in terms of the client
     /// receiving the consequential messages).
     pub next_tick_instant: Instant,
+    /// Ticks into the current day night cycle, as last set by the server and advanced since.
+    pub time_of_day: u64,
+    /// Whether the time of day advances upon each tick.
+    pub daylight_cycle: bool,
     
     pub caught_up_to: Instant,
     pub next_catch_up_tick: Instant,
@@ -103,10 +118,13 @@ pub struct PreJoinClient {
 
     // TODO: factor out these meshing things
     pub item_mesh: PerItem<Mesh>,
+    pub block_mesh: PerBlock<Mesh>,
+    pub destroy_stage_mesh: Vec<Mesh>,
 
     /// Client-side space of chunks.
     pub chunks: ClientLoadedChunks,
     pub tile_blocks: PerChunk<ChunkBlocks>,
+    pub tile_light: LightEngine,
     pub chunk_mesh_mgr: ChunkMeshMgr,
 
     /// Client-side space of players.
@@ -120,6 +138,8 @@ pub struct PreJoinClient {
     pub entities: LoadedEntities,
     pub chunk_steves: PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::SteveEntityState, sync_state_entities::SteveEntityClientState>>>,
     pub chunk_pigs: PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::PigEntityState, sync_state_entities::PigEntityClientState>>>,
+    pub chunk_falling_blocks: PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::FallingBlockEntityState, ()>>>,
+    pub chunk_items: PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::ItemEntityState, ()>>>,
 }
 
 /// Client state once the client has joined the game.
@@ -131,6 +151,29 @@ pub struct Client {
     pub pitch: f32,
     pub menu_mgr: MenuMgr,
     pub inventory_slots: sync_state_inventory_slots::PlayerInventorySlots,
+    pub hud: Hud,
+    pub chat: Chat,
+    /// Index of the selected hotbar slot, the item of which is used when placing and digging
+    /// blocks.
+    pub selected_hotbar_slot: usize,
+    /// The tile the player is digging, if any.
+    pub dig: Option<ClientDig>,
+    /// Digging can't start again until this instant, so that holding left click doesn't dig the
+    /// next tile before the server's response to finishing the last one arrives.
+    pub next_dig: Instant,
     // TODO: factor out these meshing things
     pub steve_mesh: Mesh,
 }
+
+/// Client-side state of the player digging a tile.
+#[derive(Debug, Copy, Clone)]
+pub struct ClientDig {
+    /// Tile being dug.
+    pub gtc: Vec3<i64>,
+    /// Hotbar slot of the item being dug with.
+    pub hotbar_slot: usize,
+    /// When digging started.
+    pub start: Instant,
+    /// How long digging takes to finish.
+    pub duration: Duration,
+}
@@ -0,0 +1,79 @@
+//! The servers the client has connected to over TLS, and how it trusts their certificates.
+//!
+//! When connecting to a server with a `wss://` url for the first time, the client trusts the
+//! server if its certificate is valid according to the system's certificate authorities, and
+//! remembers that it was, so that it never trusts a certificate that isn't for subsequent
+//! connections. Otherwise, such as if the certificate is self-signed, it trusts it on first use and
+//! pins its fingerprint, so that it only trusts that exact certificate for subsequent connections.
+//! Users can also pin a fingerprint the server operator gives them ahead of time by adding it to
+//! the file. See `util_net::cert_fingerprint`.
+
+use get_assets::DataDir;
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    fs::{self, File},
+    io::{
+        BufReader,
+        BufWriter,
+        ErrorKind,
+        Write,
+    },
+};
+use serde::{Serialize, Deserialize};
+use anyhow::*;
+
+
+pub const KNOWN_SERVERS_FILE_NAME: &str = "known_servers.json";
+
+
+/// The client's known servers file, mapping "host:port" to how the server is trusted.
+pub struct KnownServers {
+    path: PathBuf,
+    servers: BTreeMap<String, KnownServer>,
+}
+
+/// How the client trusts a known server's certificate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KnownServer {
+    /// Only trust the certificate with the given fingerprint.
+    Pinned(String),
+    /// Only trust certificates which are valid according to the system's certificate authorities.
+    CaVerified,
+}
+
+impl KnownServers {
+    /// Read the known servers file, or start empty if it doesn't exist yet.
+    pub fn load(data_dir: &DataDir) -> Result<Self> {
+        let path = data_dir.subdir(KNOWN_SERVERS_FILE_NAME);
+        let servers = match File::open(&path) {
+            Result::Ok(file) => serde_json::from_reader(BufReader::new(file))
+                .context("malformed known servers file")?,
+            Err(e) if e.kind() == ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(e).context("failed to open known servers file"),
+        };
+        Ok(KnownServers { path, servers })
+    }
+
+    /// Get how the server is trusted, if it's known.
+    pub fn get(&self, server: &str) -> Option<&KnownServer> {
+        self.servers.get(server)
+    }
+
+    /// Remember how the server is trusted and write the known servers file.
+    ///
+    /// The file is written to a temporary file which is then renamed over it, so that failing
+    /// partway through can't lose the pins already in it.
+    pub fn insert(&mut self, server: String, known: KnownServer) -> Result<()> {
+        self.servers.insert(server, known);
+        let tmp_path = self.path.with_extension("json.tmp");
+        let file = File::create(&tmp_path).context("failed to create known servers file")?;
+        let mut writer = BufWriter::new(file);
+        serde_json::to_writer_pretty(&mut writer, &self.servers)?;
+        writer.flush()?;
+        writer.get_ref().sync_all()?;
+        fs::rename(&tmp_path, &self.path).context("failed to replace known servers file")?;
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 //! Handle to the network IO connection to the server.
 
 mod ws;
+mod known_servers;
 
 use crate::{
     message::*,
@@ -18,6 +19,7 @@ use std::{
     fmt::{self, Formatter, Debug},
     time::Instant,
 };
+use get_assets::DataDir;
 use tokio::runtime::Handle;
 use anyhow::Error;
 
@@ -48,14 +50,17 @@ pub enum NetworkEvent {
 }
 
 impl Connection {
-    /// Establish a connection to a server at the given url.
+    /// Establish a connection to a server at the given url. For `wss://` urls, how known servers'
+    /// certificates are trusted is read from and written to the data dir. See the `known_servers`
+    /// module.
     pub async fn connect(
         url: &str,
         client_send: ClientSender,
         rt: &Handle,
         game: &Arc<GameData>,
+        data_dir: &DataDir,
     ) -> Result<Self, Error> {
-        let inner = ws::Connection::connect(url, client_send, rt, game).await?;
+        let inner = ws::Connection::connect(url, client_send, rt, game, data_dir).await?;
         Ok(Connection {
             inner: ConnectionInner::Ws(inner),
             last_up_msg_idx: Cell::new(0),
@@ -94,6 +99,15 @@ impl Connection {
         }
     }
 
+    /// Fingerprint of the TLS certificate the server presented, if the connection is encrypted with
+    /// TLS. See `util_net::cert_fingerprint`.
+    pub fn tls_fingerprint(&self) -> Option<&str> {
+        match self.inner {
+            ConnectionInner::Ws(ref inner) => inner.tls_fingerprint(),
+            ConnectionInner::InMem(_) => None,
+        }
+    }
+
     /// Relativize an instant against `est_server_t0`.
     ///
     /// Resultant `ServerRelTime` suitable for transmitting on this connection.
@@ -1,7 +1,10 @@
 //! Client-side connection implementation for websocket transport. See the corresponding server
 //! module for an explanation of the protocol.
 
-use super::*;
+use super::{
+    known_servers::*,
+    *,
+};
 use crate::{
     client::{
         channel::ClientSender,
@@ -13,8 +16,10 @@ use crate::{
     util_net::{
         try_close,
         some_or_pending,
+        cert_fingerprint,
     },
 };
+use get_assets::DataDir;
 use binschema::*;
 use std::{
     sync::Arc,
@@ -40,6 +45,7 @@ use tokio::{
         Notify,
         oneshot,
     },
+    net::TcpStream,
     runtime::Handle,
     time::sleep,
 };
@@ -49,7 +55,14 @@ use tokio_tungstenite::{
         error::Error as TungsteniteError,
         Message,
     },
-    connect_async_with_config,
+    WebSocketStream,
+    MaybeTlsStream,
+    connect_async_tls_with_config,
+    client_async_with_config,
+};
+use tokio_native_tls::{
+    TlsConnector,
+    TlsStream,
 };
 use futures::{
     stream::{Stream, StreamExt},
@@ -92,12 +105,17 @@ const RECEIVE_BUFFER_LIMIT: usize = 16 << 20;
 const SEND_CLOSE_TIMEOUT: Duration = Duration::from_secs(10);
 
 
+// websocket stream over TCP, possibly with TLS
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
 // connection inner type for websocket transport
 pub(super) struct Connection {
     // sender for queue of messages to be transmitted to server
     send_send: UnboundedSender<UpMsg>,
     // client estimate of instant when server sampled server_t0
     est_server_t0: Instant,
+    // fingerprint of the TLS certificate the server presented, if TLS is used
+    tls_fingerprint: Option<String>,
     // ensures proper shutdown when Connection dropped
     _shutdown_trigger: ConnectionShutdownTrigger,
 }
@@ -123,6 +141,7 @@ impl Connection {
         client_send: ClientSender,
         rt: &Handle,
         game: &Arc<GameData>,
+        data_dir: &DataDir,
     ) -> Result<Self, Error> {
         let (send_send, recv_send) = unbounded_channel();
         let shared_1 = Arc::new(ConnShared {
@@ -134,6 +153,7 @@ impl Connection {
         let (send_handshake_done, recv_handshake_done) = oneshot::channel();
         rt.spawn(recv_task(
             url.to_owned(),
+            data_dir.clone(),
             recv_send,
             client_send,
             rt.clone(),
@@ -142,7 +162,7 @@ impl Connection {
         ));
         // create shutdown trigger now to make this method cancel/error-safe
         let shutdown_trigger = ConnectionShutdownTrigger(shared_2);
-        let est_server_t0 = recv_handshake_done.await
+        let (est_server_t0, tls_fingerprint) = recv_handshake_done.await
             .map_err(|_| {
                 // this _should_ never happen, but best to be defensive
                 anyhow!("unexpectedly dropped Connection handshake_done oneshot")
@@ -150,6 +170,7 @@ impl Connection {
         Ok(Connection {
             send_send,
             est_server_t0,
+            tls_fingerprint,
             _shutdown_trigger: shutdown_trigger,
         })
     }
@@ -163,6 +184,11 @@ impl Connection {
     pub(super) fn est_server_t0(&self) -> Instant {
         self.est_server_t0
     }
+
+    // see outer type
+    pub(super) fn tls_fingerprint(&self) -> Option<&str> {
+        self.tls_fingerprint.as_deref()
+    }
 }
 
 impl Drop for ConnectionShutdownTrigger {
@@ -171,14 +197,112 @@ impl Drop for ConnectionShutdownTrigger {
     }
 }
 
+// connect and do the ws handshake. if the url is wss, first do the TLS handshake, deciding whether
+// to trust the server's certificate as described in the known_servers module before going any
+// further, and return its fingerprint.
+async fn connect(url: Url, data_dir: &DataDir) -> Result<(WsStream, Option<String>), Error> {
+    let config = Some(WebSocketConfig {
+        max_message_size: Some(RECEIVE_BUFFER_LIMIT),
+        ..Default::default()
+    });
+    if url.scheme() != "wss" {
+        let (ws, _) = connect_async_tls_with_config(url, config, true, None).await?;
+        return Ok((ws, None));
+    }
+
+    let host = url.host_str().unwrap_or_default();
+    let server = format!("{}:{}", host, url.port_or_known_default().unwrap());
+    let mut known_servers = KnownServers::load(data_dir)?;
+    let tls = match known_servers.get(&server) {
+        Some(KnownServer::Pinned(pinned)) => {
+            // only trust the pinned certificate
+            let tls = tls_connect(&server, host, unverified_connector()?).await??;
+            ensure!(
+                &peer_fingerprint(&tls)? == pinned,
+                "server's TLS certificate doesn't match the one pinned for it. if it was changed \
+                on purpose, remove {} from {}",
+                server, KNOWN_SERVERS_FILE_NAME,
+            );
+            tls
+        }
+        Some(KnownServer::CaVerified) => {
+            // only trust the certificate if the system does, as it did before
+            tls_connect(&server, host, verified_connector()?).await?
+                .map_err(|e| anyhow!(
+                    "server's TLS certificate isn't trusted by the system, although it was \
+                    before: {}. if it was changed on purpose, remove {} from {}",
+                    e, server, KNOWN_SERVERS_FILE_NAME,
+                ))?
+        }
+        None => match tls_connect(&server, host, verified_connector()?).await? {
+            // trust the certificate if the system does, and require that from now on
+            Ok(tls) => {
+                known_servers.insert(server, KnownServer::CaVerified)?;
+                tls
+            }
+            // otherwise, trust it on first use
+            Err(e) => {
+                warn!(
+                    %e,
+                    "server's TLS certificate not trusted by the system, trusting on first use",
+                );
+                let tls = tls_connect(&server, host, unverified_connector()?).await??;
+                let fingerprint = peer_fingerprint(&tls)?;
+                info!(%server, %fingerprint, "pinning server's TLS certificate");
+                known_servers.insert(server, KnownServer::Pinned(fingerprint))?;
+                tls
+            }
+        },
+    };
+
+    // only once the certificate is trusted, do the ws handshake
+    let fingerprint = peer_fingerprint(&tls)?;
+    let (ws, _) = client_async_with_config(url, MaybeTlsStream::NativeTls(tls), config).await?;
+    Ok((ws, Some(fingerprint)))
+}
+
+// connect over TCP and do the TLS handshake. the outer error is for failing to connect over TCP,
+// and the inner one for the TLS handshake failing, such as if the certificate isn't trusted.
+async fn tls_connect(
+    server: &str,
+    host: &str,
+    connector: TlsConnector,
+) -> Result<Result<TlsStream<TcpStream>, native_tls::Error>, Error> {
+    let tcp = TcpStream::connect(server).await?;
+    tcp.set_nodelay(true)?;
+    Ok(connector.connect(host, tcp).await)
+}
+
+// TLS connector which only accepts certificates the system trusts
+fn verified_connector() -> Result<TlsConnector, Error> {
+    Ok(native_tls::TlsConnector::new()?.into())
+}
+
+// TLS connector which accepts any certificate, for when it's checked against a pinned one instead
+fn unverified_connector() -> Result<TlsConnector, Error> {
+    let connector = native_tls::TlsConnector::builder()
+        .danger_accept_invalid_certs(true)
+        .danger_accept_invalid_hostnames(true)
+        .build()?;
+    Ok(connector.into())
+}
+
+// get the fingerprint of the certificate a TLS connected server presented
+fn peer_fingerprint(tls: &TlsStream<TcpStream>) -> Result<String, Error> {
+    let cert = tls.get_ref().peer_certificate()?
+        .ok_or_else(|| anyhow!("server presented no TLS certificate"))?;
+    Ok(cert_fingerprint(&cert.to_der()?))
+}
+
 // body of the receive task for a connection.
 async fn recv_task(
     url: String,
+    data_dir: DataDir,
     recv_send: UnboundedReceiver<UpMsg>,
     client_send: ClientSender,
     rt: Handle,
     shared: Arc<ConnShared>,
-    send_handshake_done: oneshot::Sender<Result<Instant, Error>>,
+    send_handshake_done: oneshot::Sender<Result<(Instant, Option<String>), Error>>,
 ) {
     // parse url
     let url = match parse_url(&url) {
@@ -192,14 +316,7 @@ async fn recv_task(
     info!("connecting to {}", url);
 
     // try to connect and do ws handshake
-    let connect = connect_async_with_config(
-        url,
-        Some(WebSocketConfig {
-            max_message_size: Some(RECEIVE_BUFFER_LIMIT),
-            ..Default::default()
-        }),
-        true
-    );
+    let connect = connect(url, &data_dir);
     let result = select_biased! {
         _ = shared.shutdown_recv.notified().fuse() => {
             // abandon attempt if connection dropped by user
@@ -208,8 +325,8 @@ async fn recv_task(
         }
         result = connect.fuse() => result
     };
-    let mut ws = match result {
-        Ok((ws, _)) => ws,
+    let (mut ws, tls_fingerprint) = match result {
+        Ok(outcome) => outcome,
         Err(e) => {
             // close connection on failure
             error!(%e, "error establishing ws connection");
@@ -239,7 +356,7 @@ async fn recv_task(
             }
         }
     };
-    let _ = send_handshake_done.send(Ok(est_server_t0));
+    let _ = send_handshake_done.send(Ok((est_server_t0, tls_fingerprint)));
     let (ws_send, mut ws_recv) = ws.split();
 
     // spawn the send task
@@ -0,0 +1,75 @@
+//! The keypairs the client logs in to servers with, one per username. See the `auth` module.
+
+use crate::auth::PlayerKeypair;
+use get_assets::DataDir;
+use std::{
+    collections::HashMap,
+    path::Path,
+    fs::{self, File, OpenOptions},
+    io::{
+        BufReader,
+        BufWriter,
+        ErrorKind,
+        Write,
+    },
+};
+use anyhow::*;
+
+
+pub const PLAYER_KEYS_FILE_NAME: &str = "player_keys.json";
+
+
+/// Get the client's keypair for logging in with the given username, generating and storing a new
+/// one if there isn't one yet.
+///
+/// Losing the stored keypair means losing the ability to log in as that player on servers which
+/// have seen its public key.
+pub fn player_keypair(data_dir: &DataDir, username: &str) -> Result<PlayerKeypair> {
+    let path = data_dir.subdir(PLAYER_KEYS_FILE_NAME);
+    let mut keys = read_keys(&path)?;
+    if let Some(secret) = keys.get(username) {
+        return Ok(PlayerKeypair::from_secret_bytes(secret));
+    }
+    info!(?username, "generating new player keypair");
+    let keypair = PlayerKeypair::generate();
+    keys.insert(username.to_owned(), keypair.to_secret_bytes());
+    write_keys(&path, &keys)?;
+    Ok(keypair)
+}
+
+// read the stored keys, or none if the file doesn't exist yet
+fn read_keys(path: &Path) -> Result<HashMap<String, [u8; 32]>> {
+    match File::open(path) {
+        Result::Ok(file) => serde_json::from_reader(BufReader::new(file))
+            .context("malformed player keys file"),
+        Err(e) if e.kind() == ErrorKind::NotFound => Ok(HashMap::new()),
+        Err(e) => Err(e).context("failed to open player keys file"),
+    }
+}
+
+// write the stored keys, such that only the current user can read them where supported. they're
+// written to a temporary file which is then renamed over the keys file, so that failing partway
+// through can't lose the existing keys.
+fn write_keys(path: &Path, keys: &HashMap<String, [u8; 32]>) -> Result<()> {
+    let tmp_path = path.with_extension("json.tmp");
+    let mut options = OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let file = options.open(&tmp_path).context("failed to create player keys file")?;
+    // the mode is only applied if the file is created, not if a stale one is truncated
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+    }
+    let mut writer = BufWriter::new(file);
+    serde_json::to_writer_pretty(&mut writer, keys)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    fs::rename(&tmp_path, path).context("failed to replace player keys file")?;
+    Ok(())
+}
@@ -5,6 +5,7 @@ use crate::{
     message::*,
     sync_state_entities::*,
     server::tick_mgr::TICK,
+    day_night::DAY_TICKS,
 };
 use chunk_data::*;
 use anyhow::{Result, ensure, anyhow};
@@ -28,9 +29,17 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
             client.caught_up_to = client.next_tick_instant - TICK;
             client.next_catch_up_tick = client.next_tick_instant;
             client.tick_just_finished = true;
+            if client.daylight_cycle {
+                client.time_of_day = (client.time_of_day + 1) % DAY_TICKS;
+            }
             /*
             client.just_finished_tick = Some(client.next_tick_instant);*/
         },
+        // set world clock
+        PreJoinDownMsg::SetTimeOfDay { time_of_day, daylight_cycle } => {
+            client.time_of_day = time_of_day;
+            client.daylight_cycle = daylight_cycle;
+        }
         // add player to world
         PreJoinDownMsg::AddPlayer(DownMsgAddPlayer {
             player_idx,
@@ -60,11 +69,32 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
             chunk_tile_blocks,
             steves,
             pigs,
+            falling_blocks,
+            items,
         }) => {
             let (ci, _getter) = client.chunks.on_add_chunk(chunk_idx, cc)?.get(&client.chunks);
             client.chunk_newly_added.add(cc, ci, true);
             client.tile_blocks.add(cc, ci, chunk_tile_blocks);
-            client.chunk_mesh_mgr.add_chunk(cc, ci, &client.chunks, &client.tile_blocks);
+            let mut light_changed = Vec::new();
+            client.tile_light.add_chunk(
+                cc,
+                ci,
+                &client.chunks.getter(),
+                &client.tile_blocks,
+                &client.game,
+                |tile| light_changed.push(tile),
+            );
+            client.chunk_mesh_mgr.add_chunk(
+                cc,
+                ci,
+                &client.chunks,
+                &client.tile_blocks,
+                client.tile_light.chunk_light(),
+            );
+            let getter = client.chunks.getter();
+            for tile in light_changed {
+                client.chunk_mesh_mgr.mark_adj_dirty(&getter, tile.gtc());
+            }
             client.entities
                 .add_chunk(
                     &mut client.chunk_steves,
@@ -81,6 +111,20 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
                         .map(|entity| (PigEntityClientState::new(cc, &entity), entity)),
                 )
                 .map_err(|_| anyhow!("server inserted entities with colliding uuids"))?;
+            client.entities
+                .add_chunk(
+                    &mut client.chunk_falling_blocks,
+                    cc, ci,
+                    falling_blocks.into_iter().map(|entity| ((), entity)),
+                )
+                .map_err(|_| anyhow!("server inserted entities with colliding uuids"))?;
+            client.entities
+                .add_chunk(
+                    &mut client.chunk_items,
+                    cc, ci,
+                    items.into_iter().map(|entity| ((), entity)),
+                )
+                .map_err(|_| anyhow!("server inserted entities with colliding uuids"))?;
         }
         // remove chunk from world
         PreJoinDownMsg::RemoveChunk(DownMsgRemoveChunk { chunk_idx }) => {
@@ -88,13 +132,31 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
             client.chunk_newly_added.remove(cc, ci);
             client.tile_blocks.remove(cc, ci);
             client.chunk_mesh_mgr.remove_chunk(cc, ci);
+            let getter = client.chunks.getter();
+            client.tile_light.remove_chunk(
+                cc,
+                ci,
+                &getter,
+                &client.tile_blocks,
+                &client.game,
+                |tile| client.chunk_mesh_mgr.mark_adj_dirty(&getter, tile.gtc()),
+            );
         }
         // set tile block
         PreJoinDownMsg::SetTileBlock { chunk_idx, lti, bid_meta } => {
             let (cc, ci, getter) = client.chunks.lookup(chunk_idx)?;
             let tile = TileKey { cc, ci, lti };
+            let old_bid = tile.get(&client.tile_blocks).get();
             tile.get(&mut client.tile_blocks).erased_set(bid_meta);
             client.chunk_mesh_mgr.mark_adj_dirty(&getter, tile.gtc());
+            client.tile_light.on_block_changed(
+                tile,
+                old_bid,
+                &getter,
+                &client.tile_blocks,
+                &client.game,
+                |tile| client.chunk_mesh_mgr.mark_adj_dirty(&getter, tile.gtc()),
+            );
         }
         // set char state
         PreJoinDownMsg::SetPlayerCharState { player_idx, pos, yaw, pitch } => {
@@ -125,6 +187,24 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
                         cc, ci,
                     )
                 },
+                AnyEntityState::FallingBlock(state) => {
+                    let entity = EntityData { uuid, rel_pos, state };
+                    client.entities.add_entity(
+                        &mut client.chunk_falling_blocks,
+                        (),
+                        entity,
+                        cc, ci,
+                    )
+                },
+                AnyEntityState::Item(state) => {
+                    let entity = EntityData { uuid, rel_pos, state };
+                    client.entities.add_entity(
+                        &mut client.chunk_items,
+                        (),
+                        entity,
+                        cc, ci,
+                    )
+                },
             }.map_err(|sync_state_entities::UuidCollision|
                 anyhow!("server added entity with duplicate uuid")
             )?;
@@ -136,6 +216,10 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
                     .remove_entity(&mut client.chunk_steves, cc, ci, vector_idx),
                 EntityType::Pig => client.entities
                     .remove_entity(&mut client.chunk_pigs, cc, ci, vector_idx),
+                EntityType::FallingBlock => client.entities
+                    .remove_entity(&mut client.chunk_falling_blocks, cc, ci, vector_idx),
+                EntityType::Item => client.entities
+                    .remove_entity(&mut client.chunk_items, cc, ci, vector_idx),
             }.map_err(|sync_state_entities::VectorIdxOutOfBounds|
                 anyhow!("server removed entity with out of bounds index")
             )?;
@@ -155,6 +239,12 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
                 EntityType::Pig => client.entities.move_entity(
                     &mut client.chunk_pigs, old_cc, old_ci, new_cc, new_ci, vector_idx,
                 ),
+                EntityType::FallingBlock => client.entities.move_entity(
+                    &mut client.chunk_falling_blocks, old_cc, old_ci, new_cc, new_ci, vector_idx,
+                ),
+                EntityType::Item => client.entities.move_entity(
+                    &mut client.chunk_items, old_cc, old_ci, new_cc, new_ci, vector_idx,
+                ),
             }.map_err(|sync_state_entities::VectorIdxOutOfBounds|
                 anyhow!("server moved entity with out of bounds index")
             )?;
@@ -182,6 +272,12 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
                     EntityType::Pig => edit_entity(
                         &mut client.chunk_pigs, cc, ci, vector_idx, |e, _| e.rel_pos = rel_pos
                     ),
+                    EntityType::FallingBlock => edit_entity(
+                        &mut client.chunk_falling_blocks, cc, ci, vector_idx, |e, _| e.rel_pos = rel_pos
+                    ),
+                    EntityType::Item => edit_entity(
+                        &mut client.chunk_items, cc, ci, vector_idx, |e, _| e.rel_pos = rel_pos
+                    ),
                 },
                 AnyEntityEdit::Steve(edit) => edit_entity(
                     &mut client.chunk_steves, cc, ci, vector_idx, |e, _| match edit {
@@ -195,6 +291,17 @@ pub fn process_pre_join_msg(client: &mut PreJoinClient, msg: PreJoinDownMsg) ->
                         PigEntityEdit::SetColor(v) => e.state.color = v,
                     }
                 ),
+                AnyEntityEdit::FallingBlock(edit) => edit_entity(
+                    &mut client.chunk_falling_blocks, cc, ci, vector_idx, |e, _| match edit {
+                        FallingBlockEntityEdit::SetVel(v) => e.state.vel = v,
+                    }
+                ),
+                AnyEntityEdit::Item(edit) => edit_entity(
+                    &mut client.chunk_items, cc, ci, vector_idx, |e, _| match edit {
+                        ItemEntityEdit::SetVel(v) => e.state.vel = v,
+                        ItemEntityEdit::SetStack(v) => e.state.stack = v,
+                    }
+                ),
             }?;
         },
     }
@@ -212,9 +319,37 @@ pub fn process_post_join_msg(client: &mut Client, msg: PostJoinDownMsg) -> Resul
             *match item_slot {
                 DownItemSlotRef::Held => &mut client.inventory_slots.held_slot,
                 DownItemSlotRef::Inventory(i) =>
-                    i.idx_mut(&mut client.inventory_slots.inventory_slots)
+                    i.idx_mut(&mut client.inventory_slots.inventory_slots),
+                DownItemSlotRef::Container { gtc, idx } => {
+                    // the container may have been unloaded or broken since this was sent
+                    let getter = client.pre_join.chunks.getter();
+                    let Some(tile) = getter.gtc_get(gtc) else { return Ok(()) };
+                    let bid = tile.get(&client.pre_join.tile_blocks).get();
+                    let Some(container) = client.pre_join.game.blocks_container_logic[bid].as_ref()
+                        else { return Ok(()) };
+                    let slots = container.slots_mut(tile.get(&mut client.pre_join.tile_blocks));
+                    let Some(slot) = slots.get_mut(idx.get()) else { return Ok(()) };
+                    slot
+                }
+                DownItemSlotRef::Crafting(i) =>
+                    i.idx_mut(&mut client.inventory_slots.crafting_slots),
             } = slot_content;
         }
+        // set container property
+        PostJoinDownMsg::SetContainerProperty { gtc, idx, value } => {
+            // the container may have been unloaded or broken since this was sent
+            let getter = client.pre_join.chunks.getter();
+            let Some(tile) = getter.gtc_get(gtc) else { return Ok(()) };
+            let bid = tile.get(&client.pre_join.tile_blocks).get();
+            let Some(container) = client.pre_join.game.blocks_container_logic[bid].as_ref()
+                else { return Ok(()) };
+            container.set_property(tile.get(&mut client.pre_join.tile_blocks), idx.get(), value);
+        }
+        // add chat line
+        PostJoinDownMsg::ChatLine { line } => client.chat.add_line(line),
+        PostJoinDownMsg::TabCompletions { text, completions } =>
+            client.menu_mgr.on_tab_completions_msg(&text, completions, &mut client.chat),
+        PostJoinDownMsg::Teleport { pos } => client.pos = pos,
     }
     Ok(())
 }
@@ -0,0 +1,12 @@
+//! The world clock, which the server owns and advances each tick and clients draw the sky from.
+
+
+/// Number of ticks in a full day night cycle.
+pub const DAY_TICKS: u64 = 24000;
+
+
+/// Convert a time of day in ticks to a point in the day night cycle, where 0 is sunrise, 0.25 is
+/// mid day, 0.5 is sun set, 0.75 is midnight, and 1 is the next sunrise.
+pub fn day_night_time(time_of_day: u64) -> f32 {
+    (time_of_day % DAY_TICKS) as f32 / DAY_TICKS as f32
+}
@@ -318,6 +318,20 @@ impl<T: GameBinschema> GameBinschema for Box<T> {
         T::decode(decoder, game).map(Box::new)
     }
 }
+
+impl<T: GameBinschema> GameBinschema for Arc<T> {
+    fn schema(game: &Arc<GameData>) -> Schema {
+        T::schema(game)
+    }
+
+    fn encode(&self, encoder: &mut Encoder<Vec<u8>>, game: &Arc<GameData>) -> Result<()> {
+        T::encode(&**self, encoder, game)
+    }
+
+    fn decode(decoder: &mut Decoder<Cursor<&[u8]>>, game: &Arc<GameData>) -> Result<Self> {
+        T::decode(decoder, game).map(Arc::new)
+    }
+}
 /*
 impl<T: GameBinschema> GameBinschema for RefCell<T> {
     fn schema(game: &Arc<GameData>) -> Schema {
@@ -13,6 +13,8 @@ impl ContentModule {
         builder.blocks_hitscan_logic.set(AIR, BlockHitscanLogic::Vacuous);
         builder.blocks_physics_logic.set(AIR, BlockPhysicsLogic::NoClip);
         builder.blocks_can_place_over.set(AIR, true);
+        builder.blocks_light_opacity.set(AIR, 0);
+        builder.blocks_hardness.set(AIR, None);
         ContentModule
     }
 }
@@ -0,0 +1,34 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_bedrock: BlockId<()>,
+    pub iid_bedrock: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_bedrock = builder.register_block(
+            "bedrock",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_BEDROCK),
+        );
+        builder.blocks_hardness.set(bid_bedrock, None);
+
+        let iid_bedrock = builder.register_item(
+            "bedrock",
+            #[cfg(feature = "client")]
+            LangKey::tile_bedrock_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_BEDROCK),
+        );
+        builder.items_place_logic.set(iid_bedrock, ItemPlaceLogic::block(bid_bedrock, ()));
+
+        ContentModule {
+            bid_bedrock,
+            iid_bedrock,
+        }
+    }
+}
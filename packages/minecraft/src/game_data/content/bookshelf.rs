@@ -0,0 +1,45 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_bookshelf: BlockId<()>,
+    pub iid_bookshelf: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_bookshelf = builder.register_block(
+            "bookshelf",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_faces({
+                let mut tex_indices = PerFace::repeat(BTI_BOOKSHELF);
+                tex_indices[Face::PosY] = BTI_PLANKS;
+                tex_indices[Face::NegY] = BTI_PLANKS;
+                tex_indices
+            }),
+        );
+        builder.blocks_hardness.set(bid_bookshelf, Some(1.5));
+        builder.blocks_dig_tool.set(bid_bookshelf, Some(BlockDigTool::faster(ToolKind::Axe)));
+
+        let iid_bookshelf = builder.register_item(
+            "bookshelf",
+            #[cfg(feature = "client")]
+            LangKey::tile_bookshelf_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::FullCube {
+                top_tex_index: BTI_PLANKS,
+                left_tex_index: BTI_BOOKSHELF,
+                right_tex_index: BTI_BOOKSHELF,
+            },
+        );
+        builder.items_place_logic.set(iid_bookshelf, ItemPlaceLogic::block(bid_bookshelf, ()));
+        builder.items_fuel_ticks.set(iid_bookshelf, 300);
+
+        ContentModule {
+            bid_bookshelf,
+            iid_bookshelf,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_brick: BlockId<()>,
+    pub iid_brick: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_brick = builder.register_block(
+            "brick",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_BRICK),
+        );
+        builder.blocks_hardness.set(bid_brick, Some(2.0));
+        builder.blocks_dig_tool.set(bid_brick, Some(BlockDigTool::required(ToolKind::Pickaxe, 0)));
+
+        let iid_brick = builder.register_item(
+            "brick",
+            #[cfg(feature = "client")]
+            LangKey::tile_brick_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_BRICK),
+        );
+        builder.items_place_logic.set(iid_brick, ItemPlaceLogic::block(bid_brick, ()));
+        builder.blocks_drop_logic.set(bid_brick, BlockDropLogic::item(iid_brick, ()));
+
+        ContentModule {
+            bid_brick,
+            iid_brick,
+        }
+    }
+}
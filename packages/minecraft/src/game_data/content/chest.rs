@@ -5,6 +5,7 @@ use crate::game_data::content_module_prelude::*;
 #[derive(Debug)]
 pub struct ContentModule {
     pub bid_chest: BlockId<ChestBlockMeta>,
+    pub iid_chest: ItemId<()>,
 }
 
 impl ContentModule {
@@ -20,9 +21,35 @@ impl ContentModule {
                 faces
             }),
         );
+        builder.blocks_hardness.set(bid_chest, Some(2.5));
+        builder.blocks_dig_tool.set(bid_chest, Some(BlockDigTool::faster(ToolKind::Axe)));
+
+        let iid_chest = builder.register_item(
+            "chest",
+            #[cfg(feature = "client")]
+            LangKey::tile_chest_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::FullCube {
+                top_tex_index: BTI_CHEST_TOP_BOTTOM,
+                left_tex_index: BTI_CHEST_FRONT,
+                right_tex_index: BTI_CHEST_SIDE,
+            },
+        );
+        builder.items_place_logic
+            .set(iid_chest, ItemPlaceLogic::block(bid_chest, ChestBlockMeta::default()));
+        builder.blocks_drop_logic.set(bid_chest, BlockDropLogic::item(iid_chest, ()));
+        builder.blocks_container_logic.set(bid_chest, Some(Box::new(ChestContainerLogic)));
+        builder.recipes.push(Recipe::shaped(
+            &["###", "# #", "###"],
+            &[('#', "planks")],
+            "chest",
+            1,
+        ));
+        builder.items_fuel_ticks.set(iid_chest, 300);
 
         ContentModule {
-            bid_chest
+            bid_chest,
+            iid_chest,
         }
     }
 }
@@ -41,152 +68,48 @@ impl Default for ChestBlockMeta {
     }
 }
 
-/*
-/// Game menu for an opened chest.
+/// Container logic for chest blocks.
 #[derive(Debug)]
-pub struct ChestMenu {
-    gtc: Vec3<i64>,
-    slots_state: Box<[ItemSlotGuiState; 27]>,
-}
+pub struct ChestContainerLogic;
 
-impl ChestMenu {
-    pub fn new(gtc: Vec3<i64>) -> ChestMenu {
-        ChestMenu {
-            gtc,
-            slots_state: Box::new(array_default()),
-        }
+impl ContainerLogic for ChestContainerLogic {
+    fn slots<'a>(&self, tile: TileBlockRead<'a>) -> &'a [Option<ItemStack>] {
+        &tile.raw_meta::<ChestBlockMeta>().slots
     }
 
-    pub fn gui<'a>(
-        &'a mut self,
-        args: MenuGuiParams<'a, '_>,
-    ) -> impl GuiBlock<'a, DimParentSets, DimParentSets> + 'a {
-        let (
-            inventory_slots_state_bottom,
-            inventory_slots_state_top,
-        ) = args.inventory_slots_state.split_at_mut(9);
-        // TODO: don't panic
-        let tile = args.getter.gtc_get(self.gtc).unwrap();
-        let meta = tile
-            .get(args.tile_blocks)
-            .try_meta(args.ctx.game().content.chest.bid_chest).unwrap();
-        align(0.5,
-            logical_size([352.0, 336.0],
-                layer((
-                    SingleChestBg,
-                    margin(14.0, 0.0, 34.0, 0.0,
-                        align(0.0,
-                            ItemGrid {
-                                slots: meta.slots.iter(),
-                                slots_state: self.slots_state.iter_mut(),
-                                click_logic: MultiplayerItemSlotClickLogic {
-                                    slot_offset: 36,
-                                    open_menu_msg_idx: args.open_menu_msg_idx.unwrap(),
-                                    connection: args.connection,
-                                    predictions_to_make: args.predictions_to_make,
-                                    idx_space: MultiplayerItemSlotIdxSpace::Chest {
-                                        ci: tile.ci,
-                                        lti: tile.lti,
-                                        meta,
-                                    },
-                                },
-                                grid_size: [9, 3].into(),
-                                config: ItemGridConfig::default(),
-                                items_mesh: args.items_mesh,
-                            }
-                        )
-                    ),
-                    margin(14.0, 0.0, 170.0, 0.0,
-                        align(0.0,
-                            ItemGrid {
-                                slots: &args.inventory_slots[9..],
-                                slots_state: inventory_slots_state_top.iter_mut(),
-                                click_logic: MultiplayerItemSlotClickLogic {
-                                    slot_offset: 9,
-                                    open_menu_msg_idx: args.open_menu_msg_idx.unwrap(),
-                                    connection: args.connection,
-                                    predictions_to_make: args.predictions_to_make,
-                                    idx_space: MultiplayerItemSlotIdxSpace::Inventory,
-                                },
-                                grid_size: [9, 3].into(),
-                                config: ItemGridConfig::default(),
-                                items_mesh: &args.items_mesh,
-                            }
-                        )
-                    ),
-                    margin(14.0, 0.0, 286.0, 0.0,
-                        align(0.0,
-                            ItemGrid {
-                                slots: &args.inventory_slots[..9],
-                                slots_state: inventory_slots_state_bottom.iter_mut(),
-                                click_logic: MultiplayerItemSlotClickLogic {
-                                    slot_offset: 0,
-                                    open_menu_msg_idx: args.open_menu_msg_idx.unwrap(),
-                                    connection: args.connection,
-                                    predictions_to_make: args.predictions_to_make,
-                                    idx_space: MultiplayerItemSlotIdxSpace::Inventory,
-                                },
-                                grid_size: [9, 1].into(),
-                                config: ItemGridConfig::default(),
-                                items_mesh: &args.items_mesh,
-                            }
-                        )
-                    ),
-                    HeldItemGuiBlock {
-                        held: args.held_item,
-                        held_state: args.held_item_state,
-                        items_mesh: &args.items_mesh,
-                    }
-                ))
-            )
-        )
+    fn slots_mut<'a>(&self, tile: TileBlockWrite<'a>) -> &'a mut [Option<ItemStack>] {
+        &mut tile.raw_meta::<ChestBlockMeta>().slots
     }
-}
-
-
-/// Gui block for a single chest background texture.
-#[derive(Debug, Clone)]
-pub struct SingleChestBg;
-
-impl<'a> GuiNode<'a> for SimpleGuiBlock<SingleChestBg> {
-    simple_blocks_cursor_impl!();
 
-    fn draw(self, ctx: GuiSpatialContext, canvas: &mut Canvas2) {
-        canvas.reborrow()
-            .draw_image_uv(
-                &ctx.assets().gui_chest,
-                0,
-                Extent2 {
-                    w: self.size.w,
-                    h: self.size.h * 71.0 / 168.0,
+    #[cfg(feature = "client")]
+    fn menu_layout(&self) -> ContainerMenuLayout {
+        // a single chest is the top and bottom of the container texture stitched together,
+        // skipping the rows a double chest would have
+        let piece = |src_y, h, dst_y| ContainerMenuBgPiece {
+            image: |assets| &assets.gui_chest,
+            image_size: [176.0, 222.0].into(),
+            src_pos: [0.0, src_y].into(),
+            src_size: [176.0, h].into(),
+            dst_pos: [0.0, dst_y].into(),
+        };
+        ContainerMenuLayout {
+            title: "Chest",
+            title_x: 8.0,
+            size: [176.0, 168.0].into(),
+            background: vec![
+                piece(0.0, 71.0, 0.0),
+                piece(125.0, 97.0, 71.0),
+            ],
+            slot_grids: vec![
+                ContainerSlotGrid {
+                    slots: 0..27,
+                    columns: 9,
+                    pos: [7.0, 17.0].into(),
                 },
-                0.0,
-                Extent2 { 
-                    w: 1.0,
-                    h: 71.0 / 222.0,
-                }
-            );
-        canvas.reborrow()
-            .translate(Vec2 {
-                x: 0.0,
-                y: self.size.h * 71.0 / 168.0,
-            })
-            .draw_image_uv(
-                &ctx.assets().gui_chest,
-                0,
-                Extent2 {
-                    w: self.size.w,
-                    h: self.size.h * 97.0 / 168.0,
-                },
-                Vec2 {
-                    x: 0.0,
-                    y: 125.0 / 222.0,
-                },
-                Extent2 {
-                    w: 1.0,
-                    h: 97.0 / 222.0,
-                },
-            );
+            ],
+            inventory_pos: [7.0, 85.0].into(),
+            progress_bars: Vec::new(),
+            crafting_grid: None,
+        }
     }
 }
-*/
\ No newline at end of file
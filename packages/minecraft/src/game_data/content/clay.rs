@@ -0,0 +1,36 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_clay: BlockId<()>,
+    pub iid_clay: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_clay = builder.register_block(
+            "clay",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_CLAY),
+        );
+        builder.blocks_hardness.set(bid_clay, Some(0.6));
+        builder.blocks_dig_tool.set(bid_clay, Some(BlockDigTool::faster(ToolKind::Shovel)));
+
+        let iid_clay = builder.register_item(
+            "clay",
+            #[cfg(feature = "client")]
+            LangKey::tile_clay_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_CLAY),
+        );
+        builder.items_place_logic.set(iid_clay, ItemPlaceLogic::block(bid_clay, ()));
+        builder.blocks_drop_logic.set(bid_clay, BlockDropLogic::item(iid_clay, ()));
+
+        ContentModule {
+            bid_clay,
+            iid_clay,
+        }
+    }
+}
@@ -0,0 +1,62 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_cobblestone: BlockId<()>,
+    pub iid_cobblestone: ItemId<()>,
+    pub bid_mossy_cobblestone: BlockId<()>,
+    pub iid_mossy_cobblestone: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_cobblestone = builder.register_block(
+            "cobblestone",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_COBBLESTONE),
+        );
+        builder.blocks_hardness.set(bid_cobblestone, Some(2.0));
+        builder.blocks_dig_tool
+            .set(bid_cobblestone, Some(BlockDigTool::required(ToolKind::Pickaxe, 0)));
+
+        let iid_cobblestone = builder.register_item(
+            "cobblestone",
+            #[cfg(feature = "client")]
+            LangKey::tile_stonebrick_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_COBBLESTONE),
+        );
+        builder.items_place_logic.set(iid_cobblestone, ItemPlaceLogic::block(bid_cobblestone, ()));
+        builder.blocks_drop_logic.set(bid_cobblestone, BlockDropLogic::item(iid_cobblestone, ()));
+
+        let bid_mossy_cobblestone = builder.register_block(
+            "mossy_cobblestone",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_MOSSY_COBBLESTONE),
+        );
+        builder.blocks_hardness.set(bid_mossy_cobblestone, Some(2.0));
+        builder.blocks_dig_tool
+            .set(bid_mossy_cobblestone, Some(BlockDigTool::required(ToolKind::Pickaxe, 0)));
+
+        let iid_mossy_cobblestone = builder.register_item(
+            "mossy_cobblestone",
+            #[cfg(feature = "client")]
+            LangKey::tile_stoneMoss_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_MOSSY_COBBLESTONE),
+        );
+        builder.items_place_logic
+            .set(iid_mossy_cobblestone, ItemPlaceLogic::block(bid_mossy_cobblestone, ()));
+        builder.blocks_drop_logic
+            .set(bid_mossy_cobblestone, BlockDropLogic::item(iid_mossy_cobblestone, ()));
+        
+        ContentModule {
+            bid_cobblestone,
+            iid_cobblestone,
+            bid_mossy_cobblestone,
+            iid_mossy_cobblestone,
+        }
+    }
+}
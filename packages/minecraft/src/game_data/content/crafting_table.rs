@@ -0,0 +1,101 @@
+//! The crafting table, which players can open a menu with a 3x3 crafting grid from.
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_crafting_table: BlockId<()>,
+    pub iid_crafting_table: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_crafting_table = builder.register_block(
+            "crafting_table",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_faces({
+                let mut faces = PerFace::repeat(BTI_CRAFTING_TABLE_SIDE);
+                faces[Face::PosY] = BTI_CRAFTING_TABLE_TOP;
+                faces[Face::NegY] = BTI_PLANKS;
+                faces[Face::NegZ] = BTI_CRAFTING_TABLE_FRONT;
+                faces[Face::NegX] = BTI_CRAFTING_TABLE_FRONT;
+                faces
+            }),
+        );
+        builder.blocks_hardness.set(bid_crafting_table, Some(2.5));
+        builder.blocks_dig_tool.set(bid_crafting_table, Some(BlockDigTool::faster(ToolKind::Axe)));
+        builder.blocks_container_logic
+            .set(bid_crafting_table, Some(Box::new(CraftingTableContainerLogic)));
+
+        let iid_crafting_table = builder.register_item(
+            "crafting_table",
+            #[cfg(feature = "client")]
+            LangKey::tile_workbench_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::FullCube {
+                top_tex_index: BTI_CRAFTING_TABLE_TOP,
+                left_tex_index: BTI_CRAFTING_TABLE_FRONT,
+                right_tex_index: BTI_CRAFTING_TABLE_SIDE,
+            },
+        );
+        builder.items_place_logic
+            .set(iid_crafting_table, ItemPlaceLogic::block(bid_crafting_table, ()));
+        builder.blocks_drop_logic
+            .set(bid_crafting_table, BlockDropLogic::item(iid_crafting_table, ()));
+        builder.recipes.push(Recipe::shaped(
+            &["##", "##"],
+            &[('#', "planks")],
+            "crafting_table",
+            1,
+        ));
+        builder.items_fuel_ticks.set(iid_crafting_table, 300);
+
+        ContentModule {
+            bid_crafting_table,
+            iid_crafting_table,
+        }
+    }
+}
+
+/// Container logic for crafting table blocks, which have no slots of their own, only a crafting
+/// grid.
+#[derive(Debug)]
+pub struct CraftingTableContainerLogic;
+
+impl ContainerLogic for CraftingTableContainerLogic {
+    fn slots<'a>(&self, _: TileBlockRead<'a>) -> &'a [Option<ItemStack>] {
+        &[]
+    }
+
+    fn slots_mut<'a>(&self, _: TileBlockWrite<'a>) -> &'a mut [Option<ItemStack>] {
+        &mut []
+    }
+
+    fn crafting_grid_width(&self) -> Option<usize> {
+        Some(3)
+    }
+
+    #[cfg(feature = "client")]
+    fn menu_layout(&self) -> ContainerMenuLayout {
+        ContainerMenuLayout {
+            title: "Crafting",
+            title_x: 28.0,
+            size: [176.0, 166.0].into(),
+            background: vec![ContainerMenuBgPiece {
+                image: |assets| &assets.gui_crafting,
+                image_size: [176.0, 166.0].into(),
+                src_pos: [0.0, 0.0].into(),
+                src_size: [176.0, 166.0].into(),
+                dst_pos: [0.0, 0.0].into(),
+            }],
+            slot_grids: Vec::new(),
+            inventory_pos: [7.0, 83.0].into(),
+            progress_bars: Vec::new(),
+            crafting_grid: Some(ContainerCraftingGrid {
+                grid_pos: [29.0, 16.0].into(),
+                output_pos: [123.0, 34.0].into(),
+            }),
+        }
+    }
+}
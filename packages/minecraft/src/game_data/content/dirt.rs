@@ -0,0 +1,36 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_dirt: BlockId<()>,
+    pub iid_dirt: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_dirt = builder.register_block(
+            "dirt",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_DIRT),
+        );
+        builder.blocks_hardness.set(bid_dirt, Some(0.5));
+        builder.blocks_dig_tool.set(bid_dirt, Some(BlockDigTool::faster(ToolKind::Shovel)));
+
+        let iid_dirt = builder.register_item(
+            "dirt",
+            #[cfg(feature = "client")]
+            LangKey::tile_dirt_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_DIRT),
+        );
+        builder.items_place_logic.set(iid_dirt, ItemPlaceLogic::block(bid_dirt, ()));
+        builder.blocks_drop_logic.set(bid_dirt, BlockDropLogic::item(iid_dirt, ()));
+        
+        ContentModule {
+            bid_dirt,
+            iid_dirt,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+//! Wooden doors, two tiles tall, which players open and close by using them.
+//!
+//! Each half of a door is its own door block, with its meta saying which half it is and whether
+//! the door is open. A closed door lies against the negative Z side of its tiles, and an open door
+//! against their negative X side. Doors are placed, opened, and closed both halves at once, and a
+//! half whose other half is gone removes itself.
+
+use crate::{
+    game_data::content_module_prelude::*,
+    server::process_player_msg::block_obstructed,
+};
+
+
+/// The part of a tile a closed door fills.
+pub const DOOR_CLOSED_BOX: AaBox = AaBox {
+    pos: Vec3 { x: 0.0, y: 0.0, z: 0.0 },
+    ext: Extent3 { w: 1.0, h: 1.0, d: 3.0 / 16.0 },
+};
+
+/// The part of a tile an open door fills.
+pub const DOOR_OPEN_BOX: AaBox = AaBox {
+    pos: Vec3 { x: 0.0, y: 0.0, z: 0.0 },
+    ext: Extent3 { w: 3.0 / 16.0, h: 1.0, d: 1.0 },
+};
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_door: BlockId<DoorBlockMeta>,
+    pub iid_wooden_door: ItemId<()>,
+}
+
+/// Metadata for door blocks, each of which is one half of a door.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, GameBinschema)]
+pub struct DoorBlockMeta {
+    /// Whether this is the upper half, rather than the lower half.
+    pub upper: bool,
+    /// Whether the door is open.
+    pub open: bool,
+}
+
+impl DoorBlockMeta {
+    /// The part of the tile this half of a door fills, for hitscans, physics, and meshing alike.
+    pub fn aa_box(self) -> &'static AaBox {
+        if self.open { &DOOR_OPEN_BOX } else { &DOOR_CLOSED_BOX }
+    }
+
+    /// Box to mesh this half of a door as.
+    #[cfg(feature = "client")]
+    pub fn mesh_box(self) -> BlockMeshLogicBox {
+        BlockMeshLogicBox {
+            aa_box: *self.aa_box(),
+            tex_indices: PerFace::repeat(if self.upper { BTI_DOOR_UPPER } else { BTI_DOOR_LOWER }),
+        }
+    }
+
+    // gtc of the other half of the door, given the gtc of this half
+    fn other_half_gtc(self, gtc: Vec3<i64>) -> Vec3<i64> {
+        if self.upper { gtc - Vec3::unit_y() } else { gtc + Vec3::unit_y() }
+    }
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_door = builder.register_block(
+            "door",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::Door,
+        );
+        builder.blocks_hitscan_logic.set(bid_door, BlockHitscanLogic::Door);
+        builder.blocks_physics_logic.set(bid_door, BlockPhysicsLogic::Door);
+        builder.blocks_update_logic.set(bid_door, BlockUpdateLogic::Custom(door_update));
+        builder.blocks_use_logic.set(bid_door, BlockUseLogic::Custom(door_use));
+        builder.blocks_light_opacity.set(bid_door, 0);
+        builder.blocks_hardness.set(bid_door, Some(3.0));
+        builder.blocks_dig_tool.set(bid_door, Some(BlockDigTool::faster(ToolKind::Axe)));
+
+        let iid_wooden_door = builder.register_item(
+            "wooden_door",
+            #[cfg(feature = "client")]
+            LangKey::item_doorWood_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::flat(ITI_WOODEN_DOOR),
+        );
+        builder.items_max_count.set(iid_wooden_door, 1.try_into().unwrap());
+        builder.items_place_logic.set(iid_wooden_door, ItemPlaceLogic::Custom(place_door));
+        builder.blocks_drop_logic.set(bid_door, BlockDropLogic::item(iid_wooden_door, ()));
+        builder.recipes.push(Recipe::shaped(
+            &["##", "##", "##"],
+            &[('#', "planks")],
+            "wooden_door",
+            1,
+        ));
+
+        ContentModule {
+            bid_door,
+            iid_wooden_door,
+        }
+    }
+}
+
+// place a closed door with its lower half at the given tile, if the tile above it can also be
+// placed over and neither half would be placed inside anything
+fn place_door(world: &mut SyncWorld, gtc: Vec3<i64>) -> bool {
+    let game = Arc::clone(&world.sync_ctx.game);
+    let bid = game.content.door.bid_door;
+    let (Some(lower), Some(upper)) = (
+        world.getter.gtc_get(gtc),
+        world.getter.gtc_get(gtc + Vec3::unit_y()),
+    ) else { return false };
+    if !game.blocks_can_place_over[upper.get(world.tile_blocks.as_ref()).get()]
+        || block_obstructed(world, gtc, &[DOOR_CLOSED_BOX])
+        || block_obstructed(world, gtc + Vec3::unit_y(), &[DOOR_CLOSED_BOX])
+    {
+        return false;
+    }
+    for (tile, upper) in [(lower, false), (upper, true)] {
+        let meta = DoorBlockMeta { upper, open: false };
+        tile.get(&mut world.tile_blocks).erased_set(ErasedBidMeta::new(bid, meta));
+    }
+    true
+}
+
+// remove a half of a door whose other half is gone
+fn door_update(world: &mut SyncWorld, tile: TileKey, _: BlockUpdateCause) {
+    let bid = world.sync_ctx.game.content.door.bid_door;
+    let meta = *tile.get(world.tile_blocks.as_ref()).meta(bid);
+    let Some(other) = world.getter.gtc_get(meta.other_half_gtc(tile.gtc())) else { return };
+    let other_meta = other.get(world.tile_blocks.as_ref()).try_meta(bid).copied();
+    if other_meta.is_none_or(|other_meta| other_meta.upper == meta.upper) {
+        tile.get(&mut world.tile_blocks).erased_set(ErasedBidMeta::new(AIR, ()));
+    }
+}
+
+// open or close a door, both halves at once
+fn door_use(world: &mut SyncWorld, tile: TileKey) {
+    let bid = world.sync_ctx.game.content.door.bid_door;
+    let meta = *tile.get(world.tile_blocks.as_ref()).meta(bid);
+    let open = !meta.open;
+    let other = world.getter.gtc_get(meta.other_half_gtc(tile.gtc()));
+    for tile in [Some(tile), other].into_iter().flatten() {
+        let Some(&meta) = tile.get(world.tile_blocks.as_ref()).try_meta(bid) else { continue };
+        let meta = DoorBlockMeta { open, ..meta };
+        tile.get(&mut world.tile_blocks).erased_set(ErasedBidMeta::new(bid, meta));
+    }
+}
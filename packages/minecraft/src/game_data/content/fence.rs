@@ -0,0 +1,58 @@
+//! Wooden fences, a narrow post standing in the middle of its tile.
+
+use crate::game_data::content_module_prelude::*;
+
+
+/// The part of a tile a fence post fills.
+pub const FENCE_BOX: AaBox = AaBox {
+    pos: Vec3 { x: 6.0 / 16.0, y: 0.0, z: 6.0 / 16.0 },
+    ext: Extent3 { w: 4.0 / 16.0, h: 1.0, d: 4.0 / 16.0 },
+};
+
+
+#[cfg(feature = "client")]
+fn fence_mesh_boxes() -> Vec<BlockMeshLogicBox> {
+    vec![BlockMeshLogicBox {
+        aa_box: FENCE_BOX,
+        tex_indices: PerFace::repeat(BTI_PLANKS),
+    }]
+}
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_fence: BlockId<()>,
+    pub iid_fence: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_fence = builder.register_block(
+            "fence",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::Boxes(fence_mesh_boxes()),
+        );
+        builder.blocks_hitscan_logic.set(bid_fence, BlockHitscanLogic::Boxes(vec![FENCE_BOX]));
+        builder.blocks_physics_logic.set(bid_fence, BlockPhysicsLogic::Boxes(vec![FENCE_BOX]));
+        builder.blocks_light_opacity.set(bid_fence, 0);
+        builder.blocks_hardness.set(bid_fence, Some(2.0));
+        builder.blocks_dig_tool.set(bid_fence, Some(BlockDigTool::faster(ToolKind::Axe)));
+
+        let iid_fence = builder.register_item(
+            "fence",
+            #[cfg(feature = "client")]
+            LangKey::tile_fence_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::Boxes(fence_mesh_boxes()),
+        );
+        builder.items_place_logic.set(iid_fence, ItemPlaceLogic::block(bid_fence, ()));
+        builder.blocks_drop_logic.set(bid_fence, BlockDropLogic::item(iid_fence, ()));
+        builder.recipes.push(Recipe::shaped(&["###", "###"], &[('#', "stick")], "fence", 2));
+        builder.items_fuel_ticks.set(iid_fence, 300);
+
+        ContentModule {
+            bid_fence,
+            iid_fence,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! The flower, a plant which can be walked through.
+
+use crate::game_data::content_module_prelude::*;
+
+
+/// The part of a tile in which a flower can be hit.
+pub const FLOWER_BOX: AaBox = AaBox {
+    pos: Vec3 { x: 0.3, y: 0.0, z: 0.3 },
+    ext: Extent3 { w: 0.4, h: 0.6, d: 0.4 },
+};
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_flower: BlockId<()>,
+    pub iid_flower: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_flower = builder.register_block(
+            "flower",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::cross(BTI_FLOWER),
+        );
+        builder.blocks_hitscan_logic.set(bid_flower, BlockHitscanLogic::Boxes(vec![FLOWER_BOX]));
+        builder.blocks_physics_logic.set(bid_flower, BlockPhysicsLogic::NoClip);
+        builder.blocks_light_opacity.set(bid_flower, 0);
+        builder.blocks_hardness.set(bid_flower, Some(0.0));
+
+        let iid_flower = builder.register_item(
+            "flower",
+            #[cfg(feature = "client")]
+            LangKey::tile_flower_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::flat_block(BTI_FLOWER),
+        );
+        builder.items_place_logic.set(iid_flower, ItemPlaceLogic::block(bid_flower, ()));
+        builder.blocks_drop_logic.set(bid_flower, BlockDropLogic::item(iid_flower, ()));
+
+        ContentModule {
+            bid_flower,
+            iid_flower,
+        }
+    }
+}
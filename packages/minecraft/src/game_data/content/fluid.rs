@@ -0,0 +1,268 @@
+//! Water and lava, which flow outwards from source blocks and fall downwards.
+//!
+//! Flow follows beta 1.0.2's rules, except that flowing fluid spreads evenly in all horizontal
+//! directions rather than preferring directions towards nearby drops. Each fluid tile, upon it or
+//! one of its neighbors changing, schedules itself a tick. Upon that tick firing, flowing fluid
+//! first re-derives its level from the fluid feeding it, and then fluid falls into the tile below
+//! if it can, or otherwise spreads sideways with its level increased by the fluid's decay.
+
+use crate::game_data::content_module_prelude::*;
+
+
+/// Maximum level of flowing fluid. Fluid which would flow with a higher level doesn't flow.
+pub const FLUID_MAX_LEVEL: u8 = 7;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_water: BlockId<FluidBlockMeta>,
+    pub iid_water: ItemId<()>,
+    pub bid_lava: BlockId<FluidBlockMeta>,
+    pub iid_lava: ItemId<()>,
+}
+
+/// Metadata for fluid blocks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default, GameBinschema)]
+pub struct FluidBlockMeta {
+    /// 0 for a source, otherwise how far the fluid has decayed while flowing from one, up to
+    /// `FLUID_MAX_LEVEL`.
+    pub level: u8,
+    /// Whether the fluid is falling, being fed from the same fluid directly above it. Falling
+    /// fluid spreads as if it were level 0, but is not a source.
+    pub falling: bool,
+}
+
+impl FluidBlockMeta {
+    /// Meta for a source block.
+    pub const SOURCE: Self = FluidBlockMeta { level: 0, falling: false };
+
+    /// Whether this is a source block.
+    pub fn is_source(self) -> bool {
+        self == Self::SOURCE
+    }
+
+    // level to spread onwards from, with falling fluid spreading like a source
+    fn spread_level(self) -> u8 {
+        if self.falling { 0 } else { self.level }
+    }
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let (bid_water, iid_water) = register_fluid(
+            builder,
+            "water",
+            #[cfg(feature = "client")]
+            LangKey::tile_water_name,
+            #[cfg(feature = "client")]
+            BTI_WATER,
+            #[cfg(feature = "client")]
+            true,
+        );
+        builder.blocks_light_opacity.set(bid_water, 3);
+        builder.blocks_update_logic.set(bid_water, BlockUpdateLogic::Custom(water_update));
+
+        let (bid_lava, iid_lava) = register_fluid(
+            builder,
+            "lava",
+            #[cfg(feature = "client")]
+            LangKey::tile_lava_name,
+            #[cfg(feature = "client")]
+            BTI_LAVA,
+            #[cfg(feature = "client")]
+            false,
+        );
+        builder.blocks_light_emission.set(bid_lava, MAX_LIGHT);
+        builder.blocks_update_logic.set(bid_lava, BlockUpdateLogic::Custom(lava_update));
+
+        ContentModule {
+            bid_water,
+            iid_water,
+            bid_lava,
+            iid_lava,
+        }
+    }
+
+    /// Whether the given block is any fluid.
+    pub fn is_fluid(&self, bid: RawBlockId) -> bool {
+        bid == self.bid_water || bid == self.bid_lava
+    }
+}
+
+// register a fluid block and its item, which places a source
+fn register_fluid(
+    builder: &mut GameDataBuilder,
+    machine_name: &str,
+    #[cfg(feature = "client")]
+    name: LangKey,
+    #[cfg(feature = "client")]
+    tex_index: usize,
+    #[cfg(feature = "client")]
+    translucent: bool,
+) -> (BlockId<FluidBlockMeta>, ItemId<()>) {
+    let bid = builder.register_block(
+        machine_name,
+        #[cfg(feature = "client")]
+        BlockMeshLogic::Fluid(BlockMeshLogicFluid { tex_index, translucent }),
+    );
+    builder.blocks_hitscan_logic.set(bid, BlockHitscanLogic::Vacuous);
+    builder.blocks_physics_logic.set(bid, BlockPhysicsLogic::NoClip);
+    builder.blocks_can_place_over.set(bid, true);
+    builder.blocks_hardness.set(bid, None);
+    let iid = builder.register_item(
+        machine_name,
+        #[cfg(feature = "client")]
+        name,
+        #[cfg(feature = "client")]
+        ItemMeshLogic::basic_cube(tex_index),
+    );
+    builder.items_place_logic.set(iid, ItemPlaceLogic::block(bid, FluidBlockMeta::SOURCE));
+    (bid, iid)
+}
+
+// parameters which differ between fluids
+#[derive(Copy, Clone)]
+struct Fluid {
+    bid: BlockId<FluidBlockMeta>,
+    // how much the level increases per tile of horizontal flow
+    decay: u8,
+    // number of ticks between a change and the fluid reacting to it
+    tick_rate: u64,
+    // whether flowing fluid between two sources over a solid floor becomes a source
+    infinite: bool,
+}
+
+fn water_update(world: &mut SyncWorld, tile: TileKey, cause: BlockUpdateCause) {
+    let fluid = Fluid {
+        bid: world.sync_ctx.game.content.fluid.bid_water,
+        decay: 1,
+        tick_rate: 5,
+        infinite: true,
+    };
+    fluid_update(world, tile, cause, fluid);
+}
+
+fn lava_update(world: &mut SyncWorld, tile: TileKey, cause: BlockUpdateCause) {
+    let fluid = Fluid {
+        bid: world.sync_ctx.game.content.fluid.bid_lava,
+        decay: 2,
+        tick_rate: 30,
+        infinite: false,
+    };
+    fluid_update(world, tile, cause, fluid);
+}
+
+fn fluid_update(world: &mut SyncWorld, tile: TileKey, cause: BlockUpdateCause, fluid: Fluid) {
+    match cause {
+        BlockUpdateCause::Changed => world.sync_ctx.schedule_tick(tile, fluid.tick_rate),
+        BlockUpdateCause::Scheduled => fluid_flow(world, tile, fluid),
+    }
+}
+
+// get the fluid meta at the tile if it's loaded and the given fluid
+fn get_fluid(world: &SyncWorld, gtc: Vec3<i64>, fluid: Fluid) -> Option<FluidBlockMeta> {
+    let tile = world.getter.gtc_get(gtc)?;
+    let read = tile.get(world.tile_blocks.as_ref());
+    read.try_meta(fluid.bid).copied()
+}
+
+// do a scheduled tick for a fluid tile
+fn fluid_flow(world: &mut SyncWorld, tile: TileKey, fluid: Fluid) {
+    let gtc = tile.gtc();
+    let Some(mut meta) = get_fluid(world, gtc, fluid) else { return };
+
+    // flowing fluid re-derives its state from the fluid feeding it, drying up if there is none
+    if !meta.is_source() {
+        let fed = fed_meta(world, gtc, fluid);
+        if fed != Some(meta) {
+            let bid_meta = match fed {
+                Some(fed) => ErasedBidMeta::new(fluid.bid, fed),
+                None => ErasedBidMeta::new(AIR, ()),
+            };
+            tile.get(&mut world.tile_blocks).erased_set(bid_meta);
+            match fed {
+                Some(fed) => meta = fed,
+                None => return,
+            }
+        }
+    }
+
+    // fall if possible, otherwise spread sideways
+    let below = gtc - Vec3::unit_y();
+    let falling = FluidBlockMeta { level: 0, falling: true };
+    if can_flow_into(world, below, fluid, falling) {
+        set_fluid(world, below, fluid, falling);
+    } else if meta.is_source() || blocks_flow(world, below, fluid) {
+        let level = meta.spread_level() + fluid.decay;
+        if level > FLUID_MAX_LEVEL {
+            return;
+        }
+        let spread = FluidBlockMeta { level, falling: false };
+        for face in [Face::PosX, Face::NegX, Face::PosZ, Face::NegZ] {
+            let gtc2 = gtc + face.to_vec();
+            if can_flow_into(world, gtc2, fluid, spread) {
+                set_fluid(world, gtc2, fluid, spread);
+            }
+        }
+    }
+}
+
+// the state non-source fluid at the tile should have based on the fluid around it, if any
+fn fed_meta(world: &SyncWorld, gtc: Vec3<i64>, fluid: Fluid) -> Option<FluidBlockMeta> {
+    if get_fluid(world, gtc + Vec3::unit_y(), fluid).is_some() {
+        return Some(FluidBlockMeta { level: 0, falling: true });
+    }
+    let mut min_level = None;
+    let mut sources = 0;
+    for face in [Face::PosX, Face::NegX, Face::PosZ, Face::NegZ] {
+        if let Some(meta2) = get_fluid(world, gtc + face.to_vec(), fluid) {
+            if meta2.is_source() {
+                sources += 1;
+            }
+            let level = meta2.spread_level();
+            min_level = Some(min_level.map_or(level, |min_level: u8| min_level.min(level)));
+        }
+    }
+    if fluid.infinite && sources >= 2 {
+        let below = gtc - Vec3::unit_y();
+        let source_below = get_fluid(world, below, fluid).is_some_and(FluidBlockMeta::is_source);
+        if source_below || blocks_flow(world, below, fluid) {
+            return Some(FluidBlockMeta::SOURCE);
+        }
+    }
+    min_level
+        .map(|level| level + fluid.decay)
+        .filter(|&level| level <= FLUID_MAX_LEVEL)
+        .map(|level| FluidBlockMeta { level, falling: false })
+}
+
+// whether the tile is loaded and is not something fluid can flow into or through
+fn blocks_flow(world: &SyncWorld, gtc: Vec3<i64>, fluid: Fluid) -> bool {
+    let Some(tile) = world.getter.gtc_get(gtc) else { return false };
+    let bid = tile.get(world.tile_blocks.as_ref()).get();
+    bid != fluid.bid && !world.sync_ctx.game.blocks_can_place_over[bid]
+}
+
+// whether fluid with the given state may replace the block at the tile
+fn can_flow_into(world: &SyncWorld, gtc: Vec3<i64>, fluid: Fluid, meta: FluidBlockMeta) -> bool {
+    let Some(tile) = world.getter.gtc_get(gtc) else { return false };
+    let read = tile.get(world.tile_blocks.as_ref());
+    if let Some(&meta2) = read.try_meta(fluid.bid) {
+        // only displace weaker flowing fluid
+        !meta2.is_source() && match (meta.falling, meta2.falling) {
+            (true, false) => true,
+            (false, false) => meta.level < meta2.level,
+            (_, true) => false,
+        }
+    } else {
+        let game = &world.sync_ctx.game;
+        let bid = read.get();
+        game.blocks_can_place_over[bid] && !game.content.fluid.is_fluid(bid)
+    }
+}
+
+fn set_fluid(world: &mut SyncWorld, gtc: Vec3<i64>, fluid: Fluid, meta: FluidBlockMeta) {
+    if let Some(tile) = world.getter.gtc_get(gtc) {
+        tile.get(&mut world.tile_blocks).erased_set(ErasedBidMeta::new(fluid.bid, meta));
+    }
+}
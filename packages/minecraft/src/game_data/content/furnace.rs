@@ -0,0 +1,262 @@
+//! The furnace, which smelts items using fuel.
+//!
+//! Smelting progress is kept in the furnace's block metadata, so it's saved with the chunk. While a
+//! furnace is burning fuel or could start to, it schedules itself a block tick every tick, upon
+//! which it advances by one tick. Its item slots changing gives it a block update, which starts
+//! this up again once it's stopped.
+
+use crate::{
+    game_data::content_module_prelude::*,
+    server::container_sync::write_container_slot,
+    util_usize_lt::UsizeLt,
+};
+use std::num::NonZeroU8;
+
+
+/// Number of ticks of burning fuel it takes to smelt an item.
+pub const SMELT_TICKS: u16 = 200;
+
+/// Index of the slot of the item being smelted.
+pub const INPUT_SLOT: usize = 0;
+/// Index of the slot of the fuel.
+pub const FUEL_SLOT: usize = 1;
+/// Index of the slot smelted items are put in.
+pub const OUTPUT_SLOT: usize = 2;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_furnace: BlockId<FurnaceBlockMeta>,
+    pub iid_furnace: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_furnace = builder.register_block(
+            "furnace",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_faces({
+                let mut faces = PerFace::repeat(BTI_FURNACE_SIDE);
+                faces[Face::PosY] = BTI_FURNACE_TOP;
+                faces[Face::NegY] = BTI_FURNACE_TOP;
+                faces[Face::NegZ] = BTI_FURNACE_FRONT;
+                faces
+            }),
+        );
+        builder.blocks_hardness.set(bid_furnace, Some(3.5));
+        builder.blocks_dig_tool
+            .set(bid_furnace, Some(BlockDigTool::required(ToolKind::Pickaxe, 0)));
+        builder.blocks_update_logic.set(bid_furnace, BlockUpdateLogic::Custom(furnace_update));
+        builder.blocks_container_logic.set(bid_furnace, Some(Box::new(FurnaceContainerLogic)));
+
+        let iid_furnace = builder.register_item(
+            "furnace",
+            #[cfg(feature = "client")]
+            LangKey::tile_furnace_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::FullCube {
+                top_tex_index: BTI_FURNACE_TOP,
+                left_tex_index: BTI_FURNACE_FRONT,
+                right_tex_index: BTI_FURNACE_SIDE,
+            },
+        );
+        builder.items_place_logic
+            .set(iid_furnace, ItemPlaceLogic::block(bid_furnace, FurnaceBlockMeta::default()));
+        builder.blocks_drop_logic.set(bid_furnace, BlockDropLogic::item(iid_furnace, ()));
+        builder.recipes.push(Recipe::shaped(
+            &["###", "# #", "###"],
+            &[('#', "cobblestone")],
+            "furnace",
+            1,
+        ));
+        builder.recipes.push(Recipe::smelting("cobblestone", "stone", 1));
+        builder.recipes.push(Recipe::smelting("sand", "glass", 1));
+
+        ContentModule {
+            bid_furnace,
+            iid_furnace,
+        }
+    }
+}
+
+/// Metadata for furnace blocks.
+#[derive(Debug, Clone, Default, GameBinschema)]
+pub struct FurnaceBlockMeta {
+    /// Input, fuel, and output slots, at `INPUT_SLOT`, `FUEL_SLOT`, and `OUTPUT_SLOT`.
+    pub slots: [Option<ItemStack>; 3],
+    /// Ticks until the fuel being burnt burns out, or 0 if none is burning.
+    pub burn_ticks: u16,
+    /// Ticks the fuel being burnt burns for in total.
+    pub burn_ticks_total: u16,
+    /// Ticks of burning fuel the item in the input slot has been smelting for.
+    pub smelt_ticks: u16,
+}
+
+// block update logic for furnaces. see module docs.
+fn furnace_update(world: &mut SyncWorld, tile: TileKey, cause: BlockUpdateCause) {
+    if cause == BlockUpdateCause::Changed {
+        world.sync_ctx.schedule_tick(tile, 1);
+        return;
+    }
+    let game = Arc::clone(&world.sync_ctx.game);
+    let old = tile.get(world.tile_blocks.as_ref()).raw_meta::<FurnaceBlockMeta>();
+    let mut meta = old.clone();
+
+    // burn fuel, taking more when it burns out if there's something to smelt
+    meta.burn_ticks = meta.burn_ticks.saturating_sub(1);
+    if meta.burn_ticks == 0 && smelted_output(&meta, &game).is_some() {
+        let fuel_ticks = meta.slots[FUEL_SLOT].as_ref()
+            .map(|stack| game.items_fuel_ticks[stack.iid])
+            .unwrap_or(0);
+        if fuel_ticks > 0 {
+            meta.burn_ticks = fuel_ticks;
+            meta.burn_ticks_total = fuel_ticks;
+            take_one(&mut meta.slots[FUEL_SLOT]);
+        }
+    }
+
+    // smelt the input while fuel is burning
+    match smelted_output(&meta, &game) {
+        Some(output) if meta.burn_ticks > 0 => {
+            meta.smelt_ticks += 1;
+            if meta.smelt_ticks >= SMELT_TICKS {
+                meta.smelt_ticks = 0;
+                meta.slots[OUTPUT_SLOT] = Some(output);
+                take_one(&mut meta.slots[INPUT_SLOT]);
+            }
+        }
+        _ => meta.smelt_ticks = 0,
+    }
+
+    // keep ticking while there's anything to do
+    if meta.burn_ticks > 0
+        || (smelted_output(&meta, &game).is_some() && meta.slots[FUEL_SLOT].is_some())
+    {
+        world.sync_ctx.schedule_tick(tile, 1);
+    }
+
+    // apply changes. properties are sent to players with the furnace open as part of syncing
+    // container properties, whereas slots are sent when written.
+    let changed_slots = (0..3)
+        .filter(|&i| old.slots[i] != meta.slots[i])
+        .collect::<Vec<_>>();
+    {
+        let written = tile.get(&mut world.tile_blocks).as_write_manual()
+            .raw_meta::<FurnaceBlockMeta>();
+        written.burn_ticks = meta.burn_ticks;
+        written.burn_ticks_total = meta.burn_ticks_total;
+        written.smelt_ticks = meta.smelt_ticks;
+    }
+    world.sync_ctx.save_mgr.mark_chunk_unsaved(tile.cc, tile.ci);
+    for i in changed_slots {
+        write_container_slot(world, tile.gtc(), UsizeLt::new(i), meta.slots[i].take());
+    }
+}
+
+// the content of the output slot after smelting the item in the input slot, if it can be smelted
+// and the result fits in the output slot
+fn smelted_output(meta: &FurnaceBlockMeta, game: &GameData) -> Option<ItemStack> {
+    let input = meta.slots[INPUT_SLOT].as_ref()?;
+    let smelted = game.recipes.smelt(input)?;
+    let Some(mut output) = meta.slots[OUTPUT_SLOT].clone() else { return Some(smelted) };
+    if output.iid != smelted.iid || output.meta != smelted.meta || output.damage != smelted.damage {
+        return None;
+    }
+    output.count = output.count.checked_add(smelted.count.get())
+        .filter(|&count| count <= game.items_max_count[output.iid])?;
+    Some(output)
+}
+
+// remove one item from the slot
+fn take_one(slot: &mut Option<ItemStack>) {
+    *slot = slot.take().and_then(|mut stack| {
+        stack.count = NonZeroU8::new(stack.count.get() - 1)?;
+        Some(stack)
+    });
+}
+
+/// Container logic for furnace blocks.
+///
+/// Its properties are its metadata's `burn_ticks`, `burn_ticks_total`, and `smelt_ticks`.
+#[derive(Debug)]
+pub struct FurnaceContainerLogic;
+
+impl ContainerLogic for FurnaceContainerLogic {
+    fn slots<'a>(&self, tile: TileBlockRead<'a>) -> &'a [Option<ItemStack>] {
+        &tile.raw_meta::<FurnaceBlockMeta>().slots
+    }
+
+    fn slots_mut<'a>(&self, tile: TileBlockWrite<'a>) -> &'a mut [Option<ItemStack>] {
+        &mut tile.raw_meta::<FurnaceBlockMeta>().slots
+    }
+
+    fn can_put(&self, idx: usize, stack: &ItemStack, game: &GameData) -> bool {
+        match idx {
+            INPUT_SLOT => true,
+            FUEL_SLOT => game.items_fuel_ticks[stack.iid] > 0,
+            _ => false,
+        }
+    }
+
+    fn properties(&self, tile: TileBlockRead) -> Vec<u16> {
+        let meta = tile.raw_meta::<FurnaceBlockMeta>();
+        vec![meta.burn_ticks, meta.burn_ticks_total, meta.smelt_ticks]
+    }
+
+    fn set_property(&self, tile: TileBlockWrite, idx: usize, value: u16) {
+        let meta = tile.raw_meta::<FurnaceBlockMeta>();
+        match idx {
+            0 => meta.burn_ticks = value,
+            1 => meta.burn_ticks_total = value,
+            2 => meta.smelt_ticks = value,
+            _ => (),
+        }
+    }
+
+    #[cfg(feature = "client")]
+    fn menu_layout(&self) -> ContainerMenuLayout {
+        let piece = |src_pos: [f32; 2], src_size: [f32; 2], dst_pos: [f32; 2]|
+            ContainerMenuBgPiece {
+                image: |assets| &assets.gui_furnace,
+                image_size: [256.0, 256.0].into(),
+                src_pos: src_pos.into(),
+                src_size: src_size.into(),
+                dst_pos: dst_pos.into(),
+            };
+        let slot = |idx: usize, pos: [f32; 2]| ContainerSlotGrid {
+            slots: idx..idx + 1,
+            columns: 1,
+            pos: pos.into(),
+        };
+        ContainerMenuLayout {
+            title: "Furnace",
+            title_x: 8.0,
+            size: [176.0, 166.0].into(),
+            background: vec![piece([0.0, 0.0], [176.0, 166.0], [0.0, 0.0])],
+            slot_grids: vec![
+                slot(INPUT_SLOT, [55.0, 16.0]),
+                slot(FUEL_SLOT, [55.0, 52.0]),
+                slot(OUTPUT_SLOT, [115.0, 34.0]),
+            ],
+            inventory_pos: [7.0, 83.0].into(),
+            progress_bars: vec![
+                // flame, showing how much of the burning fuel is left
+                ContainerProgressBar {
+                    piece: piece([176.0, 0.0], [14.0, 14.0], [56.0, 36.0]),
+                    direction: ProgressBarDirection::Up,
+                    fraction: |properties| match properties[1] {
+                        0 => 0.0,
+                        total => properties[0] as f32 / total as f32,
+                    },
+                },
+                // arrow, showing smelting progress
+                ContainerProgressBar {
+                    piece: piece([176.0, 14.0], [24.0, 17.0], [79.0, 34.0]),
+                    direction: ProgressBarDirection::Right,
+                    fraction: |properties| properties[2] as f32 / SMELT_TICKS as f32,
+                },
+            ],
+            crafting_grid: None,
+        }
+    }
+}
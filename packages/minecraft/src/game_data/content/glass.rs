@@ -0,0 +1,35 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_glass: BlockId<()>,
+    pub iid_glass: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_glass = builder.register_block(
+            "glass",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_transparent(BTI_GLASS),
+        );
+        builder.blocks_hardness.set(bid_glass, Some(0.3));
+        builder.blocks_light_opacity.set(bid_glass, 0);
+
+        let iid_glass = builder.register_item(
+            "glass",
+            #[cfg(feature = "client")]
+            LangKey::tile_glass_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_GLASS),
+        );
+        builder.items_place_logic.set(iid_glass, ItemPlaceLogic::block(bid_glass, ()));
+
+        ContentModule {
+            bid_glass,
+            iid_glass,
+        }
+    }
+}
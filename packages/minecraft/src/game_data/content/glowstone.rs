@@ -0,0 +1,35 @@
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_glowstone: BlockId<()>,
+    pub iid_glowstone: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_glowstone = builder.register_block(
+            "glowstone",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_GLOWSTONE),
+        );
+        builder.blocks_hardness.set(bid_glowstone, Some(0.3));
+        builder.blocks_light_emission.set(bid_glowstone, MAX_LIGHT);
+
+        let iid_glowstone = builder.register_item(
+            "glowstone",
+            #[cfg(feature = "client")]
+            LangKey::tile_lightgem_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_GLOWSTONE),
+        );
+        builder.items_place_logic.set(iid_glowstone, ItemPlaceLogic::block(bid_glowstone, ()));
+        builder.blocks_drop_logic.set(bid_glowstone, BlockDropLogic::item(iid_glowstone, ()));
+        
+        ContentModule {
+            bid_glowstone,
+            iid_glowstone,
+        }
+    }
+}
@@ -0,0 +1,97 @@
+
+use crate::game_data::content_module_prelude::*;
+use rand::prelude::*;
+
+
+/// Grass covered by a block of at least this opacity turns to dirt if the light above it is below
+/// this level.
+pub const GRASS_DIE_OPACITY: u8 = 3;
+
+/// Grass turns to dirt if the light above it is below this level and it is covered.
+pub const GRASS_DIE_LIGHT: u8 = 4;
+
+/// Grass may spread to nearby dirt if the light above it is at least this level.
+pub const GRASS_SPREAD_LIGHT: u8 = 9;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_grass: BlockId<()>,
+    pub iid_grass: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_grass = builder.register_block(
+            "grass",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_faces({
+                let mut tex_indices = PerFace::repeat(BTI_GRASS_SIDE);
+                tex_indices[Face::PosY] = BTI_GRASS_TOP;
+                tex_indices[Face::NegY] = BTI_DIRT;
+                tex_indices
+            }),
+        );
+        builder.blocks_hardness.set(bid_grass, Some(0.6));
+        builder.blocks_dig_tool.set(bid_grass, Some(BlockDigTool::faster(ToolKind::Shovel)));
+        builder.blocks_random_tick_logic
+            .set(bid_grass, BlockRandomTickLogic::Custom(grass_random_tick));
+
+        let iid_grass = builder.register_item(
+            "grass",
+            #[cfg(feature = "client")]
+            LangKey::tile_grass_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::FullCube {
+                top_tex_index: BTI_GRASS_TOP,
+                left_tex_index: BTI_GRASS_SIDE,
+                right_tex_index: BTI_GRASS_SIDE,
+            },
+        );
+        builder.items_place_logic.set(iid_grass, ItemPlaceLogic::block(bid_grass, ()));
+        builder.blocks_drop_logic.set(bid_grass, BlockDropLogic::Custom(|game| {
+            Some(ItemStack::new(game.content.dirt.iid_dirt, ()))
+        }));
+        
+        ContentModule {
+            bid_grass,
+            iid_grass,
+        }
+    }
+}
+
+// light level above the tile and opacity of the block above the tile, if loaded
+fn above(world: &SyncWorld, gtc: Vec3<i64>) -> Option<(u8, u8)> {
+    let above = world.getter.gtc_get(gtc + Vec3::new(0, 1, 0))?;
+    let bid = above.get(world.tile_blocks.as_ref()).get();
+    Some((world.tile_blocks.light().get(above), world.sync_ctx.game.blocks_light_opacity[bid]))
+}
+
+// grass dies if covered in darkness, and spreads to nearby dirt if well lit
+fn grass_random_tick(world: &mut SyncWorld, tile: TileKey, rng: &mut dyn RngCore) {
+    let game = Arc::clone(&world.sync_ctx.game);
+    let gtc = cc_ltc_to_gtc(tile.cc, lti_to_ltc(tile.lti));
+    let Some((light, opacity)) = above(world, gtc) else { return };
+    if light < GRASS_DIE_LIGHT && opacity >= GRASS_DIE_OPACITY {
+        if rng.gen_range(0..4) == 0 {
+            tile.get(&mut world.tile_blocks)
+                .erased_set(ErasedBidMeta::new(game.content.dirt.bid_dirt, ()));
+        }
+    } else if light >= GRASS_SPREAD_LIGHT {
+        let target_gtc = gtc + grass_spread_offset(rng);
+        let Some(target) = world.getter.gtc_get(target_gtc) else { return };
+        if target.get(world.tile_blocks.as_ref()).get() != game.content.dirt.bid_dirt {
+            return;
+        }
+        let Some((light, opacity)) = above(world, target_gtc) else { return };
+        if light >= GRASS_DIE_LIGHT && opacity < GRASS_DIE_OPACITY {
+            target.get(&mut world.tile_blocks)
+                .erased_set(ErasedBidMeta::new(game.content.grass.bid_grass, ()));
+        }
+    }
+}
+
+/// Pick the offset from a grass tile to the nearby tile it tries to spread to.
+pub fn grass_spread_offset(rng: &mut dyn RngCore) -> Vec3<i64> {
+    Vec3::new(rng.gen_range(-1..=1), rng.gen_range(-3..=1), rng.gen_range(-1..=1))
+}
@@ -0,0 +1,40 @@
+
+use crate::game_data::{
+    content_module_prelude::*,
+    content::sand::falling_block_update,
+};
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_gravel: BlockId<()>,
+    pub iid_gravel: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_gravel = builder.register_block(
+            "gravel",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_GRAVEL),
+        );
+        builder.blocks_hardness.set(bid_gravel, Some(0.6));
+        builder.blocks_dig_tool.set(bid_gravel, Some(BlockDigTool::faster(ToolKind::Shovel)));
+        builder.blocks_update_logic.set(bid_gravel, BlockUpdateLogic::Custom(falling_block_update));
+
+        let iid_gravel = builder.register_item(
+            "gravel",
+            #[cfg(feature = "client")]
+            LangKey::tile_gravel_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_GRAVEL),
+        );
+        builder.items_place_logic.set(iid_gravel, ItemPlaceLogic::block(bid_gravel, ()));
+        builder.blocks_drop_logic.set(bid_gravel, BlockDropLogic::item(iid_gravel, ()));
+
+        ContentModule {
+            bid_gravel,
+            iid_gravel,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_ice: BlockId<()>,
+    pub iid_ice: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_ice = builder.register_block(
+            "ice",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_transparent(BTI_ICE),
+        );
+        builder.blocks_hardness.set(bid_ice, Some(0.5));
+        builder.blocks_dig_tool.set(bid_ice, Some(BlockDigTool::faster(ToolKind::Pickaxe)));
+        builder.blocks_light_opacity.set(bid_ice, 3);
+
+        let iid_ice = builder.register_item(
+            "ice",
+            #[cfg(feature = "client")]
+            LangKey::tile_ice_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_ICE),
+        );
+        builder.items_place_logic.set(iid_ice, ItemPlaceLogic::block(bid_ice, ()));
+
+        ContentModule {
+            bid_ice,
+            iid_ice,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_leaves: BlockId<()>,
+    pub iid_leaves: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_leaves = builder.register_block(
+            "leaves",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_transparent(BTI_LEAVES),
+        );
+        builder.blocks_hardness.set(bid_leaves, Some(0.2));
+        builder.blocks_light_opacity.set(bid_leaves, 1);
+
+        let iid_leaves = builder.register_item(
+            "leaves",
+            #[cfg(feature = "client")]
+            LangKey::tile_leaves_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_LEAVES),
+        );
+        builder.items_place_logic.set(iid_leaves, ItemPlaceLogic::block(bid_leaves, ()));
+        
+        ContentModule {
+            bid_leaves,
+            iid_leaves,
+        }
+    }
+}
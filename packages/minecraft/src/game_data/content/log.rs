@@ -0,0 +1,46 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_log: BlockId<()>,
+    pub iid_log: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_log = builder.register_block(
+            "log",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_faces({
+                let mut tex_indices = PerFace::repeat(BTI_LOG_SIDE);
+                tex_indices[Face::PosY] = BTI_LOG_TOP;
+                tex_indices[Face::NegY] = BTI_LOG_TOP;
+                tex_indices
+            }),
+        );
+        builder.blocks_hardness.set(bid_log, Some(2.0));
+        builder.blocks_dig_tool.set(bid_log, Some(BlockDigTool::faster(ToolKind::Axe)));
+
+        let iid_log = builder.register_item(
+            "log",
+            #[cfg(feature = "client")]
+            LangKey::tile_log_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::FullCube {
+                top_tex_index: BTI_LOG_TOP,
+                left_tex_index: BTI_LOG_SIDE,
+                right_tex_index: BTI_LOG_SIDE,
+            },
+        );
+        builder.items_place_logic.set(iid_log, ItemPlaceLogic::block(bid_log, ()));
+        builder.blocks_drop_logic.set(bid_log, BlockDropLogic::item(iid_log, ()));
+        builder.items_fuel_ticks.set(iid_log, 300);
+        
+        ContentModule {
+            bid_log,
+            iid_log,
+        }
+    }
+}
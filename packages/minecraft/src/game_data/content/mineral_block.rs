@@ -0,0 +1,89 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_gold_block: BlockId<()>,
+    pub iid_gold_block: ItemId<()>,
+    pub bid_iron_block: BlockId<()>,
+    pub iid_iron_block: ItemId<()>,
+    pub bid_diamond_block: BlockId<()>,
+    pub iid_diamond_block: ItemId<()>,
+}
+
+// register a basic cube mineral storage block, which requires a pickaxe of the given tier, and its
+// item
+fn register_mineral_block(
+    builder: &mut GameDataBuilder,
+    machine_name: &str,
+    hardness: f32,
+    harvest_tier: u8,
+    #[cfg(feature = "client")]
+    name: LangKey,
+    #[cfg(feature = "client")]
+    tex_index: usize,
+) -> (BlockId<()>, ItemId<()>) {
+    let bid = builder.register_block(
+        machine_name,
+        #[cfg(feature = "client")]
+        BlockMeshLogic::basic_cube(tex_index),
+    );
+    builder.blocks_hardness.set(bid, Some(hardness));
+    builder.blocks_dig_tool
+        .set(bid, Some(BlockDigTool::required(ToolKind::Pickaxe, harvest_tier)));
+    let iid = builder.register_item(
+        machine_name,
+        #[cfg(feature = "client")]
+        name,
+        #[cfg(feature = "client")]
+        ItemMeshLogic::basic_cube(tex_index),
+    );
+    builder.items_place_logic.set(iid, ItemPlaceLogic::block(bid, ()));
+    builder.blocks_drop_logic.set(bid, BlockDropLogic::item(iid, ()));
+    (bid, iid)
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let (bid_gold_block, iid_gold_block) = register_mineral_block(
+            builder,
+            "gold_block",
+            3.0,
+            2,
+            #[cfg(feature = "client")]
+            LangKey::tile_blockGold_name,
+            #[cfg(feature = "client")]
+            BTI_GOLD_BLOCK,
+        );
+        let (bid_iron_block, iid_iron_block) = register_mineral_block(
+            builder,
+            "iron_block",
+            5.0,
+            1,
+            #[cfg(feature = "client")]
+            LangKey::tile_blockIron_name,
+            #[cfg(feature = "client")]
+            BTI_IRON_BLOCK,
+        );
+        let (bid_diamond_block, iid_diamond_block) = register_mineral_block(
+            builder,
+            "diamond_block",
+            5.0,
+            2,
+            #[cfg(feature = "client")]
+            LangKey::tile_blockDiamond_name,
+            #[cfg(feature = "client")]
+            BTI_DIAMOND_BLOCK,
+        );
+
+        ContentModule {
+            bid_gold_block,
+            iid_gold_block,
+            bid_iron_block,
+            iid_iron_block,
+            bid_diamond_block,
+            iid_diamond_block,
+        }
+    }
+}
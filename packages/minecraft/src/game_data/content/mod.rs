@@ -29,5 +29,40 @@ macro_rules! content {
 content!(
     air,
     stone,
+    dirt,
+    grass,
+    sand,
+    ore,
+    cobblestone,
+    log,
+    leaves,
+    glowstone,
     chest,
+    fluid,
+    gravel,
+    planks,
+    brick,
+    glass,
+    bedrock,
+    obsidian,
+    sponge,
+    wool,
+    mineral_block,
+    bookshelf,
+    tnt,
+    snow,
+    ice,
+    clay,
+    netherrack,
+    soul_sand,
+    tools,
+    stick,
+    crafting_table,
+    furnace,
+    stone_slab,
+    stairs,
+    flower,
+    torch,
+    fence,
+    door,
 );
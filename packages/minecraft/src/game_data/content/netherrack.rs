@@ -0,0 +1,37 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_netherrack: BlockId<()>,
+    pub iid_netherrack: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_netherrack = builder.register_block(
+            "netherrack",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_NETHERRACK),
+        );
+        builder.blocks_hardness.set(bid_netherrack, Some(0.4));
+        builder.blocks_dig_tool
+            .set(bid_netherrack, Some(BlockDigTool::required(ToolKind::Pickaxe, 0)));
+
+        let iid_netherrack = builder.register_item(
+            "netherrack",
+            #[cfg(feature = "client")]
+            LangKey::tile_hellrock_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_NETHERRACK),
+        );
+        builder.items_place_logic.set(iid_netherrack, ItemPlaceLogic::block(bid_netherrack, ()));
+        builder.blocks_drop_logic.set(bid_netherrack, BlockDropLogic::item(iid_netherrack, ()));
+
+        ContentModule {
+            bid_netherrack,
+            iid_netherrack,
+        }
+    }
+}
@@ -0,0 +1,37 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_obsidian: BlockId<()>,
+    pub iid_obsidian: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_obsidian = builder.register_block(
+            "obsidian",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_OBSIDIAN),
+        );
+        builder.blocks_hardness.set(bid_obsidian, Some(10.0));
+        builder.blocks_dig_tool
+            .set(bid_obsidian, Some(BlockDigTool::required(ToolKind::Pickaxe, 3)));
+
+        let iid_obsidian = builder.register_item(
+            "obsidian",
+            #[cfg(feature = "client")]
+            LangKey::tile_obsidian_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_OBSIDIAN),
+        );
+        builder.items_place_logic.set(iid_obsidian, ItemPlaceLogic::block(bid_obsidian, ()));
+        builder.blocks_drop_logic.set(bid_obsidian, BlockDropLogic::item(iid_obsidian, ()));
+
+        ContentModule {
+            bid_obsidian,
+            iid_obsidian,
+        }
+    }
+}
@@ -0,0 +1,112 @@
+
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_coal_ore: BlockId<()>,
+    pub iid_coal_ore: ItemId<()>,
+    pub bid_iron_ore: BlockId<()>,
+    pub iid_iron_ore: ItemId<()>,
+    pub bid_gold_ore: BlockId<()>,
+    pub iid_gold_ore: ItemId<()>,
+    pub bid_redstone_ore: BlockId<()>,
+    pub iid_redstone_ore: ItemId<()>,
+    pub bid_diamond_ore: BlockId<()>,
+    pub iid_diamond_ore: ItemId<()>,
+}
+
+// register a basic cube ore block, which requires a pickaxe of the given tier, and its item
+fn register_ore(
+    builder: &mut GameDataBuilder,
+    machine_name: &str,
+    harvest_tier: u8,
+    #[cfg(feature = "client")]
+    name: LangKey,
+    #[cfg(feature = "client")]
+    tex_index: usize,
+) -> (BlockId<()>, ItemId<()>) {
+    let bid = builder.register_block(
+        machine_name,
+        #[cfg(feature = "client")]
+        BlockMeshLogic::basic_cube(tex_index),
+    );
+    builder.blocks_hardness.set(bid, Some(3.0));
+    builder.blocks_dig_tool
+        .set(bid, Some(BlockDigTool::required(ToolKind::Pickaxe, harvest_tier)));
+    let iid = builder.register_item(
+        machine_name,
+        #[cfg(feature = "client")]
+        name,
+        #[cfg(feature = "client")]
+        ItemMeshLogic::basic_cube(tex_index),
+    );
+    builder.items_place_logic.set(iid, ItemPlaceLogic::block(bid, ()));
+    // TODO: coal, redstone, and diamond ore should drop their minerals once those items exist
+    builder.blocks_drop_logic.set(bid, BlockDropLogic::item(iid, ()));
+    (bid, iid)
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let (bid_coal_ore, iid_coal_ore) = register_ore(
+            builder,
+            "coal_ore",
+            0,
+            #[cfg(feature = "client")]
+            LangKey::tile_oreCoal_name,
+            #[cfg(feature = "client")]
+            BTI_COAL_ORE,
+        );
+        let (bid_iron_ore, iid_iron_ore) = register_ore(
+            builder,
+            "iron_ore",
+            1,
+            #[cfg(feature = "client")]
+            LangKey::tile_oreIron_name,
+            #[cfg(feature = "client")]
+            BTI_IRON_ORE,
+        );
+        let (bid_gold_ore, iid_gold_ore) = register_ore(
+            builder,
+            "gold_ore",
+            2,
+            #[cfg(feature = "client")]
+            LangKey::tile_oreGold_name,
+            #[cfg(feature = "client")]
+            BTI_GOLD_ORE,
+        );
+        let (bid_redstone_ore, iid_redstone_ore) = register_ore(
+            builder,
+            "redstone_ore",
+            2,
+            #[cfg(feature = "client")]
+            LangKey::tile_oreRedstone_name,
+            #[cfg(feature = "client")]
+            BTI_REDSTONE_ORE,
+        );
+        let (bid_diamond_ore, iid_diamond_ore) = register_ore(
+            builder,
+            "diamond_ore",
+            2,
+            #[cfg(feature = "client")]
+            LangKey::tile_oreDiamond_name,
+            #[cfg(feature = "client")]
+            BTI_DIAMOND_ORE,
+        );
+
+        ContentModule {
+            bid_coal_ore,
+            iid_coal_ore,
+            bid_iron_ore,
+            iid_iron_ore,
+            bid_gold_ore,
+            iid_gold_ore,
+            bid_redstone_ore,
+            iid_redstone_ore,
+            bid_diamond_ore,
+            iid_diamond_ore,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_planks: BlockId<()>,
+    pub iid_planks: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_planks = builder.register_block(
+            "planks",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_PLANKS),
+        );
+        builder.blocks_hardness.set(bid_planks, Some(2.0));
+        builder.blocks_dig_tool.set(bid_planks, Some(BlockDigTool::faster(ToolKind::Axe)));
+
+        let iid_planks = builder.register_item(
+            "planks",
+            #[cfg(feature = "client")]
+            LangKey::tile_planks_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_PLANKS),
+        );
+        builder.items_place_logic.set(iid_planks, ItemPlaceLogic::block(bid_planks, ()));
+        builder.blocks_drop_logic.set(bid_planks, BlockDropLogic::item(iid_planks, ()));
+        builder.recipes.push(Recipe::shapeless(&["log"], "planks", 4));
+        builder.items_fuel_ticks.set(iid_planks, 300);
+
+        ContentModule {
+            bid_planks,
+            iid_planks,
+        }
+    }
+}
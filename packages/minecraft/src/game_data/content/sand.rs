@@ -0,0 +1,78 @@
+//! Sand, and the falling logic it shares with other blocks that fall when nothing supports them.
+//!
+//! Upon a falling block or one of its neighbors changing, if the tile beneath it can be placed
+//! over, it schedules itself a tick. Upon that tick firing, if it's still unsupported, it replaces
+//! itself with air and becomes a falling block entity, which places the block back into the world
+//! where it lands, or drops it as an item if the tile it lands in can't be placed over.
+
+use crate::{
+    game_data::content_module_prelude::*,
+    sync_state_entities::FallingBlockEntityState,
+};
+
+
+/// Number of ticks between a falling block losing its support and it starting to fall.
+pub const FALL_DELAY: u64 = 2;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_sand: BlockId<()>,
+    pub iid_sand: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_sand = builder.register_block(
+            "sand",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_SAND),
+        );
+        builder.blocks_hardness.set(bid_sand, Some(0.5));
+        builder.blocks_dig_tool.set(bid_sand, Some(BlockDigTool::faster(ToolKind::Shovel)));
+        builder.blocks_update_logic.set(bid_sand, BlockUpdateLogic::Custom(falling_block_update));
+
+        let iid_sand = builder.register_item(
+            "sand",
+            #[cfg(feature = "client")]
+            LangKey::tile_sand_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_SAND),
+        );
+        builder.items_place_logic.set(iid_sand, ItemPlaceLogic::block(bid_sand, ()));
+        builder.blocks_drop_logic.set(bid_sand, BlockDropLogic::item(iid_sand, ()));
+
+        ContentModule {
+            bid_sand,
+            iid_sand,
+        }
+    }
+}
+
+/// Block update logic for blocks which fall when the tile beneath them can be placed over.
+pub fn falling_block_update(world: &mut SyncWorld, tile: TileKey, cause: BlockUpdateCause) {
+    if is_supported(world, tile) {
+        return;
+    }
+    match cause {
+        BlockUpdateCause::Changed => world.sync_ctx.schedule_tick(tile, FALL_DELAY),
+        BlockUpdateCause::Scheduled => {
+            let bid_meta = tile.get(&mut world.tile_blocks)
+                .erased_replace(ErasedBidMeta::new(AIR, ()));
+            let rel_pos = lti_to_ltc(tile.lti).map(|n| n as f32) + Vec3::new(0.5, 0.0, 0.5);
+            let state = FallingBlockEntityState {
+                vel: 0.0.into(),
+                bid_meta: Arc::new(bid_meta),
+            };
+            world.chunk_falling_blocks.create_entity(tile.cc, tile.ci, state, (), rel_pos);
+        }
+    }
+}
+
+// whether the block at the tile is held up by the tile beneath it. tiles beneath which aren't
+// loaded count as supporting, so that blocks don't fall into unloaded chunks
+fn is_supported(world: &SyncWorld, tile: TileKey) -> bool {
+    let Some(below) = world.getter.gtc_get(tile.gtc() - Vec3::unit_y()) else { return true };
+    let bid = below.get(world.tile_blocks.as_ref()).get();
+    !world.sync_ctx.game.blocks_can_place_over[bid]
+}
@@ -0,0 +1,37 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_snow_block: BlockId<()>,
+    pub iid_snow_block: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_snow_block = builder.register_block(
+            "snow_block",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_SNOW),
+        );
+        builder.blocks_hardness.set(bid_snow_block, Some(0.2));
+        builder.blocks_dig_tool
+            .set(bid_snow_block, Some(BlockDigTool::required(ToolKind::Shovel, 0)));
+
+        let iid_snow_block = builder.register_item(
+            "snow_block",
+            #[cfg(feature = "client")]
+            LangKey::tile_snow_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_SNOW),
+        );
+        builder.items_place_logic.set(iid_snow_block, ItemPlaceLogic::block(bid_snow_block, ()));
+        builder.blocks_drop_logic.set(bid_snow_block, BlockDropLogic::item(iid_snow_block, ()));
+
+        ContentModule {
+            bid_snow_block,
+            iid_snow_block,
+        }
+    }
+}
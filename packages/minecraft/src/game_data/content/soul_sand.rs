@@ -0,0 +1,36 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_soul_sand: BlockId<()>,
+    pub iid_soul_sand: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_soul_sand = builder.register_block(
+            "soul_sand",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_SOUL_SAND),
+        );
+        builder.blocks_hardness.set(bid_soul_sand, Some(0.5));
+        builder.blocks_dig_tool.set(bid_soul_sand, Some(BlockDigTool::faster(ToolKind::Shovel)));
+
+        let iid_soul_sand = builder.register_item(
+            "soul_sand",
+            #[cfg(feature = "client")]
+            LangKey::tile_hellsand_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_SOUL_SAND),
+        );
+        builder.items_place_logic.set(iid_soul_sand, ItemPlaceLogic::block(bid_soul_sand, ()));
+        builder.blocks_drop_logic.set(bid_soul_sand, BlockDropLogic::item(iid_soul_sand, ()));
+
+        ContentModule {
+            bid_soul_sand,
+            iid_soul_sand,
+        }
+    }
+}
@@ -0,0 +1,35 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_sponge: BlockId<()>,
+    pub iid_sponge: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_sponge = builder.register_block(
+            "sponge",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_SPONGE),
+        );
+        builder.blocks_hardness.set(bid_sponge, Some(0.6));
+
+        let iid_sponge = builder.register_item(
+            "sponge",
+            #[cfg(feature = "client")]
+            LangKey::tile_sponge_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_SPONGE),
+        );
+        builder.items_place_logic.set(iid_sponge, ItemPlaceLogic::block(bid_sponge, ()));
+        builder.blocks_drop_logic.set(bid_sponge, BlockDropLogic::item(iid_sponge, ()));
+
+        ContentModule {
+            bid_sponge,
+            iid_sponge,
+        }
+    }
+}
@@ -0,0 +1,74 @@
+//! Cobblestone stairs, which fill the bottom half of their tile and the back half of its top half.
+
+use crate::game_data::content_module_prelude::*;
+
+
+/// The parts of a tile stairs fill.
+pub const STAIRS_BOXES: [AaBox; 2] = [
+    AaBox {
+        pos: Vec3 { x: 0.0, y: 0.0, z: 0.0 },
+        ext: Extent3 { w: 1.0, h: 0.5, d: 1.0 },
+    },
+    AaBox {
+        pos: Vec3 { x: 0.0, y: 0.5, z: 0.5 },
+        ext: Extent3 { w: 1.0, h: 0.5, d: 0.5 },
+    },
+];
+
+
+#[cfg(feature = "client")]
+fn stairs_mesh_boxes() -> Vec<BlockMeshLogicBox> {
+    STAIRS_BOXES.iter()
+        .map(|&aa_box| BlockMeshLogicBox {
+            aa_box,
+            tex_indices: PerFace::repeat(BTI_COBBLESTONE),
+        })
+        .collect()
+}
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_cobblestone_stairs: BlockId<()>,
+    pub iid_cobblestone_stairs: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_cobblestone_stairs = builder.register_block(
+            "cobblestone_stairs",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::Boxes(stairs_mesh_boxes()),
+        );
+        builder.blocks_hitscan_logic
+            .set(bid_cobblestone_stairs, BlockHitscanLogic::Boxes(STAIRS_BOXES.to_vec()));
+        builder.blocks_physics_logic
+            .set(bid_cobblestone_stairs, BlockPhysicsLogic::Boxes(STAIRS_BOXES.to_vec()));
+        builder.blocks_hardness.set(bid_cobblestone_stairs, Some(2.0));
+        builder.blocks_dig_tool
+            .set(bid_cobblestone_stairs, Some(BlockDigTool::required(ToolKind::Pickaxe, 0)));
+
+        let iid_cobblestone_stairs = builder.register_item(
+            "cobblestone_stairs",
+            #[cfg(feature = "client")]
+            LangKey::tile_stairsStone_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::Boxes(stairs_mesh_boxes()),
+        );
+        builder.items_place_logic
+            .set(iid_cobblestone_stairs, ItemPlaceLogic::block(bid_cobblestone_stairs, ()));
+        builder.blocks_drop_logic
+            .set(bid_cobblestone_stairs, BlockDropLogic::item(iid_cobblestone_stairs, ()));
+        builder.recipes.push(Recipe::shaped(
+            &["#  ", "## ", "###"],
+            &[('#', "cobblestone")],
+            "cobblestone_stairs",
+            4,
+        ));
+
+        ContentModule {
+            bid_cobblestone_stairs,
+            iid_cobblestone_stairs,
+        }
+    }
+}
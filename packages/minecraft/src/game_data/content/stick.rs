@@ -0,0 +1,26 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub iid_stick: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let iid_stick = builder.register_item(
+            "stick",
+            #[cfg(feature = "client")]
+            LangKey::item_stick_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::flat(ITI_STICK),
+        );
+        builder.recipes.push(Recipe::shaped(&["#", "#"], &[('#', "planks")], "stick", 4));
+        builder.items_fuel_ticks.set(iid_stick, 100);
+
+        ContentModule {
+            iid_stick,
+        }
+    }
+}
@@ -15,6 +15,8 @@ impl ContentModule {
             #[cfg(feature = "client")]
             BlockMeshLogic::basic_cube(BTI_STONE),
         );
+        builder.blocks_hardness.set(bid_stone, Some(1.5));
+        builder.blocks_dig_tool.set(bid_stone, Some(BlockDigTool::required(ToolKind::Pickaxe, 0)));
 
         let iid_stone = builder.register_item(
             "stone",
@@ -23,6 +25,10 @@ impl ContentModule {
             #[cfg(feature = "client")]
             ItemMeshLogic::basic_cube(BTI_STONE),
         );
+        builder.items_place_logic.set(iid_stone, ItemPlaceLogic::block(bid_stone, ()));
+        builder.blocks_drop_logic.set(bid_stone, BlockDropLogic::Custom(|game| {
+            Some(ItemStack::new(game.content.cobblestone.iid_cobblestone, ()))
+        }));
         
         ContentModule {
             bid_stone,
@@ -0,0 +1,60 @@
+//! The stone slab, which fills the bottom half of its tile.
+
+use crate::game_data::content_module_prelude::*;
+
+
+/// The part of a tile a slab fills.
+pub const SLAB_BOX: AaBox = AaBox {
+    pos: Vec3 { x: 0.0, y: 0.0, z: 0.0 },
+    ext: Extent3 { w: 1.0, h: 0.5, d: 1.0 },
+};
+
+
+#[cfg(feature = "client")]
+fn slab_tex_indices() -> PerFace<usize> {
+    let mut faces = PerFace::repeat(BTI_STONE_SLAB_SIDE);
+    faces[Face::PosY] = BTI_STONE_SLAB_TOP;
+    faces[Face::NegY] = BTI_STONE_SLAB_TOP;
+    faces
+}
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_stone_slab: BlockId<()>,
+    pub iid_stone_slab: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_stone_slab = builder.register_block(
+            "stone_slab",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::single_box(SLAB_BOX, slab_tex_indices()),
+        );
+        builder.blocks_hitscan_logic.set(bid_stone_slab, BlockHitscanLogic::Boxes(vec![SLAB_BOX]));
+        builder.blocks_physics_logic.set(bid_stone_slab, BlockPhysicsLogic::Boxes(vec![SLAB_BOX]));
+        builder.blocks_hardness.set(bid_stone_slab, Some(2.0));
+        builder.blocks_dig_tool
+            .set(bid_stone_slab, Some(BlockDigTool::required(ToolKind::Pickaxe, 0)));
+
+        let iid_stone_slab = builder.register_item(
+            "stone_slab",
+            #[cfg(feature = "client")]
+            LangKey::tile_stoneSlab_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::Boxes(vec![BlockMeshLogicBox {
+                aa_box: SLAB_BOX,
+                tex_indices: slab_tex_indices(),
+            }]),
+        );
+        builder.items_place_logic.set(iid_stone_slab, ItemPlaceLogic::block(bid_stone_slab, ()));
+        builder.blocks_drop_logic.set(bid_stone_slab, BlockDropLogic::item(iid_stone_slab, ()));
+        builder.recipes.push(Recipe::shaped(&["###"], &[('#', "stone")], "stone_slab", 3));
+
+        ContentModule {
+            bid_stone_slab,
+            iid_stone_slab,
+        }
+    }
+}
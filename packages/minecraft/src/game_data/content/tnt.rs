@@ -0,0 +1,43 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_tnt: BlockId<()>,
+    pub iid_tnt: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_tnt = builder.register_block(
+            "tnt",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube_faces({
+                let mut tex_indices = PerFace::repeat(BTI_TNT_SIDE);
+                tex_indices[Face::PosY] = BTI_TNT_TOP;
+                tex_indices[Face::NegY] = BTI_TNT_BOTTOM;
+                tex_indices
+            }),
+        );
+
+        let iid_tnt = builder.register_item(
+            "tnt",
+            #[cfg(feature = "client")]
+            LangKey::tile_tnt_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::FullCube {
+                top_tex_index: BTI_TNT_TOP,
+                left_tex_index: BTI_TNT_SIDE,
+                right_tex_index: BTI_TNT_SIDE,
+            },
+        );
+        builder.items_place_logic.set(iid_tnt, ItemPlaceLogic::block(bid_tnt, ()));
+        builder.blocks_drop_logic.set(bid_tnt, BlockDropLogic::item(iid_tnt, ()));
+
+        ContentModule {
+            bid_tnt,
+            iid_tnt,
+        }
+    }
+}
@@ -0,0 +1,184 @@
+//! Shovels, pickaxes, and axes, in each of beta 1.0.2's tool materials.
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub wooden: ToolSet,
+    pub stone: ToolSet,
+    pub iron: ToolSet,
+    pub diamond: ToolSet,
+    pub golden: ToolSet,
+}
+
+/// Item IDs for the tools of a single material.
+#[derive(Debug)]
+pub struct ToolSet {
+    pub iid_shovel: ItemId<()>,
+    pub iid_pickaxe: ItemId<()>,
+    pub iid_axe: ItemId<()>,
+}
+
+// properties which differ between tool materials
+struct ToolMaterial {
+    // prefix of the machine names
+    machine_name: &'static str,
+    tier: u8,
+    speed: f32,
+    // number of uses before the tool breaks
+    uses: u16,
+    // machine name of the item tool heads are crafted from, if it exists yet
+    head_ingredient: Option<&'static str>,
+    // lang keys for the shovel, pickaxe, and axe
+    #[cfg(feature = "client")]
+    names: [LangKey; 3],
+    // item texture indexes for the shovel, pickaxe, and axe
+    #[cfg(feature = "client")]
+    tex_indices: [usize; 3],
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        ContentModule {
+            wooden: register_tool_set(builder, ToolMaterial {
+                machine_name: "wooden",
+                tier: 0,
+                speed: 2.0,
+                uses: 59,
+                head_ingredient: Some("planks"),
+                #[cfg(feature = "client")]
+                names: [
+                    LangKey::item_shovelWood_name,
+                    LangKey::item_pickaxeWood_name,
+                    LangKey::item_hatchetWood_name,
+                ],
+                #[cfg(feature = "client")]
+                tex_indices: [ITI_WOODEN_SHOVEL, ITI_WOODEN_PICKAXE, ITI_WOODEN_AXE],
+            }),
+            stone: register_tool_set(builder, ToolMaterial {
+                machine_name: "stone",
+                tier: 1,
+                speed: 4.0,
+                uses: 131,
+                head_ingredient: Some("cobblestone"),
+                #[cfg(feature = "client")]
+                names: [
+                    LangKey::item_shovelStone_name,
+                    LangKey::item_pickaxeStone_name,
+                    LangKey::item_hatchetStone_name,
+                ],
+                #[cfg(feature = "client")]
+                tex_indices: [ITI_STONE_SHOVEL, ITI_STONE_PICKAXE, ITI_STONE_AXE],
+            }),
+            iron: register_tool_set(builder, ToolMaterial {
+                machine_name: "iron",
+                tier: 2,
+                speed: 6.0,
+                uses: 250,
+                head_ingredient: None,
+                #[cfg(feature = "client")]
+                names: [
+                    LangKey::item_shovelIron_name,
+                    LangKey::item_pickaxeIron_name,
+                    LangKey::item_hatchetIron_name,
+                ],
+                #[cfg(feature = "client")]
+                tex_indices: [ITI_IRON_SHOVEL, ITI_IRON_PICKAXE, ITI_IRON_AXE],
+            }),
+            diamond: register_tool_set(builder, ToolMaterial {
+                machine_name: "diamond",
+                tier: 3,
+                speed: 8.0,
+                uses: 1561,
+                head_ingredient: None,
+                #[cfg(feature = "client")]
+                names: [
+                    LangKey::item_shovelDiamond_name,
+                    LangKey::item_pickaxeDiamond_name,
+                    LangKey::item_hatchetDiamond_name,
+                ],
+                #[cfg(feature = "client")]
+                tex_indices: [ITI_DIAMOND_SHOVEL, ITI_DIAMOND_PICKAXE, ITI_DIAMOND_AXE],
+            }),
+            golden: register_tool_set(builder, ToolMaterial {
+                machine_name: "golden",
+                tier: 0,
+                speed: 12.0,
+                uses: 32,
+                head_ingredient: None,
+                #[cfg(feature = "client")]
+                names: [
+                    LangKey::item_shovelGold_name,
+                    LangKey::item_pickaxeGold_name,
+                    LangKey::item_hatchetGold_name,
+                ],
+                #[cfg(feature = "client")]
+                tex_indices: [ITI_GOLDEN_SHOVEL, ITI_GOLDEN_PICKAXE, ITI_GOLDEN_AXE],
+            }),
+        }
+    }
+}
+
+// register the shovel, pickaxe, and axe of a material, and their recipes
+fn register_tool_set(builder: &mut GameDataBuilder, material: ToolMaterial) -> ToolSet {
+    if let Some(head_ingredient) = material.head_ingredient {
+        let key = [('X', head_ingredient), ('#', "stick")];
+        for (kind_name, pattern) in [
+            ("shovel", &["X", "#", "#"][..]),
+            ("pickaxe", &["XXX", " # ", " # "][..]),
+            ("axe", &["XX", "X#", " #"][..]),
+        ] {
+            let output = format!("{}_{}", material.machine_name, kind_name);
+            builder.recipes.push(Recipe::shaped(pattern, &key, &output, 1));
+        }
+    }
+    ToolSet {
+        iid_shovel: register_tool(
+            builder,
+            &material,
+            "shovel",
+            ToolKind::Shovel,
+            #[cfg(feature = "client")]
+            0,
+        ),
+        iid_pickaxe: register_tool(
+            builder,
+            &material,
+            "pickaxe",
+            ToolKind::Pickaxe,
+            #[cfg(feature = "client")]
+            1,
+        ),
+        iid_axe: register_tool(
+            builder,
+            &material,
+            "axe",
+            ToolKind::Axe,
+            #[cfg(feature = "client")]
+            2,
+        ),
+    }
+}
+
+// register a single tool item, using the material's client-side properties at the given index
+fn register_tool(
+    builder: &mut GameDataBuilder,
+    material: &ToolMaterial,
+    kind_name: &str,
+    kind: ToolKind,
+    #[cfg(feature = "client")]
+    idx: usize,
+) -> ItemId<()> {
+    let iid = builder.register_item(
+        &format!("{}_{}", material.machine_name, kind_name),
+        #[cfg(feature = "client")]
+        material.names[idx],
+        #[cfg(feature = "client")]
+        ItemMeshLogic::flat(material.tex_indices[idx]),
+    );
+    builder.items_max_count.set(iid, 1.try_into().unwrap());
+    builder.items_max_damage.set(iid, material.uses);
+    builder.items_tool.set(iid, Some(ItemTool { kind, tier: material.tier, speed: material.speed }));
+    iid
+}
@@ -0,0 +1,58 @@
+//! The torch, a thin light source standing in the middle of its tile.
+
+use crate::game_data::content_module_prelude::*;
+
+
+/// The part of a tile a torch's stick fills.
+pub const TORCH_BOX: AaBox = AaBox {
+    pos: Vec3 { x: 7.0 / 16.0, y: 0.0, z: 7.0 / 16.0 },
+    ext: Extent3 { w: 2.0 / 16.0, h: 10.0 / 16.0, d: 2.0 / 16.0 },
+};
+
+/// The part of a tile in which a torch can be hit.
+pub const TORCH_HITSCAN_BOX: AaBox = AaBox {
+    pos: Vec3 { x: 0.4, y: 0.0, z: 0.4 },
+    ext: Extent3 { w: 0.2, h: 0.6, d: 0.2 },
+};
+
+/// Light level torches emit.
+pub const TORCH_LIGHT: u8 = 14;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_torch: BlockId<()>,
+    pub iid_torch: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_torch = builder.register_block(
+            "torch",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::single_box(TORCH_BOX, PerFace::repeat(BTI_TORCH)),
+        );
+        builder.blocks_hitscan_logic
+            .set(bid_torch, BlockHitscanLogic::Boxes(vec![TORCH_HITSCAN_BOX]));
+        builder.blocks_physics_logic.set(bid_torch, BlockPhysicsLogic::NoClip);
+        builder.blocks_light_opacity.set(bid_torch, 0);
+        builder.blocks_light_emission.set(bid_torch, TORCH_LIGHT);
+        builder.blocks_hardness.set(bid_torch, Some(0.0));
+
+        let iid_torch = builder.register_item(
+            "torch",
+            #[cfg(feature = "client")]
+            LangKey::tile_torch_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::flat_block(BTI_TORCH),
+        );
+        builder.items_place_logic.set(iid_torch, ItemPlaceLogic::block(bid_torch, ()));
+        builder.blocks_drop_logic.set(bid_torch, BlockDropLogic::item(iid_torch, ()));
+        // TODO: craft from coal and a stick once coal exists
+
+        ContentModule {
+            bid_torch,
+            iid_torch,
+        }
+    }
+}
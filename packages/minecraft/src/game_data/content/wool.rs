@@ -0,0 +1,35 @@
+
+use crate::game_data::content_module_prelude::*;
+
+
+#[derive(Debug)]
+pub struct ContentModule {
+    pub bid_wool: BlockId<()>,
+    pub iid_wool: ItemId<()>,
+}
+
+impl ContentModule {
+    pub fn init(builder: &mut GameDataBuilder) -> Self {
+        let bid_wool = builder.register_block(
+            "wool",
+            #[cfg(feature = "client")]
+            BlockMeshLogic::basic_cube(BTI_WOOL),
+        );
+        builder.blocks_hardness.set(bid_wool, Some(0.8));
+
+        let iid_wool = builder.register_item(
+            "wool",
+            #[cfg(feature = "client")]
+            LangKey::tile_cloth_name,
+            #[cfg(feature = "client")]
+            ItemMeshLogic::basic_cube(BTI_WOOL),
+        );
+        builder.items_place_logic.set(iid_wool, ItemPlaceLogic::block(bid_wool, ()));
+        builder.blocks_drop_logic.set(bid_wool, BlockDropLogic::item(iid_wool, ()));
+
+        ContentModule {
+            bid_wool,
+            iid_wool,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 
+use crate::physics::aa_box::AaBox;
 use chunk_data::*;
 
 
@@ -6,6 +7,14 @@ use chunk_data::*;
 pub enum BlockMeshLogic {
     NoMesh,
     FullCube(BlockMeshLogicFullCube),
+    /// Fluid, with its top lowered according to its level. Block meta must be `FluidBlockMeta`.
+    Fluid(BlockMeshLogicFluid),
+    /// Boxes within the tile, such as for slabs and stairs. Boxes must not overlap.
+    Boxes(Vec<BlockMeshLogicBox>),
+    /// Two quads crossing diagonally through the tile, such as for plants.
+    Cross(BlockMeshLogicCross),
+    /// Door, closed or open according to its meta. Block meta must be `DoorBlockMeta`.
+    Door,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -14,6 +23,28 @@ pub struct BlockMeshLogicFullCube {
     pub transparent: bool,
 }
 
+#[derive(Debug, Copy, Clone)]
+pub struct BlockMeshLogicFluid {
+    pub tex_index: usize,
+    /// Whether faces are drawn partially see-through.
+    pub translucent: bool,
+}
+
+/// Box within a tile of a block meshed as boxes.
+#[derive(Debug, Copy, Clone)]
+pub struct BlockMeshLogicBox {
+    /// The box, relative to the tile, within the unit cube.
+    pub aa_box: AaBox,
+    /// Texture of each face of the box, the part of which corresponding to the part of the tile's
+    /// face the box's face covers is drawn.
+    pub tex_indices: PerFace<usize>,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct BlockMeshLogicCross {
+    pub tex_index: usize,
+}
+
 impl BlockMeshLogic {
     pub fn basic_cube(tex_index: usize) -> Self {
         BlockMeshLogic::FullCube(BlockMeshLogicFullCube {
@@ -36,10 +67,31 @@ impl BlockMeshLogic {
         })
     }
 
-    pub fn obscures(&self, _face: Face) -> bool {
-        match self {
-            &BlockMeshLogic::NoMesh => false,
-            &BlockMeshLogic::FullCube(mesh_logic) => !mesh_logic.transparent,
+    pub fn single_box(aa_box: AaBox, tex_indices: PerFace<usize>) -> Self {
+        BlockMeshLogic::Boxes(vec![BlockMeshLogicBox { aa_box, tex_indices }])
+    }
+
+    pub fn cross(tex_index: usize) -> Self {
+        BlockMeshLogic::Cross(BlockMeshLogicCross { tex_index })
+    }
+
+    /// Whether instances of this block fully cover their side of the tile in the given direction,
+    /// such that the face of the neighboring tile against it needn't be drawn.
+    pub fn obscures(&self, face: Face) -> bool {
+        match *self {
+            BlockMeshLogic::NoMesh => false,
+            BlockMeshLogic::FullCube(mesh_logic) => !mesh_logic.transparent,
+            BlockMeshLogic::Fluid(_) => false,
+            BlockMeshLogic::Boxes(ref boxes) => {
+                // since boxes don't overlap, the side is covered if their faces on it add up to
+                // its area
+                let area = boxes.iter()
+                    .map(|&BlockMeshLogicBox { aa_box, .. }| aa_box.tile_side_area(face))
+                    .sum::<f32>();
+                area >= 1.0
+            }
+            BlockMeshLogic::Cross(_) => false,
+            BlockMeshLogic::Door => false,
         }
     }
 }
@@ -0,0 +1,163 @@
+//! Logic for blocks whose metadata holds item slots which players can open a menu to access.
+
+use crate::{
+    game_data::GameData,
+    item::ItemStack,
+};
+#[cfg(feature = "client")]
+use crate::asset::Assets;
+#[cfg(feature = "client")]
+use graphics::frame_content::GpuImageArray;
+use chunk_data::*;
+use std::fmt::Debug;
+#[cfg(feature = "client")]
+use std::ops::Range;
+#[cfg(feature = "client")]
+use vek::*;
+
+
+/// Exclusive maximum number of item slots a container may have.
+pub const MAX_CONTAINER_SLOTS: usize = 256;
+
+/// Exclusive maximum number of properties a container may have.
+pub const MAX_CONTAINER_PROPERTIES: usize = 16;
+
+
+/// Logic for a block whose metadata holds item slots which players can open a sync menu to
+/// access, such as a chest.
+///
+/// The server uses this to validate and apply players' changes to the slots and to drop the slots'
+/// contents when the block is broken, and the client uses this to lay out the menu. Slots are
+/// referred to by their index into the slice of slots.
+///
+/// Containers may also have properties, numbers in their metadata which the server sends to
+/// players with the container open when they change so that the menu can display them, such as a
+/// furnace's smelting progress.
+///
+/// Containers' menus may also have a crafting grid, such as a crafting table's. Rather than being
+/// in the container's metadata, the crafting grid's slots are per-player temporary slots: each
+/// player with the menu open uses their own, and their contents are dropped into the world when the
+/// player closes the menu.
+pub trait ContainerLogic: Debug + Send + Sync + 'static {
+    /// The item slots in the metadata of a tile with this block.
+    fn slots<'a>(&self, tile: TileBlockRead<'a>) -> &'a [Option<ItemStack>];
+
+    /// Mutable version of `slots`.
+    fn slots_mut<'a>(&self, tile: TileBlockWrite<'a>) -> &'a mut [Option<ItemStack>];
+
+    /// Whether players may put the given items into the slot at the given index. Players may
+    /// always take items out of slots.
+    fn can_put(&self, idx: usize, stack: &ItemStack, game: &GameData) -> bool {
+        let _ = (idx, stack, game);
+        true
+    }
+
+    /// Items to drop into the world when a tile with this block is broken. By default, the
+    /// contents of all its slots.
+    fn dropped_on_break(&self, tile: TileBlockRead) -> Vec<ItemStack> {
+        self.slots(tile).iter().flatten().cloned().collect()
+    }
+
+    /// The properties in the metadata of a tile with this block. Must always be the same length,
+    /// no more than `MAX_CONTAINER_PROPERTIES`.
+    fn properties(&self, tile: TileBlockRead) -> Vec<u16> {
+        let _ = tile;
+        Vec::new()
+    }
+
+    /// Set the property at the given index in the metadata of a tile with this block. Does nothing
+    /// if the index is out of range.
+    fn set_property(&self, tile: TileBlockWrite, idx: usize, value: u16) {
+        let _ = (tile, idx, value);
+    }
+
+    /// Width of the square crafting grid in the container's menu, if it has one. No more than 3.
+    fn crafting_grid_width(&self) -> Option<usize> {
+        None
+    }
+
+    /// Layout of the container's menu.
+    #[cfg(feature = "client")]
+    fn menu_layout(&self) -> ContainerMenuLayout;
+}
+
+
+/// Layout of a container's menu. Positions and sizes are in pixels of its background texture.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
+pub struct ContainerMenuLayout {
+    /// Title text drawn at the top of the menu.
+    pub title: &'static str,
+    /// Distance from the left of the menu to the title text.
+    pub title_x: f32,
+    /// Size of the menu.
+    pub size: Extent2<f32>,
+    /// Pieces of textures drawn in order to make up the background.
+    pub background: Vec<ContainerMenuBgPiece>,
+    /// Grids of the container's item slots.
+    pub slot_grids: Vec<ContainerSlotGrid>,
+    /// Top left of the grid of the player's inventory, with the hotbar beneath it.
+    pub inventory_pos: Vec2<f32>,
+    /// Progress bars displaying the container's properties.
+    pub progress_bars: Vec<ContainerProgressBar>,
+    /// The crafting grid. Must be `Some` if and only if the container has a crafting grid.
+    pub crafting_grid: Option<ContainerCraftingGrid>,
+}
+
+/// Piece of a texture drawn as part of a container menu's background.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
+pub struct ContainerMenuBgPiece {
+    /// Get the texture from the assets.
+    pub image: fn(&Assets) -> &GpuImageArray,
+    /// Size of the whole texture.
+    pub image_size: Extent2<f32>,
+    /// Top left of the region of the texture to draw.
+    pub src_pos: Vec2<f32>,
+    /// Size of the region of the texture to draw.
+    pub src_size: Extent2<f32>,
+    /// Top left of where in the menu to draw it.
+    pub dst_pos: Vec2<f32>,
+}
+
+/// Grid of a container's item slots in its menu.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
+pub struct ContainerSlotGrid {
+    /// Indexes of the slots in the grid, in row-major order.
+    pub slots: Range<usize>,
+    /// Number of slots per row.
+    pub columns: usize,
+    /// Top left of the grid, which is one pixel up and left of its first slot's item.
+    pub pos: Vec2<f32>,
+}
+
+/// Crafting grid in a container's menu. See `ContainerLogic::crafting_grid_width`.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
+pub struct ContainerCraftingGrid {
+    /// Top left of the grid, which is one pixel up and left of its first slot's item.
+    pub grid_pos: Vec2<f32>,
+    /// Top left of the output slot, which is one pixel up and left of its item.
+    pub output_pos: Vec2<f32>,
+}
+
+/// Progress bar in a container's menu, drawn by drawing part of a background texture piece.
+#[cfg(feature = "client")]
+#[derive(Debug, Clone)]
+pub struct ContainerProgressBar {
+    /// The piece when the bar is full.
+    pub piece: ContainerMenuBgPiece,
+    /// Direction the bar fills towards.
+    pub direction: ProgressBarDirection,
+    /// Compute how full the bar is, from 0 to 1, from the container's properties.
+    pub fraction: fn(&[u16]) -> f32,
+}
+
+/// Direction a container menu progress bar fills towards.
+#[cfg(feature = "client")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProgressBarDirection {
+    Right,
+    Up,
+}
@@ -0,0 +1,304 @@
+//! Crafting recipes, which turn the items in a crafting grid into a new item stack, and smelting
+//! recipes, which turn an item smelted in a furnace into a new item stack.
+//!
+//! Content modules define recipes referring to items by machine name, so that they can use items
+//! registered by other content modules. These are resolved to item IDs once all content modules
+//! are initialized.
+
+use crate::{
+    game_data::{
+        per_item::PerItem,
+        transclone_logic::ItemTranscloner,
+    },
+    item::*,
+};
+use std::{
+    any::TypeId,
+    collections::HashMap,
+    num::NonZeroU8,
+};
+
+
+/// Crafting or smelting recipe defined by a content module, referring to items by machine name.
+///
+/// Ingredients match any item stack of the item, regardless of metadata and damage, and crafting
+/// consumes one item from each non-empty grid slot. Outputs must be items with `()` metadata.
+#[derive(Debug, Clone)]
+pub struct Recipe {
+    inputs: RecipeInputs<String>,
+    output: String,
+    count: NonZeroU8,
+}
+
+// the inputs of a recipe, referring to items as I
+#[derive(Debug, Clone)]
+enum RecipeInputs<I> {
+    // a pattern of slots in row-major order with the given width, with no empty rows or columns
+    // around its edges, which may be placed anywhere in the grid and may be mirrored horizontally
+    Shaped {
+        width: usize,
+        pattern: Vec<Option<I>>,
+    },
+    // ingredients which may be placed in any arrangement
+    Shapeless(Vec<I>),
+    // an item smelted in a furnace, one at a time
+    Smelting(I),
+}
+
+impl Recipe {
+    /// Construct a shaped recipe. Each row of the pattern is a string with a character per column,
+    /// in which spaces are empty slots and other characters are mapped to item machine names by
+    /// the key. Empty rows and columns around the edges of the pattern are trimmed off. Panics if
+    /// rows differ in length, the pattern is empty, or a character isn't in the key.
+    pub fn shaped(pattern: &[&str], key: &[(char, &str)], output: &str, count: u8) -> Self {
+        let rows = pattern.iter().map(|row| row.chars().collect::<Vec<_>>()).collect::<Vec<_>>();
+        let width = rows.first().map(|row| row.len()).unwrap_or(0);
+        assert!(rows.iter().all(|row| row.len() == width), "recipe pattern rows differ in length");
+
+        // trim to the bounding box of the non-empty slots
+        let filled_rows = || (0..rows.len()).filter(|&y| rows[y].iter().any(|&c| c != ' '));
+        let filled_cols = || (0..width).filter(|&x| rows.iter().any(|row| row[x] != ' '));
+        let (Some(min_y), Some(min_x)) = (filled_rows().next(), filled_cols().next()) else {
+            panic!("recipe pattern is empty");
+        };
+        let max_y = filled_rows().next_back().unwrap();
+        let max_x = filled_cols().next_back().unwrap();
+
+        let pattern = rows[min_y..=max_y].iter()
+            .flat_map(|row| &row[min_x..=max_x])
+            .map(|&c| (c != ' ').then(|| key.iter()
+                .find(|&&(c2, _)| c2 == c)
+                .unwrap_or_else(|| panic!("recipe pattern character {:?} not in key", c))
+                .1
+                .to_owned()))
+            .collect();
+        Recipe {
+            inputs: RecipeInputs::Shaped { width: max_x - min_x + 1, pattern },
+            output: output.to_owned(),
+            count: count.try_into().unwrap(),
+        }
+    }
+
+    /// Construct a shapeless recipe, with an item machine name per ingredient.
+    pub fn shapeless(ingredients: &[&str], output: &str, count: u8) -> Self {
+        Recipe {
+            inputs: RecipeInputs::Shapeless(ingredients.iter()
+                .map(|&ingredient| ingredient.to_owned())
+                .collect()),
+            output: output.to_owned(),
+            count: count.try_into().unwrap(),
+        }
+    }
+
+    /// Construct a smelting recipe, with the item machine name of the item smelted.
+    pub fn smelting(input: &str, output: &str, count: u8) -> Self {
+        Recipe {
+            inputs: RecipeInputs::Smelting(input.to_owned()),
+            output: output.to_owned(),
+            count: count.try_into().unwrap(),
+        }
+    }
+}
+
+
+/// All crafting recipes, resolved to item IDs.
+#[derive(Debug)]
+pub struct Recipes {
+    recipes: Vec<ResolvedRecipe>,
+}
+
+#[derive(Debug)]
+struct ResolvedRecipe {
+    inputs: RecipeInputs<RawItemId>,
+    output: RawItemId,
+    count: NonZeroU8,
+}
+
+impl Recipes {
+    /// Resolve recipes' item machine names. Panics if an item isn't registered or an output
+    /// doesn't have `()` metadata.
+    pub fn new(
+        recipes: Vec<Recipe>,
+        items: &ItemRegistry,
+        items_machine_name: &PerItem<String>,
+        items_meta_transcloner: &PerItem<ItemTranscloner>,
+    ) -> Self {
+        let iids = items.iter()
+            .map(|iid| (items_machine_name[iid].as_str(), iid))
+            .collect::<HashMap<_, _>>();
+        let resolve = |machine_name: &str| *iids.get(machine_name)
+            .unwrap_or_else(|| panic!("recipe uses unknown item {:?}", machine_name));
+        Recipes {
+            recipes: recipes.into_iter()
+                .map(|recipe| {
+                    let output = resolve(&recipe.output);
+                    assert!(
+                        items_meta_transcloner[output].instance_type_id() == TypeId::of::<()>(),
+                        "recipe output {:?} doesn't have () metadata",
+                        recipe.output,
+                    );
+                    let inputs = match recipe.inputs {
+                        RecipeInputs::Shaped { width, pattern } => RecipeInputs::Shaped {
+                            width,
+                            pattern: pattern.iter()
+                                .map(|slot| slot.as_deref().map(resolve))
+                                .collect(),
+                        },
+                        RecipeInputs::Shapeless(ingredients) => RecipeInputs::Shapeless(
+                            ingredients.iter().map(|ingredient| resolve(ingredient)).collect()
+                        ),
+                        RecipeInputs::Smelting(input) => RecipeInputs::Smelting(resolve(&input)),
+                    };
+                    ResolvedRecipe { inputs, output, count: recipe.count }
+                })
+                .collect(),
+        }
+    }
+
+    /// Compute the output of crafting with the given crafting grid, in row-major order with the
+    /// given width, if it matches a recipe.
+    pub fn craft(&self, grid: &[Option<ItemStack>], width: usize) -> Option<ItemStack> {
+        let grid = grid.iter()
+            .map(|slot| slot.as_ref().map(|stack| stack.iid))
+            .collect::<Vec<_>>();
+        let recipe = self.recipes.iter()
+            .find(|recipe| match recipe.inputs {
+                RecipeInputs::Shaped { width: pattern_width, ref pattern } =>
+                    shaped_matches(&grid, width, pattern, pattern_width),
+                RecipeInputs::Shapeless(ref ingredients) =>
+                    shapeless_matches(&grid, ingredients),
+                RecipeInputs::Smelting(_) => false,
+            })?;
+        Some(recipe.output_stack())
+    }
+
+    /// Compute the output of smelting one of the given item, if it matches a recipe.
+    pub fn smelt(&self, input: &ItemStack) -> Option<ItemStack> {
+        self.recipes.iter()
+            .find(|recipe| matches!(recipe.inputs, RecipeInputs::Smelting(iid) if iid == input.iid))
+            .map(ResolvedRecipe::output_stack)
+    }
+}
+
+impl ResolvedRecipe {
+    fn output_stack(&self) -> ItemStack {
+        ItemStack {
+            iid: self.output,
+            meta: ItemMeta::new(()),
+            count: self.count,
+            damage: 0,
+        }
+    }
+}
+
+// whether the grid matches the pattern, when trimmed to the bounding box of its non-empty slots
+fn shaped_matches(
+    grid: &[Option<RawItemId>],
+    width: usize,
+    pattern: &[Option<RawItemId>],
+    pattern_width: usize,
+) -> bool {
+    let filled = || (0..grid.len()).filter(|&i| grid[i].is_some());
+    let Some(first) = filled().next() else { return false };
+    let min_x = filled().map(|i| i % width).min().unwrap();
+    let max_x = filled().map(|i| i % width).max().unwrap();
+    let min_y = first / width;
+    let max_y = filled().next_back().unwrap() / width;
+    if max_x - min_x + 1 != pattern_width || (max_y - min_y + 1) * pattern_width != pattern.len() {
+        return false;
+    }
+    let matches = |mirror: bool| (0..pattern.len()).all(|i| {
+        let (x, y) = (i % pattern_width, i / pattern_width);
+        let x = if mirror { pattern_width - 1 - x } else { x };
+        grid[(min_y + y) * width + min_x + x] == pattern[i]
+    });
+    matches(false) || matches(true)
+}
+
+// whether the grid's non-empty slots are the ingredients in some order
+fn shapeless_matches(grid: &[Option<RawItemId>], ingredients: &[RawItemId]) -> bool {
+    let mut grid = grid.iter().filter_map(|&slot| slot).collect::<Vec<_>>();
+    let mut ingredients = ingredients.to_vec();
+    grid.sort();
+    ingredients.sort();
+    grid == ingredients
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[(char, &str)] = &[('#', "planks"), ('/', "stick"), ('o', "coal")];
+
+    // item ID the test key's item machine name resolves to
+    fn resolve(machine_name: &str) -> RawItemId {
+        RawItemId(KEY.iter().position(|&(_, name)| name == machine_name).unwrap() as u16)
+    }
+
+    // whether the recipe matches the crafting grid, written the same way as a shaped pattern
+    fn matches(recipe: &Recipe, grid: &[&str]) -> bool {
+        let width = grid[0].len();
+        let grid = grid.iter()
+            .flat_map(|row| row.chars())
+            .map(|c| (c != ' ')
+                .then(|| resolve(KEY.iter().find(|&&(c2, _)| c2 == c).unwrap().1)))
+            .collect::<Vec<_>>();
+        match recipe.inputs {
+            RecipeInputs::Shaped { width: pattern_width, ref pattern } => {
+                let pattern = pattern.iter()
+                    .map(|slot| slot.as_deref().map(resolve))
+                    .collect::<Vec<_>>();
+                shaped_matches(&grid, width, &pattern, pattern_width)
+            }
+            RecipeInputs::Shapeless(ref ingredients) => {
+                let ingredients = ingredients.iter()
+                    .map(|ingredient| resolve(ingredient))
+                    .collect::<Vec<_>>();
+                shapeless_matches(&grid, &ingredients)
+            }
+            RecipeInputs::Smelting(_) => false,
+        }
+    }
+
+    #[test]
+    fn shaped_matches_anywhere() {
+        let recipe = Recipe::shaped(&[" # ", " # ", "   "], KEY, "stick", 4);
+        assert!(matches(&recipe, &["#  ", "#  ", "   "]));
+        assert!(matches(&recipe, &["   ", "  #", "  #"]));
+        assert!(matches(&recipe, &[" #", " #"]));
+        assert!(matches(&recipe, &["# ", "# "]));
+    }
+
+    #[test]
+    fn shaped_matches_mirrored() {
+        let recipe = Recipe::shaped(&["##", "#/", " /"], KEY, "planks", 1);
+        assert!(matches(&recipe, &["## ", "#/ ", " / "]));
+        assert!(matches(&recipe, &[" ##", " /#", " / "]));
+        assert!(!matches(&recipe, &[" / ", "#/ ", "## "]));
+    }
+
+    #[test]
+    fn shapeless_matches_any_order() {
+        let recipe = Recipe::shapeless(&["coal", "stick", "stick"], "planks", 1);
+        assert!(matches(&recipe, &["o/", "/ "]));
+        assert!(matches(&recipe, &["  /", " o ", "/  "]));
+        assert!(matches(&recipe, &["//o", "   ", "   "]));
+    }
+
+    #[test]
+    fn non_matching_grids() {
+        let shaped = Recipe::shaped(&["o", "/"], KEY, "planks", 1);
+        assert!(!matches(&shaped, &["  ", "  "]));
+        assert!(!matches(&shaped, &["o ", "/o"]));
+        assert!(!matches(&shaped, &["o ", "# "]));
+        assert!(!matches(&shaped, &["o/", "  "]));
+        assert!(!matches(&shaped, &["o ", " /"]));
+
+        let shapeless = Recipe::shapeless(&["coal", "stick"], "planks", 1);
+        assert!(!matches(&shapeless, &["  ", "  "]));
+        assert!(!matches(&shapeless, &["o ", "  "]));
+        assert!(!matches(&shapeless, &["o/", "/ "]));
+        assert!(!matches(&shapeless, &["o#", "  "]));
+    }
+}
@@ -0,0 +1,79 @@
+use crate::{
+    game_data::GameData,
+    item::RawItemId,
+};
+use chunk_data::*;
+
+
+/// Kind of tool, which digs certain blocks faster.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ToolKind {
+    Pickaxe,
+    Shovel,
+    Axe,
+}
+
+/// Which tool digs instances of a block faster.
+#[derive(Debug, Copy, Clone)]
+pub struct BlockDigTool {
+    /// Kind of tool which digs the block faster.
+    pub kind: ToolKind,
+    /// If the block can only be harvested with this kind of tool, the minimum tier the tool must
+    /// be. Digging a block one can't harvest is much slower and yields nothing.
+    pub harvest_tier: Option<u8>,
+}
+
+impl BlockDigTool {
+    /// Dug faster by the given kind of tool, but can be harvested without it.
+    pub fn faster(kind: ToolKind) -> Self {
+        BlockDigTool { kind, harvest_tier: None }
+    }
+
+    /// Can only be harvested with the given kind of tool of at least the given tier.
+    pub fn required(kind: ToolKind, harvest_tier: u8) -> Self {
+        BlockDigTool { kind, harvest_tier: Some(harvest_tier) }
+    }
+}
+
+/// What makes an item a tool.
+#[derive(Debug, Copy, Clone)]
+pub struct ItemTool {
+    /// Kind of tool it is.
+    pub kind: ToolKind,
+    /// Tier of the tool, which determines which blocks it can harvest.
+    pub tier: u8,
+    /// Multiplier on dig speed for blocks this kind of tool digs faster.
+    pub speed: f32,
+}
+
+/// Whether a player using the given item, or their hand, can harvest instances of the given
+/// block.
+pub fn can_harvest(game: &GameData, bid: RawBlockId, iid: Option<RawItemId>) -> bool {
+    let Some(block_tool) = game.blocks_dig_tool[bid] else { return true };
+    let Some(harvest_tier) = block_tool.harvest_tier else { return true };
+    iid
+        .and_then(|iid| game.items_tool[iid])
+        .is_some_and(|item_tool| item_tool.kind == block_tool.kind && item_tool.tier >= harvest_tier)
+}
+
+/// Number of ticks it takes a player using the given item, or their hand, to dig an instance of
+/// the given block, or `None` if it can't be dug.
+///
+/// Follows beta 1.0.2's formula, wherein a block which can be harvested takes 30 ticks per unit of
+/// hardness divided by tool speed, and a block which can't be harvested takes 100 ticks per unit
+/// of hardness.
+pub fn dig_ticks(game: &GameData, bid: RawBlockId, iid: Option<RawItemId>) -> Option<u64> {
+    let hardness = game.blocks_hardness[bid]?;
+    let ticks =
+        if can_harvest(game, bid, iid) {
+            let speed = game.blocks_dig_tool[bid]
+                .zip(iid.and_then(|iid| game.items_tool[iid]))
+                .filter(|&(block_tool, item_tool)| block_tool.kind == item_tool.kind)
+                .map(|(_, item_tool)| item_tool.speed)
+                .unwrap_or(1.0);
+            hardness * 30.0 / speed
+        } else {
+            hardness * 100.0
+        };
+    Some(ticks.ceil() as u64)
+}
@@ -0,0 +1,37 @@
+use crate::{
+    game_data::GameData,
+    item::*,
+};
+use std::fmt::Debug;
+
+
+/// Logic for what instances of each block drop when a player digs them and can harvest them.
+#[derive(Debug)]
+pub enum BlockDropLogic {
+    /// Drop nothing.
+    Nothing,
+    /// Drop a clone of the given item stack.
+    Item(ItemStack),
+    /// Drop whatever the given function returns, such as for blocks which drop items registered by
+    /// other content modules.
+    Custom(fn(&GameData) -> Option<ItemStack>),
+}
+
+impl BlockDropLogic {
+    /// Construct an `Item` variant dropping one of the given typed item ID and meta.
+    pub fn item<M>(iid: ItemId<M>, meta: M) -> Self
+    where
+        M: Debug + Clone + PartialEq + Send + Sync + 'static,
+    {
+        BlockDropLogic::Item(ItemStack::new(iid, meta))
+    }
+
+    /// Compute the item stack dropped, if any.
+    pub fn dropped(&self, game: &GameData) -> Option<ItemStack> {
+        match *self {
+            BlockDropLogic::Nothing => None,
+            BlockDropLogic::Item(ref stack) => Some(stack.clone()),
+            BlockDropLogic::Custom(f) => f(game),
+        }
+    }
+}
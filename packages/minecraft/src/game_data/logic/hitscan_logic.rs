@@ -1,6 +1,12 @@
+use crate::physics::aa_box::AaBox;
+
 
 #[derive(Debug)]
 pub enum BlockHitscanLogic {
     Vacuous,
     BasicCube,
+    /// Boxes within the tile, relative to it.
+    Boxes(Vec<AaBox>),
+    /// Door, closed or open according to its meta. Block meta must be `DoorBlockMeta`.
+    Door,
 }
@@ -1,3 +1,5 @@
+use super::block_mesh_logic::BlockMeshLogicBox;
+
 
 #[derive(Debug)]
 pub enum ItemMeshLogic {
@@ -5,7 +7,17 @@ pub enum ItemMeshLogic {
         top_tex_index: usize,
         left_tex_index: usize,
         right_tex_index: usize,
-    }
+    },
+    /// Boxes within the unit cube, such as for slabs and stairs, drawn like the block they place.
+    Boxes(Vec<BlockMeshLogicBox>),
+    /// Drawn as a flat image from the item texture atlas rather than as a mesh.
+    Flat {
+        tex_index: usize,
+    },
+    /// Drawn as a flat image from the block texture atlas, such as for plants and torches.
+    FlatBlock {
+        tex_index: usize,
+    },
 }
 
 impl ItemMeshLogic {
@@ -16,4 +28,12 @@ impl ItemMeshLogic {
             right_tex_index: tex_index,
         }
     }
+
+    pub fn flat(tex_index: usize) -> Self {
+        ItemMeshLogic::Flat { tex_index }
+    }
+
+    pub fn flat_block(tex_index: usize) -> Self {
+        ItemMeshLogic::FlatBlock { tex_index }
+    }
 }
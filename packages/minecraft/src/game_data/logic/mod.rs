@@ -2,6 +2,14 @@
 pub mod transclone_logic;
 pub mod hitscan_logic;
 pub mod physics_logic;
+pub mod update_logic;
+pub mod random_tick_logic;
+pub mod place_logic;
+pub mod use_logic;
+pub mod dig_logic;
+pub mod drop_logic;
+pub mod container_logic;
+pub mod crafting_logic;
 
 #[cfg(feature = "client")]
 pub mod item_mesh_logic;
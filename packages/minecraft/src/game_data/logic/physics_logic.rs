@@ -1,6 +1,29 @@
+use crate::{
+    game_data::content::door::DoorBlockMeta,
+    physics::aa_box::AaBox,
+};
+use chunk_data::*;
+
 
 #[derive(Debug)]
 pub enum BlockPhysicsLogic {
     NoClip,
     BasicCube,
+    /// Boxes within the tile, relative to it.
+    Boxes(Vec<AaBox>),
+    /// Door, closed or open according to its meta. Block meta must be `DoorBlockMeta`.
+    Door,
+}
+
+impl BlockPhysicsLogic {
+    /// Boxes within the tile, relative to it, which entities collide with, for an instance of this
+    /// block with the given meta.
+    pub fn boxes(&self, meta: &ErasedBlockMeta) -> &[AaBox] {
+        match self {
+            BlockPhysicsLogic::NoClip => &[],
+            BlockPhysicsLogic::BasicCube => std::slice::from_ref(&AaBox::UNIT_BOX),
+            BlockPhysicsLogic::Boxes(aa_boxes) => aa_boxes,
+            BlockPhysicsLogic::Door => std::slice::from_ref(meta.cast::<DoorBlockMeta>().aa_box()),
+        }
+    }
 }
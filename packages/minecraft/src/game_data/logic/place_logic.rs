@@ -0,0 +1,28 @@
+use crate::server::SyncWorld;
+use chunk_data::*;
+use vek::*;
+use std::fmt::Debug;
+
+
+/// Logic for what happens when a player places instances of each item into the world.
+#[derive(Debug)]
+pub enum ItemPlaceLogic {
+    /// Can't be placed.
+    Nothing,
+    /// Consume one of the item to place a clone of the given block and meta.
+    Block(ErasedBidMeta),
+    /// Call the given function with the tile to place at, which has already been validated to be
+    /// loaded, placeable over, and in reach. If it returns true, one of the item is consumed. Used
+    /// for items which place more than one tile, such as doors.
+    Custom(fn(&mut SyncWorld, Vec3<i64>) -> bool),
+}
+
+impl ItemPlaceLogic {
+    /// Construct a `Block` variant from a typed block ID and meta.
+    pub fn block<M>(bid: BlockId<M>, meta: M) -> Self
+    where
+        M: Debug + Send + Sync + 'static,
+    {
+        ItemPlaceLogic::Block(ErasedBidMeta::new(bid, meta))
+    }
+}
@@ -0,0 +1,19 @@
+
+use crate::server::SyncWorld;
+use chunk_data::*;
+use rand::RngCore;
+
+
+/// Logic for how instances of each block react to random ticks.
+///
+/// Each tick, a number of randomly chosen tiles in each loaded chunk receive a random tick,
+/// according to the `random_tick_speed` game rule. This drives slow ambient behavior such as grass
+/// spreading.
+#[derive(Debug)]
+pub enum BlockRandomTickLogic {
+    /// Ignore random ticks.
+    Nothing,
+    /// Call the given function with the ticked tile and the random source, which is seeded
+    /// deterministically from the world seed, tick number, and chunk.
+    Custom(fn(&mut SyncWorld, TileKey, &mut dyn RngCore)),
+}
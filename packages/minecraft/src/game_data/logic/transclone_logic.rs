@@ -199,6 +199,9 @@ macro_rules! item_transcloner {
 block_transcloner!(
     Unit (),
     ChestBlockMeta content::chest::ChestBlockMeta,
+    FluidBlockMeta content::fluid::FluidBlockMeta,
+    DoorBlockMeta content::door::DoorBlockMeta,
+    FurnaceBlockMeta content::furnace::FurnaceBlockMeta,
 );
 
 item_transcloner!(
@@ -0,0 +1,26 @@
+
+use crate::server::SyncWorld;
+use chunk_data::*;
+
+
+/// Logic for how instances of each block react to block updates.
+///
+/// A tile receives a block update on the tick after its block or the block of one of its
+/// neighbors changes, see `block_update_queue`. Game logic can also schedule a tile to receive a
+/// block update on a particular future tick, see `ServerSyncCtx::schedule_tick`.
+#[derive(Debug)]
+pub enum BlockUpdateLogic {
+    /// Ignore block updates.
+    Nothing,
+    /// Call the given function with the updated tile and the cause of the update.
+    Custom(fn(&mut SyncWorld, TileKey, BlockUpdateCause)),
+}
+
+/// Why a tile is receiving a block update.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BlockUpdateCause {
+    /// The tile's block or the block of one of its neighbors changed.
+    Changed,
+    /// A block tick scheduled for the tile fired.
+    Scheduled,
+}
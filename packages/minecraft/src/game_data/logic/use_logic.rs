@@ -0,0 +1,14 @@
+use crate::server::SyncWorld;
+use chunk_data::*;
+
+
+/// Logic for what happens when a player uses (right-clicks) instances of each block.
+///
+/// Blocks with container logic open their menu instead.
+#[derive(Debug)]
+pub enum BlockUseLogic {
+    /// Using does nothing, so the player places a block against it instead.
+    Nothing,
+    /// Call the given function with the tile used.
+    Custom(fn(&mut SyncWorld, TileKey)),
+}
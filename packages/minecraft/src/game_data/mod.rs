@@ -9,6 +9,14 @@ pub mod content;
 pub use self::logic::{
     hitscan_logic,
     physics_logic,
+    update_logic,
+    random_tick_logic,
+    place_logic,
+    use_logic,
+    dig_logic,
+    drop_logic,
+    container_logic,
+    crafting_logic,
     transclone_logic,
 };
 
@@ -24,6 +32,14 @@ use self::{
     per_item::PerItem,
     hitscan_logic::BlockHitscanLogic,
     physics_logic::BlockPhysicsLogic,
+    update_logic::BlockUpdateLogic,
+    random_tick_logic::BlockRandomTickLogic,
+    place_logic::ItemPlaceLogic,
+    use_logic::BlockUseLogic,
+    dig_logic::{BlockDigTool, ItemTool},
+    drop_logic::BlockDropLogic,
+    container_logic::ContainerLogic,
+    crafting_logic::{Recipe, Recipes},
     transclone_logic::{
         BlockTranscloner,
         BlockTransclonerFor,
@@ -32,9 +48,12 @@ use self::{
     },
     content::ContentModules,
 };
-use crate::item::{
-    ItemRegistry,
-    ItemId,
+use crate::{
+    item::{
+        ItemRegistry,
+        ItemId,
+    },
+    lighting::MAX_LIGHT,
 };
 use chunk_data::*;
 use std::{
@@ -62,6 +81,14 @@ pub mod content_module_prelude {
         per_block::PerBlock,
         hitscan_logic::BlockHitscanLogic,
         physics_logic::BlockPhysicsLogic,
+        update_logic::{BlockUpdateLogic, BlockUpdateCause},
+        random_tick_logic::BlockRandomTickLogic,
+        place_logic::ItemPlaceLogic,
+        use_logic::BlockUseLogic,
+        dig_logic::{ToolKind, BlockDigTool, ItemTool},
+        drop_logic::BlockDropLogic,
+        container_logic::ContainerLogic,
+        crafting_logic::Recipe,
         transclone_logic::{
             BlockTranscloner,
             BlockTransclonerFor,
@@ -72,12 +99,23 @@ pub mod content_module_prelude {
     };
     pub use crate::{
         item::*,
+        lighting::MAX_LIGHT,
+        server::SyncWorld,
+        physics::aa_box::AaBox,
         util_array::array_default,
     };
     #[cfg(feature = "client")]
     pub use super::{
-        block_mesh_logic::BlockMeshLogic,
+        block_mesh_logic::{BlockMeshLogic, BlockMeshLogicFluid, BlockMeshLogicBox},
         item_mesh_logic::ItemMeshLogic,
+        container_logic::{
+            ContainerMenuLayout,
+            ContainerMenuBgPiece,
+            ContainerSlotGrid,
+            ContainerProgressBar,
+            ProgressBarDirection,
+            ContainerCraftingGrid,
+        },
     };
     #[cfg(feature = "client")]
     pub use crate::{
@@ -112,7 +150,16 @@ pub struct GameDataBuilder {
     pub blocks_meta_transcloner: PerBlock<BlockTranscloner>,
     pub blocks_hitscan_logic: PerBlock<BlockHitscanLogic>,
     pub blocks_physics_logic: PerBlock<BlockPhysicsLogic>,
+    pub blocks_update_logic: PerBlock<BlockUpdateLogic>,
+    pub blocks_random_tick_logic: PerBlock<BlockRandomTickLogic>,
     pub blocks_can_place_over: PerBlock<bool>,
+    pub blocks_light_opacity: PerBlock<u8>,
+    pub blocks_light_emission: PerBlock<u8>,
+    pub blocks_hardness: PerBlock<Option<f32>>,
+    pub blocks_dig_tool: PerBlock<Option<BlockDigTool>>,
+    pub blocks_drop_logic: PerBlock<BlockDropLogic>,
+    pub blocks_container_logic: PerBlock<Option<Box<dyn ContainerLogic>>>,
+    pub blocks_use_logic: PerBlock<BlockUseLogic>,
 
 
     // ==== items ====
@@ -131,6 +178,13 @@ pub struct GameDataBuilder {
     pub items_max_count: PerItem<NonZeroU8>,
     /// Inclusive upper bound on its damage level.
     pub items_max_damage: PerItem<u16>,
+    pub items_place_logic: PerItem<ItemPlaceLogic>,
+    pub items_tool: PerItem<Option<ItemTool>>,
+    pub items_fuel_ticks: PerItem<u16>,
+
+
+    // ==== recipes ====
+    pub recipes: Vec<Recipe>,
 }
 
 impl GameDataBuilder {
@@ -192,12 +246,34 @@ pub struct GameData {
     pub blocks_hitscan_logic: PerBlock<BlockHitscanLogic>,
     /// Logic for physics geometry of instances of each block.
     pub blocks_physics_logic: PerBlock<BlockPhysicsLogic>,
+    /// Logic for how instances of each block react to block updates.
+    pub blocks_update_logic: PerBlock<BlockUpdateLogic>,
+    /// Logic for how instances of each block react to random ticks.
+    pub blocks_random_tick_logic: PerBlock<BlockRandomTickLogic>,
     /// Whether instances of each block can be "placed over".
     ///
     /// This means that some other block can be placed where an instance of this block is without
     /// first removing this block, and this block just gets frictionlessly overwritten with the new
     /// one.
     pub blocks_can_place_over: PerBlock<bool>,
+    /// How much light is diminished by passing through instances of each block, from 0 through
+    /// `MAX_LIGHT`. Light is always diminished by at least 1 per tile, except for sky light
+    /// passing straight down through blocks with opacity 0. See the `lighting` module.
+    pub blocks_light_opacity: PerBlock<u8>,
+    /// Level of block light emitted by instances of each block, from 0 through `MAX_LIGHT`.
+    pub blocks_light_emission: PerBlock<u8>,
+    /// How hard instances of each block are to dig, or `None` if they can't be dug. See
+    /// `dig_logic::dig_ticks`.
+    pub blocks_hardness: PerBlock<Option<f32>>,
+    /// Which tool digs instances of each block faster, if any.
+    pub blocks_dig_tool: PerBlock<Option<BlockDigTool>>,
+    /// Logic for what instances of each block drop when harvested.
+    pub blocks_drop_logic: PerBlock<BlockDropLogic>,
+    /// For blocks whose metadata holds item slots players can open a menu to access, logic for
+    /// accessing them.
+    pub blocks_container_logic: PerBlock<Option<Box<dyn ContainerLogic>>>,
+    /// Logic for what happens when a player uses instances of each block.
+    pub blocks_use_logic: PerBlock<BlockUseLogic>,
     
     /// The space of items, of which instances can exist.
     pub items: ItemRegistry,
@@ -210,6 +286,17 @@ pub struct GameData {
     pub items_meta_transcloner: PerItem<ItemTranscloner>,
     pub items_max_count: PerItem<NonZeroU8>,
     pub items_max_damage: PerItem<u16>,
+    /// Logic for what happens when a player places instances of each item into the world.
+    pub items_place_logic: PerItem<ItemPlaceLogic>,
+    /// What makes instances of each item a tool, if anything.
+    pub items_tool: PerItem<Option<ItemTool>>,
+    /// How many ticks instances of each item burn for when used as furnace fuel, or 0 if they
+    /// can't be.
+    pub items_fuel_ticks: PerItem<u16>,
+
+    /// Recipes for turning the items in a crafting grid, or items smelted in a furnace, into other
+    /// items.
+    pub recipes: Recipes,
 
     /// See content modules docs.
     pub content: ContentModules,
@@ -225,7 +312,16 @@ impl GameData {
             blocks_meta_transcloner: PerBlock::new(BlockTranscloner::Unit),
             blocks_hitscan_logic: PerBlock::new(BlockHitscanLogic::BasicCube),
             blocks_physics_logic: PerBlock::new(BlockPhysicsLogic::BasicCube),
+            blocks_update_logic: PerBlock::new(BlockUpdateLogic::Nothing),
+            blocks_random_tick_logic: PerBlock::new(BlockRandomTickLogic::Nothing),
             blocks_can_place_over: PerBlock::new(false),
+            blocks_light_opacity: PerBlock::new(MAX_LIGHT),
+            blocks_light_emission: PerBlock::new(0),
+            blocks_hardness: PerBlock::new(Some(0.0)),
+            blocks_dig_tool: PerBlock::new(None),
+            blocks_drop_logic: PerBlock::new(BlockDropLogic::Nothing),
+            blocks_container_logic: PerBlock::new(None),
+            blocks_use_logic: PerBlock::new(BlockUseLogic::Nothing),
 
             #[cfg(feature = "client")]
             blocks_mesh_logic: PerBlock::new_no_default(),
@@ -240,6 +336,11 @@ impl GameData {
             items_mesh_logic: PerItem::new_no_default(),
             items_max_count: PerItem::new(64.try_into().unwrap()),
             items_max_damage: PerItem::new(0),
+            items_place_logic: PerItem::new(ItemPlaceLogic::Nothing),
+            items_tool: PerItem::new(None),
+            items_fuel_ticks: PerItem::new(0),
+
+            recipes: Vec::new(),
         };
 
         let content = ContentModules::init(&mut builder);
@@ -256,6 +357,13 @@ impl GameData {
         // TODO: validate item metadata type
         // TODO: warn about item types without names
 
+        let recipes = Recipes::new(
+            builder.recipes,
+            &builder.items,
+            &builder.items_machine_name,
+            &builder.items_meta_transcloner,
+        );
+
         GameData {
             blocks: builder.blocks.finalize(),
 
@@ -263,7 +371,16 @@ impl GameData {
             blocks_meta_transcloner: builder.blocks_meta_transcloner,
             blocks_hitscan_logic: builder.blocks_hitscan_logic,
             blocks_physics_logic: builder.blocks_physics_logic,
+            blocks_update_logic: builder.blocks_update_logic,
+            blocks_random_tick_logic: builder.blocks_random_tick_logic,
             blocks_can_place_over: builder.blocks_can_place_over,
+            blocks_light_opacity: builder.blocks_light_opacity,
+            blocks_light_emission: builder.blocks_light_emission,
+            blocks_hardness: builder.blocks_hardness,
+            blocks_dig_tool: builder.blocks_dig_tool,
+            blocks_drop_logic: builder.blocks_drop_logic,
+            blocks_container_logic: builder.blocks_container_logic,
+            blocks_use_logic: builder.blocks_use_logic,
 
             #[cfg(feature = "client")]
             blocks_mesh_logic: builder.blocks_mesh_logic,
@@ -278,7 +395,11 @@ impl GameData {
             items_meta_transcloner: builder.items_meta_transcloner,
             items_max_count: builder.items_max_count,
             items_max_damage: builder.items_max_damage,
+            items_place_logic: builder.items_place_logic,
+            items_tool: builder.items_tool,
+            items_fuel_ticks: builder.items_fuel_ticks,
 
+            recipes,
 
             content
         }
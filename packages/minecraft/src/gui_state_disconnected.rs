@@ -0,0 +1,81 @@
+use crate::gui::prelude::*;
+use std::fmt::{self, Formatter, Debug};
+use vek::*;
+
+
+/// Gui state for the screen shown upon being disconnected from the server while in game.
+pub struct DisconnectedMenu {
+    title_text: GuiTextBlock<true>,
+    reason_text: GuiTextBlock<true>,
+    done_button: MenuButton,
+}
+
+impl DisconnectedMenu {
+    pub fn new(ctx: &GuiGlobalContext, reason: &str) -> Self {
+        let title_text = GuiTextBlock::new(&GuiTextBlockConfig {
+            text: "Disconnected",
+            font: ctx.assets.font,
+            logical_font_size: 16.0,
+            color: Rgba::white(),
+            h_align: HAlign::Center,
+            v_align: VAlign::Top,
+            shadow: true,
+        });
+        let reason_text = GuiTextBlock::new(&GuiTextBlockConfig {
+            text: reason,
+            font: ctx.assets.font,
+            logical_font_size: 16.0,
+            color: Rgba::new(0.63, 0.63, 0.63, 1.0),
+            h_align: HAlign::Center,
+            v_align: VAlign::Top,
+            shadow: true,
+        });
+        let done_button = menu_button("Back to title screen").build(ctx.assets);
+        DisconnectedMenu {
+            title_text,
+            reason_text,
+            done_button,
+        }
+    }
+
+    fn gui<'a>(
+        &'a mut self,
+        ctx: &'a GuiWindowContext,
+    ) -> impl GuiBlock<'a, DimParentSets, DimParentSets>
+    {
+        layer((
+            modify(Rgba::new(0.25, 0.25, 0.25, 1.0),
+                tile_image(&ctx.assets().menu_bg, 64.0)
+            ),
+            align(0.5,
+                logical_width(562.0,
+                    v_stack(0.0, (
+                        &mut self.title_text,
+                        logical_height(24.0, gap()),
+                        &mut self.reason_text,
+                        logical_height(64.0, gap()),
+                        h_align(0.5,
+                            logical_width(400.0,
+                                self.done_button.gui(on_done_click)
+                            )
+                        ),
+                    ))
+                )
+            )
+        ))
+    }
+}
+
+impl GuiStateFrame for DisconnectedMenu {
+    impl_visit_nodes!();
+}
+
+fn on_done_click(ctx: &GuiGlobalContext) {
+    ctx.pop_state_frame();
+}
+
+impl Debug for DisconnectedMenu {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str("DisconnectedMenu { .. }")
+    }
+}
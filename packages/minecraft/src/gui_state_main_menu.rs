@@ -10,7 +10,6 @@ use crate::{
         ServerLocation,
         spawn_join_server_thread,
     },
-    message::*,
 };
 use graphics::{
 	Renderer,
@@ -151,7 +150,8 @@ fn on_singleplayer_click(ctx: &GuiGlobalContext) {
         },
         &ctx.game,
         &ctx.thread_pool,
-        UpMsgLogIn { username: "client".to_owned() },
+        ctx.settings().username.clone(),
+        ctx.data_dir.clone(),
         ctx.renderer.borrow().create_async_gpu_vec_context(),
     );
     ctx.push_state_frame(LoadingMenu::new(ctx, oneshot));
@@ -3,7 +3,6 @@ use crate::{
     gui::prelude::*,
     gui_state_loading::LoadingMenu,
     util_hex_color::hex_color,
-    message::*,
     client::join_server::{
         spawn_join_server_thread,
         ServerLocation,
@@ -176,7 +175,8 @@ fn on_connect_click<'a>(address: &'a str) -> impl FnOnce(&GuiGlobalContext) + 'a
             },
             &ctx.game,
             &ctx.thread_pool,
-            UpMsgLogIn { username: "client".to_owned() },
+            ctx.settings().username.clone(),
+            ctx.data_dir.clone(),
             ctx.renderer.borrow().create_async_gpu_vec_context(),
         );
         ctx.push_state_frame(LoadingMenu::new(ctx, oneshot));
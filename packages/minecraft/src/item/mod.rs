@@ -123,7 +123,7 @@ impl ItemRegistry {
 
 /// Self-contained structure for a stack of items, kinda analogous to a tile
 /// block.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct ItemStack {
     /// Item ID.
     pub iid: RawItemId,
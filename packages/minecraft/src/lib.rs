@@ -26,6 +26,8 @@ pub mod util_usize_lt;
 pub mod util_time;
 pub mod util_erased_option_ref;
 pub mod block_update_queue;
+pub mod lighting;
+pub mod day_night;
 pub mod game_data;
 pub mod item;
 pub mod physics;
@@ -40,12 +42,15 @@ pub mod gui_state_about;
 pub mod gui_state_loading;
 #[cfg(feature = "client")]
 pub mod gui_state_loading_failure;
+#[cfg(feature = "client")]
+pub mod gui_state_disconnected;
 //pub mod save_file;
 pub mod thread_pool;
 pub mod settings;
 //pub mod client;
 pub mod server;
 pub mod message;
+pub mod auth;
 pub mod sync_state_tile_blocks;
 pub mod sync_state_inventory_slots;
 pub mod sync_state_steve;
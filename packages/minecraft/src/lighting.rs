@@ -0,0 +1,380 @@
+//! Per-tile sky light and block light levels, and their propagation.
+//!
+//! Each tile has two light levels, each from 0 through `MAX_LIGHT`. Sky light enters the world
+//! from above, and propagates straight down through fully transparent tiles without diminishing.
+//! Block light is emitted by light-emitting blocks. Both otherwise propagate to adjacent tiles,
+//! diminishing per tile by the light opacity of the tile they're entering, or at least by 1.
+//!
+//! Light is a pure function of the blocks in the world, so it is neither saved nor synchronized.
+//! Rather, the server and client each keep their own `LightEngine` up to date as chunks are added
+//! and removed and blocks change. Sky light enters the top of a chunk as open sky if the chunk
+//! above it is not loaded.
+
+use crate::game_data::GameData;
+use chunk_data::*;
+use std::collections::VecDeque;
+use vek::*;
+
+
+/// Maximum light level.
+pub const MAX_LIGHT: u8 = 15;
+
+
+/// Light levels for each tile in a chunk, stored as nibbles.
+#[derive(Debug, Clone, Default)]
+pub struct ChunkLight {
+    /// Sky light level for each tile.
+    pub sky: PerTileU4,
+    /// Block light level for each tile.
+    pub block: PerTileU4,
+}
+
+impl ChunkLight {
+    /// Construct with all tiles completely dark.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the overall light level of a tile, which is the greater of its sky light and block
+    /// light levels.
+    pub fn get(&self, lti: u16) -> u8 {
+        self.sky.get(lti).max(self.block.get(lti))
+    }
+}
+
+/// Tracks the light levels of every tile in all loaded chunks, and propagates changes to them.
+///
+/// Methods take a callback which is called on tiles whose light level may have changed.
+#[derive(Debug, Default)]
+pub struct LightEngine {
+    chunk_light: PerChunk<ChunkLight>,
+    // tiles which should spread their light level to their neighbors
+    spread_queue: VecDeque<TileKey>,
+    // tiles which were darkened and the light level they had, which should be removed from their
+    // neighbors which may have been lit by them
+    unspread_queue: VecDeque<(TileKey, u8)>,
+}
+
+// the two independent kinds of light
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Channel {
+    Sky,
+    Block,
+}
+
+const CHANNELS: [Channel; 2] = [Channel::Sky, Channel::Block];
+
+// world state the light engine reads from while propagating light
+struct Ctx<'c, F> {
+    getter: &'c Getter<'c>,
+    tile_blocks: &'c PerChunk<ChunkBlocks>,
+    game: &'c GameData,
+    changed: F,
+}
+
+impl<'c, F> Ctx<'c, F> {
+    fn opacity(&self, tile: TileKey) -> u8 {
+        self.game.blocks_light_opacity[tile.get(self.tile_blocks).get()]
+    }
+}
+
+impl LightEngine {
+    /// Construct with no chunks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the light levels of all loaded chunks.
+    pub fn chunk_light(&self) -> &PerChunk<ChunkLight> {
+        &self.chunk_light
+    }
+
+    /// Get the overall light level of a tile. See `ChunkLight::get`.
+    pub fn get(&self, tile: TileKey) -> u8 {
+        self.chunk_light.get(tile.cc, tile.ci).get(tile.lti)
+    }
+
+    /// Get the sky light level of a tile.
+    pub fn get_sky(&self, tile: TileKey) -> u8 {
+        self.chunk_light.get(tile.cc, tile.ci).sky.get(tile.lti)
+    }
+
+    /// Get the block light level of a tile.
+    pub fn get_block(&self, tile: TileKey) -> u8 {
+        self.chunk_light.get(tile.cc, tile.ci).block.get(tile.lti)
+    }
+
+    /// Call upon a chunk being added to the world, after its blocks are added to `tile_blocks`.
+    ///
+    /// Computes the chunk's light, and propagates changes into its neighbors. Only calls `changed`
+    /// on tiles outside of the added chunk.
+    pub fn add_chunk<F: FnMut(TileKey)>(
+        &mut self,
+        cc: Vec3<i64>,
+        ci: usize,
+        getter: &Getter,
+        tile_blocks: &PerChunk<ChunkBlocks>,
+        game: &GameData,
+        mut changed: F,
+    ) {
+        self.chunk_light.add(cc, ci, ChunkLight::new());
+        let mut ctx = Ctx {
+            getter,
+            tile_blocks,
+            game,
+            changed: |tile: TileKey| if tile.ci != ci { changed(tile) },
+        };
+
+        for ch in CHANNELS {
+            // light sources within the chunk
+            match ch {
+                Channel::Sky => for lti in border_ltis(Face::PosY) {
+                    self.seed(ch, TileKey { cc, ci, lti }, &mut ctx);
+                },
+                Channel::Block => for lti in 0..=MAX_LTI {
+                    self.seed(ch, TileKey { cc, ci, lti }, &mut ctx);
+                },
+            }
+
+            // light entering from neighboring chunks
+            for face in FACES {
+                let cc2 = cc + face.to_vec();
+                if let Some(ci2) = self.get_added(getter, cc2) {
+                    for lti in border_ltis(-face) {
+                        let tile2 = TileKey { cc: cc2, ci: ci2, lti };
+                        if self.level(ch, tile2) > 0 {
+                            self.spread_queue.push_back(tile2);
+                        }
+                    }
+                }
+            }
+
+            self.run_spread(ch, &mut ctx);
+        }
+
+        // sky light no longer enters the chunk below from open sky, so darken it where the light
+        // entering it from this chunk is dimmer
+        let cc2 = cc - Vec3::unit_y();
+        if let Some(ci2) = self.get_added(getter, cc2) {
+            for (lti, lti2) in border_ltis(Face::NegY).zip(border_ltis(Face::PosY)) {
+                let tile = TileKey { cc, ci, lti };
+                let tile2 = TileKey { cc: cc2, ci: ci2, lti: lti2 };
+                let opacity = ctx.opacity(tile2);
+                let from_sky = spread_level(Channel::Sky, MAX_LIGHT, Face::NegY, opacity);
+                let from_above =
+                    spread_level(Channel::Sky, self.level(Channel::Sky, tile), Face::NegY, opacity);
+                let level = self.level(Channel::Sky, tile2);
+                if from_above < from_sky && level > 0 {
+                    self.set_level(Channel::Sky, tile2, 0, &mut ctx);
+                    self.unspread_queue.push_back((tile2, level));
+                }
+            }
+            self.run_unspread(Channel::Sky, &mut ctx);
+            self.run_spread(Channel::Sky, &mut ctx);
+        }
+    }
+
+    /// Call upon a chunk being removed from the world, after it is removed from `getter`'s
+    /// `LoadedChunks`.
+    ///
+    /// Light which propagated out of the removed chunk into its neighbors is left as-is, except
+    /// that sky light now enters the chunk below it from open sky.
+    pub fn remove_chunk<F: FnMut(TileKey)>(
+        &mut self,
+        cc: Vec3<i64>,
+        ci: usize,
+        getter: &Getter,
+        tile_blocks: &PerChunk<ChunkBlocks>,
+        game: &GameData,
+        changed: F,
+    ) {
+        self.chunk_light.remove(cc, ci);
+        let mut ctx = Ctx { getter, tile_blocks, game, changed };
+        let cc2 = cc - Vec3::unit_y();
+        if let Some(ci2) = self.get_added(getter, cc2) {
+            for lti in border_ltis(Face::PosY) {
+                self.seed(Channel::Sky, TileKey { cc: cc2, ci: ci2, lti }, &mut ctx);
+            }
+            self.run_spread(Channel::Sky, &mut ctx);
+        }
+    }
+
+    /// Call upon a tile's block changing, after it is changed in `tile_blocks`, with the tile's
+    /// previous block ID.
+    ///
+    /// Does nothing if the old and new block have the same light opacity and emission.
+    pub fn on_block_changed<F: FnMut(TileKey)>(
+        &mut self,
+        tile: TileKey,
+        old_bid: RawBlockId,
+        getter: &Getter,
+        tile_blocks: &PerChunk<ChunkBlocks>,
+        game: &GameData,
+        changed: F,
+    ) {
+        let new_bid = tile.get(tile_blocks).get();
+        if game.blocks_light_opacity[old_bid] == game.blocks_light_opacity[new_bid]
+            && game.blocks_light_emission[old_bid] == game.blocks_light_emission[new_bid]
+        {
+            return;
+        }
+
+        let mut ctx = Ctx { getter, tile_blocks, game, changed };
+        let gtc = tile.gtc();
+        for ch in CHANNELS {
+            // remove the tile's light and all light that may have come from it
+            let level = self.level(ch, tile);
+            if level > 0 {
+                self.set_level(ch, tile, 0, &mut ctx);
+                self.unspread_queue.push_back((tile, level));
+                self.run_unspread(ch, &mut ctx);
+            }
+
+            // then relight the tile from its neighbors and itself
+            for face in FACES {
+                if let Some(tile2) = self.gtc_get_added(getter, gtc + face.to_vec()) {
+                    self.spread_queue.push_back(tile2);
+                }
+            }
+            self.seed(ch, tile, &mut ctx);
+            self.run_spread(ch, &mut ctx);
+        }
+    }
+
+    // look up a chunk, only if it's been added to the light engine
+    fn get_added(&self, getter: &Getter, cc: Vec3<i64>) -> Option<usize> {
+        getter.get(cc)
+            .filter(|&ci| self.chunk_light.0.get(ci).map(|&(cc2, _)| cc2 == cc).unwrap_or(false))
+    }
+
+    // look up a tile, only if its chunk has been added to the light engine
+    fn gtc_get_added(&self, getter: &Getter, gtc: Vec3<i64>) -> Option<TileKey> {
+        let cc = gtc_get_cc(gtc);
+        self.get_added(getter, cc).map(|ci| TileKey { cc, ci, lti: gtc_get_lti(gtc) })
+    }
+
+    fn level(&self, ch: Channel, tile: TileKey) -> u8 {
+        let chunk_light = self.chunk_light.get(tile.cc, tile.ci);
+        match ch {
+            Channel::Sky => chunk_light.sky.get(tile.lti),
+            Channel::Block => chunk_light.block.get(tile.lti),
+        }
+    }
+
+    fn set_level<F: FnMut(TileKey)>(
+        &mut self,
+        ch: Channel,
+        tile: TileKey,
+        level: u8,
+        ctx: &mut Ctx<F>,
+    ) {
+        let chunk_light = self.chunk_light.get_mut(tile.cc, tile.ci);
+        let levels = match ch {
+            Channel::Sky => &mut chunk_light.sky,
+            Channel::Block => &mut chunk_light.block,
+        };
+        if levels.get(tile.lti) != level {
+            levels.set(tile.lti, level);
+            (ctx.changed)(tile);
+        }
+    }
+
+    // light level a tile has regardless of its neighbors
+    fn source_level<F>(&self, ch: Channel, tile: TileKey, ctx: &Ctx<F>) -> u8 {
+        match ch {
+            Channel::Sky => {
+                let top = lti_get_y(tile.lti) as i64 == CHUNK_EXTENT.y - 1;
+                if top && self.get_added(ctx.getter, tile.cc + Vec3::unit_y()).is_none() {
+                    spread_level(ch, MAX_LIGHT, Face::NegY, ctx.opacity(tile))
+                } else {
+                    0
+                }
+            }
+            Channel::Block => ctx.game.blocks_light_emission[tile.get(ctx.tile_blocks).get()],
+        }
+    }
+
+    // brighten a tile to its source level if it's dimmer, and queue it to spread
+    fn seed<F: FnMut(TileKey)>(&mut self, ch: Channel, tile: TileKey, ctx: &mut Ctx<F>) {
+        let level = self.source_level(ch, tile, ctx);
+        if level > self.level(ch, tile) {
+            self.set_level(ch, tile, level, ctx);
+            self.spread_queue.push_back(tile);
+        }
+    }
+
+    // spread light from tiles in the spread queue until there's no more brightening to do
+    fn run_spread<F: FnMut(TileKey)>(&mut self, ch: Channel, ctx: &mut Ctx<F>) {
+        while let Some(tile) = self.spread_queue.pop_front() {
+            let level = self.level(ch, tile);
+            if level == 0 {
+                continue;
+            }
+            let gtc = tile.gtc();
+            for face in FACES {
+                if let Some(tile2) = self.gtc_get_added(ctx.getter, gtc + face.to_vec()) {
+                    let level2 = spread_level(ch, level, face, ctx.opacity(tile2));
+                    if level2 > self.level(ch, tile2) {
+                        self.set_level(ch, tile2, level2, ctx);
+                        self.spread_queue.push_back(tile2);
+                    }
+                }
+            }
+        }
+    }
+
+    // darken tiles which may have been lit by tiles in the unspread queue, queueing tiles at the
+    // edges of the darkened region to spread back into it
+    fn run_unspread<F: FnMut(TileKey)>(&mut self, ch: Channel, ctx: &mut Ctx<F>) {
+        while let Some((tile, level)) = self.unspread_queue.pop_front() {
+            let gtc = tile.gtc();
+            for face in FACES {
+                let tile2 = match self.gtc_get_added(ctx.getter, gtc + face.to_vec()) {
+                    Some(tile2) => tile2,
+                    None => continue,
+                };
+                let level2 = self.level(ch, tile2);
+                if level2 == 0 {
+                    continue;
+                }
+                let maybe_lit_by = level2 < level || (
+                    ch == Channel::Sky
+                    && face == Face::NegY
+                    && level == MAX_LIGHT
+                    && level2 == MAX_LIGHT
+                );
+                if maybe_lit_by {
+                    self.set_level(ch, tile2, 0, ctx);
+                    self.unspread_queue.push_back((tile2, level2));
+                    self.seed(ch, tile2, ctx);
+                } else {
+                    self.spread_queue.push_back(tile2);
+                }
+            }
+        }
+    }
+}
+
+// light level a tile gets from a neighbor with the given light level in the given direction
+fn spread_level(ch: Channel, level: u8, face: Face, opacity: u8) -> u8 {
+    if ch == Channel::Sky && face == Face::NegY && level == MAX_LIGHT && opacity == 0 {
+        MAX_LIGHT
+    } else {
+        level.saturating_sub(opacity.max(1))
+    }
+}
+
+// iterate through the ltis of tiles in a chunk which are on the given face of the chunk
+fn border_ltis(face: Face) -> impl Iterator<Item=u16> {
+    let ranges = face.to_signs().zip(CHUNK_EXTENT).map(|(sign, ext)| match sign {
+        Sign::Neg => 0..1,
+        Sign::Zero => 0..ext,
+        Sign::Pos => ext - 1..ext,
+    });
+    ranges.z.flat_map(move |z| {
+        let x_range = ranges.x.clone();
+        ranges.y.clone()
+            .flat_map(move |y| x_range.clone()
+                .map(move |x| ltc_to_lti(Vec3 { x, y, z })))
+    })
+}
@@ -7,10 +7,13 @@ use minecraft::{
     thread_pool::ThreadPool,
     game_data::GameData,
     server::{
+        ServerEvent,
         channel::*,
-        network::NetworkServer,
+        network::{NetworkServer, TlsIdentity},
         save_db::SaveDb,
-        runner::run,
+        generate_chunk::{WorldGenSettings, parse_seed},
+        runner::{run, WorldMeta},
+        command::Operators,
     },
 };
 #[cfg(feature = "client")]
@@ -28,6 +31,7 @@ use std::{
     sync::Arc,
     thread,
     env::args,
+    io::stdin,
 };
 use tokio::runtime::Runtime;
 
@@ -50,8 +54,20 @@ Examples:
     [this command] --server --save=server --bind=127.0.0.1:35565
     Run the server with explicit options.
 
+    [this command] --server --seed=12345 --world-gen=flat
+    Run the server with an explicit world seed and world generator, if creating a new world.
+
+    [this command] --server --op=alice --op=bob
+    Run the server, letting the players with these usernames run operator commands.
+
+    [this command] --server --tls-cert=cert.pem --tls-key=key.pem
+    Run the server, encrypting connections with TLS using this certificate and PKCS #8 private key
+    in PEM format. Clients must connect with a wss:// address.
+
     (Note: Change 127.0.0.1 to 0.0.0.0 to allow connections from other computers).
 
+    (Note: While the server is running, commands such as "stop" or "list" can be typed into it).
+
 Env var examples:
     RUST_LOG=minecraft=trace
     Changes logging levels"#;
@@ -67,8 +83,20 @@ Examples:
     [this command] --save=server --bind=127.0.0.1:35565
     Run the server with explicit options.
 
+    [this command] --seed=12345 --world-gen=flat
+    Run the server with an explicit world seed and world generator, if creating a new world.
+
+    [this command] --op=alice --op=bob
+    Run the server, letting the players with these usernames run operator commands.
+
+    [this command] --tls-cert=cert.pem --tls-key=key.pem
+    Run the server, encrypting connections with TLS using this certificate and PKCS #8 private key
+    in PEM format. Clients must connect with a wss:// address.
+
     (Note: Change 127.0.0.1 to 0.0.0.0 to allow connections from other computers).
 
+    (Note: While the server is running, commands such as "stop" or "list" can be typed into it).
+
 Env var examples:
     RUST_LOG=minecraft=trace
     Changes logging levels"#;
@@ -132,20 +160,60 @@ fn run_server_from_cli(args: &Vec<String>) {
         .filter_map(|arg| arg.strip_prefix("--bind="))
         .next()
         .unwrap_or("127.0.0.1:35565");
-    run_server(DataDir::new(), save_file_name, bind_to);
+    let mut world_gen = WorldGenSettings::new_random();
+    if let Some(seed) = args.iter().filter_map(|arg| arg.strip_prefix("--seed=")).next() {
+        world_gen.seed = parse_seed(seed);
+    }
+    if let Some(generator) = args.iter().filter_map(|arg| arg.strip_prefix("--world-gen=")).next() {
+        world_gen.generator = generator.to_owned();
+    }
+    let operators = Operators::Usernames(args.iter()
+        .filter_map(|arg| arg.strip_prefix("--op="))
+        .map(str::to_owned)
+        .collect());
+    let tls_cert = args.iter().filter_map(|arg| arg.strip_prefix("--tls-cert=")).next();
+    let tls_key = args.iter().filter_map(|arg| arg.strip_prefix("--tls-key=")).next();
+    let tls = match (tls_cert, tls_key) {
+        (Some(cert), Some(key)) => {
+            let tls = TlsIdentity::load(cert, key).expect("error loading TLS identity");
+            info!("TLS certificate fingerprint: {}", tls.fingerprint());
+            Some(tls)
+        }
+        (None, None) => None,
+        _ => panic!("--tls-cert and --tls-key must be given together"),
+    };
+    run_server(DataDir::new(), save_file_name, bind_to, tls, world_gen, operators);
 }
 
 // run server until it stops, or panic
-fn run_server(data_dir: DataDir, save_file_name: &str, bind_to: &str) {
+fn run_server(
+    data_dir: DataDir,
+    save_file_name: &str,
+    bind_to: &str,
+    tls: Option<TlsIdentity>,
+    world_gen: WorldGenSettings,
+    operators: Operators,
+) {
     let game = Arc::new(GameData::new());
     let save_db = SaveDb::open(save_file_name, &data_dir, &game).expect("error opening save file");
+    let world_meta = WorldMeta::load(&save_db, world_gen).expect("error loading world metadata");
     let rt = Runtime::new().expect("error creating tokio runtime");
     let thread_pool = ThreadPool::new();
     let (server_send, server_recv) = channel();
     stop_on_kill(server_send.clone());
+    commands_from_stdin(server_send.clone());
     let network_server = NetworkServer::new(server_send.clone());
-    network_server.handle().bind(bind_to.to_owned(), rt.handle(), &game);
-    run(server_send, server_recv, thread_pool, network_server, save_db, game);
+    network_server.handle().bind(bind_to.to_owned(), tls, rt.handle(), &game);
+    run(
+        server_send,
+        server_recv,
+        thread_pool,
+        network_server,
+        save_db,
+        world_meta,
+        game,
+        operators,
+    );
 }
 
 // hook up sigkill to graceful server shutdown
@@ -163,3 +231,23 @@ fn stop_on_kill(server_send: ServerSender) {
         warn!(%e, "error setting kill signal handler");
     }
 }
+
+// hook up lines typed into stdin to running server commands
+fn commands_from_stdin(server_send: ServerSender) {
+    thread::spawn(move || {
+        for line in stdin().lines() {
+            match line {
+                Ok(line) => server_send.send(
+                    ServerEvent::Command(line),
+                    EventPriority::Control,
+                    None,
+                    None,
+                ),
+                Err(e) => {
+                    warn!(%e, "error reading commands from stdin");
+                    break;
+                }
+            }
+        }
+    });
+}
@@ -4,23 +4,51 @@
 
 use crate::{
     game_binschema::GameBinschema,
+    auth::*,
     util_usize_lt::UsizeLt,
     util_time::ServerRelTime,
     item::*,
     sync_state_entities::*,
+    game_data::container_logic::{MAX_CONTAINER_SLOTS, MAX_CONTAINER_PROPERTIES},
 };
 use chunk_data::*;
 use vek::*;
 
 
+/// Maximum number of characters in a chat message a player sends.
+pub const MAX_CHAT_CHARS: usize = 100;
+
+/// Whether players may send chat messages containing the character. This is printable ASCII, which
+/// the font can draw.
+pub fn is_allowed_chat_char(c: char) -> bool {
+    (' '..='~').contains(&c)
+}
+
+/// Maximum number of characters in a username.
+pub const MAX_USERNAME_CHARS: usize = 16;
+
+/// Whether a username is allowed. Usernames are non-empty, at most `MAX_USERNAME_CHARS` long, and
+/// contain only ASCII letters, digits, and underscores.
+pub fn is_valid_username(username: &str) -> bool {
+    !username.is_empty()
+        && username.len() <= MAX_USERNAME_CHARS
+        && username.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+
 /// Message sent from client to server.
 #[derive(Debug, GameBinschema)]
 pub enum UpMsg {
     /// Part of connection initialization flow.
     ///
-    /// Client sends this right after connecting, which triggers an `AcceptLogIn` message response
-    /// if accepted.
+    /// Client sends this right after connecting, which triggers an `AuthChallenge` message
+    /// response.
     LogIn(UpMsgLogIn),
+    /// Part of connection initialization flow.
+    ///
+    /// Client sends this in response to `AuthChallenge`, which triggers an `AcceptLogIn` message
+    /// response if accepted.
+    AuthResponse(UpMsgAuthResponse),
     /// Message that client may merely need to be logged in to send, rather than fully joined.
     PreJoin(PreJoinUpMsg),
     /// Part of connection initialization flow.
@@ -33,9 +61,20 @@ pub enum UpMsg {
 }
 
 /// Part of connection initialization flow.
+///
+/// Protocol violation for the username to not be allowed by `is_valid_username`.
 #[derive(Debug, GameBinschema)]
 pub struct UpMsgLogIn {
     pub username: String,
+    /// Public key of the keypair the client has for the username. See the `auth` module.
+    pub public_key: PublicKey,
+}
+
+/// Part of connection initialization flow.
+#[derive(Debug, GameBinschema)]
+pub struct UpMsgAuthResponse {
+    /// Signature of the challenge and username with the keypair for the username.
+    pub signature: ChallengeSignature,
 }
 
 /// Message that client may merely need to be logged in to send, rather than fully joined.
@@ -54,14 +93,26 @@ pub enum PreJoinUpMsg {
 pub enum PlayerMsg {
     /// Set own position and direction.
     SetCharState(PlayerMsgSetCharState),
-    /// Set block at tile.
-    SetTileBlock(PlayerMsgSetTileBlock),
+    /// Start digging the block at a tile.
+    StartDig(PlayerMsgStartDig),
+    /// Stop digging without breaking the block.
+    CancelDig(PlayerMsgCancelDig),
+    /// Finish digging, breaking the block being dug.
+    FinishDig(PlayerMsgFinishDig),
+    /// Place the block of the item in a hotbar slot at a tile.
+    PlaceBlock(PlayerMsgPlaceBlock),
+    /// Use the block at a tile, such as to open or close a door.
+    UseBlock(PlayerMsgUseBlock),
     /// Open a game menu in a way that's synced with the server.
     OpenSyncMenu(PlayerMsgOpenSyncMenu),
     /// Close the currently open sync menu.
     CloseSyncMenu(PlayerMsgCloseSyncMenu),
     /// Player message to be processed by the currently open sync menu.
     SyncMenuMsg(SyncMenuMsg),
+    /// Say something in chat.
+    Chat(PlayerMsgChat),
+    /// Request completions for partially typed chat text.
+    TabComplete(PlayerMsgTabComplete),
     SpawnSteve(Vec3<f32>),
     ClearSteves,
     ClockDebug(ServerRelTime),
@@ -75,23 +126,73 @@ pub struct PlayerMsgSetCharState {
     pub pitch: f32,
 }
 
-/// Set block at tile.
+/// Start digging the block at a tile.
+#[derive(Debug, GameBinschema)]
+pub struct PlayerMsgStartDig {
+    /// Tile to dig.
+    pub gtc: Vec3<i64>,
+    /// Hotbar slot of the item to dig with, which is the same as its inventory slot index.
+    pub hotbar_slot: UsizeLt<9>,
+}
+
+/// Stop digging without breaking the block.
+#[derive(Debug, GameBinschema)]
+pub struct PlayerMsgCancelDig;
+
+/// Finish digging, breaking the block being dug.
+///
+/// The server rejects this if not enough time has passed since digging started for the block to
+/// have been dug with the item being dug with.
+#[derive(Debug, GameBinschema)]
+pub struct PlayerMsgFinishDig;
+
+/// Place the block of the item in a hotbar slot at a tile.
+#[derive(Debug, GameBinschema)]
+pub struct PlayerMsgPlaceBlock {
+    /// Tile to place the block at.
+    pub gtc: Vec3<i64>,
+    /// Hotbar slot to take the item from, which is the same as its inventory slot index.
+    pub hotbar_slot: UsizeLt<9>,
+}
+
+/// Use the block at a tile, such as to open or close a door.
 #[derive(Debug, GameBinschema)]
-pub struct PlayerMsgSetTileBlock {
+pub struct PlayerMsgUseBlock {
+    /// Tile to use the block at.
     pub gtc: Vec3<i64>,
-    pub bid_meta: ErasedBidMeta,
 }
 
 /// Open a game menu in a way that's synced with the server.
 #[derive(Debug, GameBinschema, Copy, Clone, PartialEq)]
 pub enum PlayerMsgOpenSyncMenu {
     Inventory,
+    /// The container block at the given tile.
+    Container(Vec3<i64>),
 }
 
 /// Close the currently open sync menu.
 #[derive(Debug, GameBinschema)]
 pub struct PlayerMsgCloseSyncMenu;
 
+/// Say something in chat.
+///
+/// Protocol violation for the text to be empty, longer than `MAX_CHAT_CHARS`, or to contain
+/// characters other than those allowed by `is_allowed_chat_char`.
+#[derive(Debug, GameBinschema)]
+pub struct PlayerMsgChat {
+    pub text: String,
+}
+
+/// Request completions for the last word of partially typed chat text, which is a command if it
+/// starts with `/`. The server responds with `PostJoinDownMsg::TabCompletions`.
+///
+/// Protocol violation for the text to be longer than `MAX_CHAT_CHARS` or to contain characters
+/// other than those allowed by `is_allowed_chat_char`.
+#[derive(Debug, GameBinschema)]
+pub struct PlayerMsgTabComplete {
+    pub text: String,
+}
+
 /// Reference to an item slot transmitted from client to server.
 ///
 /// This means it may be relative to the sync menu the client has open.
@@ -101,6 +202,10 @@ pub enum UpItemSlotRef {
     Inventory(UsizeLt<36>),
     /// The held item.
     Held,
+    /// Item in the container block the player has open.
+    Container(UsizeLt<MAX_CONTAINER_SLOTS>),
+    /// Item in the crafting grid of the open menu, in row-major order with the grid's width.
+    Crafting(UsizeLt<9>),
 }
 
 /// Player message to be processed by the currently open sync menu.
@@ -108,6 +213,7 @@ pub enum UpItemSlotRef {
 pub enum SyncMenuMsg {
     TransferItems(SyncMenuMsgTransferItems),
     SwapItemSlots(SyncMenuMsgSwapItemSlots),
+    Craft(SyncMenuMsgCraft),
 }
 
 /// Attempt to move the given number of items from one slot to another.
@@ -125,9 +231,18 @@ pub struct SyncMenuMsgTransferItems {
 #[derive(Debug, GameBinschema)]
 pub struct SyncMenuMsgSwapItemSlots(pub [UpItemSlotRef; 2]);
 
+/// Attempt to craft once with the open menu's crafting grid, putting the output in the held slot.
+#[derive(Debug, GameBinschema)]
+pub struct SyncMenuMsgCraft;
+
 /// Message sent from server to client.
 #[derive(Debug, GameBinschema)]
 pub enum DownMsg {
+    /// Part of connection initialization flow.
+    ///
+    /// The client should prove it has the keypair for the username it logged in with by responding
+    /// with an `AuthResponse` message.
+    AuthChallenge(DownMsgAuthChallenge),
     /// Part of connection initialization flow.
     ///
     /// This places the client into a state of preparing to join the world. It will begin receiving
@@ -148,6 +263,15 @@ pub enum DownMsg {
     FinalizeJoinGame(DownMsgFinalizeJoinGame),
     /// Message that is only valid to send to client once it has fully joined the game.
     PostJoin(PostJoinDownMsg),
+    /// The server is closing the connection. Can be sent at any point in the connection's
+    /// lifetime, and is the last message the server sends on the connection.
+    Close(DownMsgClose),
+}
+
+/// Part of connection initialization flow.
+#[derive(Debug, GameBinschema)]
+pub struct DownMsgAuthChallenge {
+    pub challenge: Challenge,
 }
 
 /// Part of connection initialization flow.
@@ -175,6 +299,14 @@ pub enum PreJoinDownMsg {
         /// Next tick instant increases by (1 + skip_next) x 50.
         skip_next: u64,
     },
+    /// Set the world clock. Until told otherwise, the client should advance it by 1, wrapping
+    /// around at `DAY_TICKS`, upon each `TickDone` if `daylight_cycle` is true.
+    SetTimeOfDay {
+        /// Ticks into the current day night cycle.
+        time_of_day: u64,
+        /// Whether the time of day advances.
+        daylight_cycle: bool,
+    },
     /// Load a player into the client.
     AddPlayer(DownMsgAddPlayer),
     /// Remove a loaded player from the client.
@@ -259,7 +391,23 @@ pub enum PostJoinDownMsg {
     SetItemSlot {
         item_slot: DownItemSlotRef,
         slot_content: Option<ItemStack>,
-    }
+    },
+    /// Set a property of the container block at the given tile, which the player has open.
+    SetContainerProperty {
+        gtc: Vec3<i64>,
+        idx: UsizeLt<MAX_CONTAINER_PROPERTIES>,
+        value: u16,
+    },
+    /// Add a line to the chat.
+    ChatLine { line: String },
+    /// Respond to a `PlayerMsgTabComplete` with the text it was for and the possible words to
+    /// replace that text's last word with, which may be empty.
+    TabCompletions {
+        text: String,
+        completions: Vec<String>,
+    },
+    /// Set own position, overriding wherever the client thinks it is.
+    Teleport { pos: Vec3<f32> },
 }
 
 /// Part of connection initialization flow.
@@ -269,10 +417,23 @@ pub enum PostJoinDownMsg {
 pub struct DownMsgFinalizeJoinGame {
     /// The loaded player corresponding to the client itself.
     pub self_player_idx: DownPlayerIdx,
+    /// The player's saved position, or the world spawn if they're new.
+    pub pos: Vec3<f32>,
+    /// The player's saved yaw, or 0 if they're new.
+    pub yaw: f32,
+    /// The player's saved pitch, or 0 if they're new.
+    pub pitch: f32,
     pub inventory_slots: [Option<ItemStack>; 36],
     pub held_slot: Option<ItemStack>,
 }
 
+/// The server is closing the connection.
+#[derive(Debug, GameBinschema)]
+pub struct DownMsgClose {
+    /// Why the connection is being closed, suitable for displaying to the user.
+    pub reason: String,
+}
+
 /// Load a player into the client.
 ///
 /// Contains any player-specific state to be loaded onto all clients.
@@ -304,6 +465,8 @@ pub struct DownMsgAddChunk {
     pub chunk_tile_blocks: ChunkBlocks,
     pub steves: Vec<EntityData<SteveEntityState>>,
     pub pigs: Vec<EntityData<PigEntityState>>,
+    pub falling_blocks: Vec<EntityData<FallingBlockEntityState>>,
+    pub items: Vec<EntityData<ItemEntityState>>,
 }
 
 /// Remove a loaded chunk from the client.
@@ -328,4 +491,11 @@ pub enum DownItemSlotRef {
     Held,
     /// Item in the player's open inventory.
     Inventory(UsizeLt<36>),
+    /// Item in the container block at the given tile, which the player has open.
+    Container {
+        gtc: Vec3<i64>,
+        idx: UsizeLt<MAX_CONTAINER_SLOTS>,
+    },
+    /// Item in the player's crafting grid.
+    Crafting(UsizeLt<9>),
 }
@@ -1,5 +1,6 @@
 //! Axis-aligned box.
 
+use chunk_data::{Face, Pole};
 use vek::*;
 
 
@@ -57,4 +58,19 @@ impl AaBox {
         }
         true
     }
+
+    /// Area of the part of the side of the unit cube in the direction of `face` which self's face
+    /// in that direction covers, assuming self is within the unit cube.
+    pub fn tile_side_area(self, face: Face) -> f32 {
+        let (axis, pole) = face.to_axis_pole();
+        let on_side = match pole {
+            Pole::Neg => self.pos[axis as usize] == 0.0,
+            Pole::Pos => self.pos[axis as usize] + self.ext[axis as usize] == 1.0,
+        };
+        if on_side {
+            axis.other_axes().iter().map(|&axis2| self.ext[axis2 as usize]).product()
+        } else {
+            0.0
+        }
+    }
 }
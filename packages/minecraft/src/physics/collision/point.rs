@@ -82,7 +82,7 @@ impl CollisionObject for PointCollisionObject {
                         // compare if does
                         if first
                             .as_ref()
-                            .map(|first| dt < first.dt)
+                            .map(|first| dt + dt2 < first.dt)
                             .unwrap_or(true)
                         {
                             first = Some(Collision {
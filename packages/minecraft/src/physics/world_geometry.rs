@@ -5,6 +5,7 @@ use crate::game_data::{
     GameData,
     physics_logic::BlockPhysicsLogic,
     hitscan_logic::BlockHitscanLogic,
+    content::door::DoorBlockMeta,
 };
 use chunk_data::*;
 use vek::*;
@@ -30,7 +31,7 @@ pub trait WorldGeometry: Sized {
         let mut found = None;
 
         let gtc = pos.map(|n| n.floor() as i64);
-        let rel_pos = pos.map(|n| n % 1.0);
+        let rel_pos = pos - gtc.map(|n| n as f32);
         self.tile_geometry(gtc, |aa_box, barrier_id| {
             if aa_box.contains(rel_pos) {
                 found = Some((aa_box, barrier_id));
@@ -85,8 +86,8 @@ impl<'a> WorldGeometry for WorldPhysicsGeometry<'a> {
         gtc: Vec3<i64>,
         mut visit: V,
     ) {
-        let physics_logic = self.getter
-            .gtc_get(self.cc_rel_to * CHUNK_EXTENT + gtc)
+        let tile = self.getter.gtc_get(self.cc_rel_to * CHUNK_EXTENT + gtc);
+        let physics_logic = tile
             .map(|tile| {
                 let bid = tile.get(self.tile_blocks).get();
                 self.game.blocks_physics_logic.get(bid)
@@ -100,6 +101,15 @@ impl<'a> WorldGeometry for WorldPhysicsGeometry<'a> {
                     gtc,
                 );
             }
+            BlockPhysicsLogic::Boxes(aa_boxes) => {
+                for &aa_box in aa_boxes {
+                    visit(aa_box, gtc);
+                }
+            }
+            &BlockPhysicsLogic::Door => {
+                let meta = tile.unwrap().get(self.tile_blocks).raw_meta::<DoorBlockMeta>();
+                visit(*meta.aa_box(), gtc);
+            }
         }
     }
 }
@@ -133,6 +143,15 @@ impl<'a> WorldGeometry for WorldHitscanGeometry<'a> {
                         tile,
                     );
                 }
+                BlockHitscanLogic::Boxes(aa_boxes) => {
+                    for &aa_box in aa_boxes {
+                        visit(aa_box, tile);
+                    }
+                }
+                &BlockHitscanLogic::Door => {
+                    let meta = tile.get(self.tile_blocks).raw_meta::<DoorBlockMeta>();
+                    visit(*meta.aa_box(), tile);
+                }
             }
         }
     }
@@ -8,12 +8,14 @@ use crate::{
         channel::*,
         save_content::*,
         save_db::SaveDb,
-        generate_chunk::generate_chunk,
+        generate_chunk::{WorldGenerator, GeneratedChunk},
     },
     thread_pool::*,
     util_abort_handle::*,
 };
 use std::sync::Arc;
+use anyhow::Result;
+use vek::*;
 
 
 /// Utility for triggering jobs to read chunks from the save file or generate new chunks as
@@ -25,6 +27,7 @@ pub struct ChunkLoader {
 
 struct JobCtx {
     game: Arc<GameData>,
+    world_generator: Arc<dyn WorldGenerator>,
     server_send: ServerSender,
     save_db: SaveDb,
 }
@@ -33,6 +36,7 @@ impl ChunkLoader {
     /// Construct.
     pub fn new(
         game: Arc<GameData>,
+        world_generator: Arc<dyn WorldGenerator>,
         server_send: ServerSender,
         thread_pool: ThreadPool,
         save_db: SaveDb,
@@ -41,6 +45,7 @@ impl ChunkLoader {
             thread_pool,
             job_ctx: Arc::new(JobCtx {
                 game,
+                world_generator,
                 server_send,
                 save_db,
             }),
@@ -48,24 +53,40 @@ impl ChunkLoader {
     }
 
     /// Submit a job to the thread pool to read this chunk from the save file, or generate it new
-    /// if it has never been saved, and then send it back to the server loop as a `ChunkReady`
-    /// event, with the given abort handle.
+    /// if it has never been saved, along with any placements pending being applied to it, and then
+    /// send it back to the server loop as a `ChunkReady` event, with the given abort handle.
     pub fn trigger_load(&self, save_key: ChunkSaveKey, aborted: AbortHandle) {
         let ctx = Arc::clone(&self.job_ctx);
         // submit task
         self.thread_pool.submit(WorkPriority::Server, aborted, move |aborted| {
             // attempt read
-            let result = ctx.save_db.clone().read(save_key);
+            let result = ctx.save_db.clone().read(save_key)
+                .and_then(|save_val| Ok((save_val, read_pending(&ctx.save_db, save_key.cc)?)));
             match result {
-                Ok(Some(save_val)) => {
+                Ok((Some(save_val), pending)) => {
                     // loaded
-                    let event = ServerEvent::ChunkReady { save_key, save_val, saved: true };
+                    let event = ServerEvent::ChunkReady {
+                        save_key,
+                        save_val,
+                        saved: true,
+                        pending,
+                        spilled: Vec::new(),
+                    };
                     ctx.server_send.send(event, EventPriority::Other, Some(aborted), None);
                 }
-                Ok(None) => {
+                Ok((None, pending)) => {
                     // must generate
-                    let save_val = generate_chunk(&ctx.game, save_key.cc);
-                    let event = ServerEvent::ChunkReady { save_key, save_val, saved: false };
+                    let GeneratedChunk {
+                        save_val,
+                        spilled,
+                    } = ctx.world_generator.generate_chunk(save_key.cc, &ctx.game);
+                    let event = ServerEvent::ChunkReady {
+                        save_key,
+                        save_val,
+                        saved: false,
+                        pending,
+                        spilled,
+                    };
                     ctx.server_send.send(event, EventPriority::Other, Some(aborted), None);
                 }
                 Err(e) => {
@@ -76,3 +97,30 @@ impl ChunkLoader {
         });
     }
 }
+
+// read all non-empty pending placements entries for the given chunk from the save file, keyed by
+// the neighboring chunk they came from
+fn read_pending(
+    save_db: &SaveDb,
+    cc: Vec3<i64>,
+) -> Result<Vec<(Vec3<i64>, PendingPlacementsSaveVal)>> {
+    let mut save_db = save_db.clone();
+    let mut pending = Vec::new();
+    for z in -1..=1 {
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let from_cc = cc + Vec3 { x, y, z };
+                if from_cc == cc {
+                    continue;
+                }
+                let save_key = PendingPlacementsSaveKey { cc, from_cc };
+                if let Some(save_val) = save_db.read(save_key)? {
+                    if !save_val.placements.is_empty() {
+                        pending.push((from_cc, save_val));
+                    }
+                }
+            }
+        }
+    }
+    Ok(pending)
+}
@@ -130,6 +130,11 @@ impl ChunkMgr {
         self.chunk_player_clientside_ci.get(cc, ci)[pk]
     }
 
+    /// Whether the chunk is pending being loaded, such that `on_chunk_ready` may be called for it.
+    pub fn is_loading(&self, cc: Vec3<i64>) -> bool {
+        self.loading_chunks.contains_key(&cc)
+    }
+
     /// Call upon a player being added to the world. Initializes it with no chunk client interests.
     pub fn add_player(&mut self, pk: PlayerKey) {
         // initialize player state with defaults
@@ -0,0 +1,259 @@
+//! The commands built into the server.
+
+use super::*;
+use crate::{
+    server::{
+        item_entities::give_item,
+        process_player_msg::broadcast_char_state,
+    },
+    day_night::DAY_TICKS,
+    item::{ItemStack, ItemMeta},
+    game_data::transclone_logic::ItemTranscloner,
+};
+use std::{
+    any::TypeId,
+    cmp::min,
+    sync::Arc,
+};
+use vek::*;
+
+
+/// Names of times of day settable with `/time set`.
+pub const TIME_NAMES: [&str; 4] = ["day", "noon", "night", "midnight"];
+
+/// Ticks into the day night cycle of each of `TIME_NAMES`.
+pub const NAMED_TIMES: [u64; 4] = [1000, 6000, 13000, 18000];
+
+/// Maximum number of items `/give` can give at once, which is a full inventory of 64-stacks.
+pub const MAX_GIVE: i64 = 36 * 64;
+
+
+/// Construct a registry of the built in commands.
+pub fn builtin_commands() -> CommandRegistry {
+    let mut commands = CommandRegistry::new();
+    commands.register(Command {
+        name: "stop",
+        permission: PermissionLevel::Operator,
+        forms: vec![CommandForm { args: vec![], run: stop }],
+    });
+    commands.register(Command {
+        name: "save",
+        permission: PermissionLevel::Operator,
+        forms: vec![CommandForm { args: vec![], run: save }],
+    });
+    commands.register(Command {
+        name: "kick",
+        permission: PermissionLevel::Operator,
+        forms: vec![CommandForm {
+            args: vec![ArgSpec::required("player", ArgKind::Player)],
+            run: kick,
+        }],
+    });
+    commands.register(Command {
+        name: "tp",
+        permission: PermissionLevel::Operator,
+        forms: vec![
+            CommandForm {
+                args: vec![
+                    ArgSpec::required("player", ArgKind::Player),
+                    ArgSpec::required("target", ArgKind::Player),
+                ],
+                run: tp_to_player,
+            },
+            CommandForm {
+                args: vec![
+                    ArgSpec::required("player", ArgKind::Player),
+                    ArgSpec::required("x", ArgKind::Float),
+                    ArgSpec::required("y", ArgKind::Float),
+                    ArgSpec::required("z", ArgKind::Float),
+                ],
+                run: tp_player_to_pos,
+            },
+            CommandForm {
+                args: vec![
+                    ArgSpec::required("x", ArgKind::Float),
+                    ArgSpec::required("y", ArgKind::Float),
+                    ArgSpec::required("z", ArgKind::Float),
+                ],
+                run: tp_self_to_pos,
+            },
+        ],
+    });
+    commands.register(Command {
+        name: "give",
+        permission: PermissionLevel::Operator,
+        forms: vec![CommandForm {
+            args: vec![
+                ArgSpec::required("player", ArgKind::Player),
+                ArgSpec::required("item", ArgKind::Item),
+                ArgSpec::optional("count", ArgKind::Int { min: 1, max: MAX_GIVE }),
+            ],
+            run: give,
+        }],
+    });
+    commands.register(Command {
+        name: "time",
+        permission: PermissionLevel::Operator,
+        forms: vec![
+            CommandForm {
+                args: vec![
+                    ArgSpec::required("set", ArgKind::Literal(&["set"])),
+                    ArgSpec::required("ticks", ArgKind::Int {
+                        min: 0,
+                        max: DAY_TICKS as i64 - 1,
+                    }),
+                ],
+                run: time_set_ticks,
+            },
+            CommandForm {
+                args: vec![
+                    ArgSpec::required("set", ArgKind::Literal(&["set"])),
+                    ArgSpec::required("time", ArgKind::Literal(&TIME_NAMES)),
+                ],
+                run: time_set_named,
+            },
+        ],
+    });
+    commands.register(Command {
+        name: "list",
+        permission: PermissionLevel::Player,
+        forms: vec![CommandForm { args: vec![], run: list }],
+    });
+    commands
+}
+
+// /stop: stop the server, saving first
+fn stop(world: &mut SyncWorld, sender: CommandSender, _: &CommandArgs) -> Result<()> {
+    reply(world, sender, "stopping the server");
+    world.server_only.server_send.send_stop();
+    Ok(())
+}
+
+// /save: save the world now rather than waiting for the next periodic save
+fn save(world: &mut SyncWorld, sender: CommandSender, _: &CommandArgs) -> Result<()> {
+    world.sync_ctx.save_mgr.request_save();
+    reply(world, sender, "saving the world");
+    Ok(())
+}
+
+// /kick <player>: disconnect a player
+fn kick(world: &mut SyncWorld, sender: CommandSender, args: &CommandArgs) -> Result<()> {
+    let pk = args.player(0);
+    let username = world.sync_ctx.conn_mgr.player_username(pk).to_owned();
+    world.sync_ctx.conn_mgr.kick_later(pk, "kicked by an operator".to_owned());
+    reply(world, sender, &format!("kicked {}", username));
+    Ok(())
+}
+
+// /tp <player> <target>: teleport a player to another player
+fn tp_to_player(world: &mut SyncWorld, sender: CommandSender, args: &CommandArgs) -> Result<()> {
+    let pos = world.server_only.player_pos[args.player(1)];
+    teleport(world, sender, args.player(0), pos);
+    Ok(())
+}
+
+// /tp <player> <x> <y> <z>: teleport a player to a position
+fn tp_player_to_pos(
+    world: &mut SyncWorld,
+    sender: CommandSender,
+    args: &CommandArgs,
+) -> Result<()> {
+    let pos = Vec3::new(args.float(1), args.float(2), args.float(3));
+    teleport(world, sender, args.player(0), pos);
+    Ok(())
+}
+
+// /tp <x> <y> <z>: teleport oneself to a position
+fn tp_self_to_pos(world: &mut SyncWorld, sender: CommandSender, args: &CommandArgs) -> Result<()> {
+    let CommandSender::Player(pk) = sender else { bail!("the console must specify a player") };
+    let pos = Vec3::new(args.float(0), args.float(1), args.float(2));
+    teleport(world, sender, pk, pos);
+    Ok(())
+}
+
+// teleport a player to a position and tell the sender
+fn teleport(world: &mut SyncWorld, sender: CommandSender, pk: JoinedPlayerKey, pos: Vec3<f32>) {
+    world.server_only.player_pos[pk] = pos;
+    world.server_only.player_dig[pk] = None;
+    world.sync_ctx.conn_mgr.send(pk, DownMsg::PostJoin(PostJoinDownMsg::Teleport { pos }));
+    broadcast_char_state(world, pk);
+    let username = world.sync_ctx.conn_mgr.player_username(pk);
+    reply(world, sender, &format!("teleported {} to {} {} {}", username, pos.x, pos.y, pos.z));
+}
+
+// /give <player> <item> [count]: give a player items, dropping any that don't fit
+fn give(world: &mut SyncWorld, sender: CommandSender, args: &CommandArgs) -> Result<()> {
+    let pk = args.player(0);
+    let iid = args.item(1);
+    let count = args.get(2).map(|_| args.int(2)).unwrap_or(1);
+    let game = Arc::clone(&world.sync_ctx.game);
+    ensure!(
+        has_unit_meta(&game.items_meta_transcloner[iid]),
+        "{} can't be given", game.items_machine_name[iid],
+    );
+    let max_count = game.items_max_count[iid].get() as i64;
+    let mut left = count;
+    while left > 0 {
+        let stack_count = min(left, max_count);
+        left -= stack_count;
+        let stack = ItemStack {
+            iid,
+            meta: ItemMeta::new(()),
+            count: (stack_count as u8).try_into().unwrap(),
+            damage: 0,
+        };
+        give_item(world, pk, stack);
+    }
+    let username = world.sync_ctx.conn_mgr.player_username(pk);
+    reply(world, sender, &format!(
+        "gave {} {} {}", username, count, game.items_machine_name[iid],
+    ));
+    Ok(())
+}
+
+// whether items with the transcloner have `()` metadata
+fn has_unit_meta(transcloner: &ItemTranscloner) -> bool {
+    transcloner.instance_type_id() == TypeId::of::<()>()
+}
+
+// /time set <ticks>: set the time of day in ticks
+fn time_set_ticks(
+    world: &mut SyncWorld,
+    sender: CommandSender,
+    args: &CommandArgs,
+) -> Result<()> {
+    set_time_of_day(world, sender, args.int(1) as u64);
+    Ok(())
+}
+
+// /time set day|noon|night|midnight: set the time of day to a named time
+fn time_set_named(
+    world: &mut SyncWorld,
+    sender: CommandSender,
+    args: &CommandArgs,
+) -> Result<()> {
+    let i = TIME_NAMES.iter().position(|&name| name == args.literal(1)).unwrap();
+    set_time_of_day(world, sender, NAMED_TIMES[i]);
+    Ok(())
+}
+
+// set the time of day, tell all players, and tell the sender
+fn set_time_of_day(world: &mut SyncWorld, sender: CommandSender, time_of_day: u64) {
+    world.server_only.time_of_day = time_of_day;
+    for pk in world.sync_ctx.conn_mgr.players().iter() {
+        world.sync_ctx.conn_mgr.send(pk, DownMsg::PreJoin(PreJoinDownMsg::SetTimeOfDay {
+            time_of_day,
+            daylight_cycle: world.server_only.game_rules.do_daylight_cycle,
+        }));
+    }
+    reply(world, sender, &format!("set the time to {}", time_of_day));
+}
+
+// /list: list the online players
+fn list(world: &mut SyncWorld, sender: CommandSender, _: &CommandArgs) -> Result<()> {
+    let usernames = online_usernames(world);
+    reply(world, sender, &format!(
+        "{} player(s) online: {}", usernames.len(), usernames.join(", "),
+    ));
+    Ok(())
+}
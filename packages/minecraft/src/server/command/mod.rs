@@ -0,0 +1,395 @@
+//! Commands, which can be run from the server console or by players by sending a chat message
+//! starting with `/`.
+//!
+//! Commands are registered in a `CommandRegistry` along with the permission level needed to run
+//! them and the arguments they take. Arguments are parsed and validated before the command runs,
+//! and the same argument specifications provide the data for tab-completing a partially typed
+//! command.
+//!
+//! This is a "game logic" module moreso than a "system" module.
+
+pub mod builtin;
+
+use crate::{
+    server::{
+        per_player::*,
+        SyncWorld,
+    },
+    game_data::GameData,
+    item::RawItemId,
+    message::*,
+};
+use std::collections::HashSet;
+use anyhow::*;
+
+
+/// Permission level needed to run a command. Variants increase in privilege.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum PermissionLevel {
+    /// Any player may run it.
+    Player,
+    /// Only operators and the server console may run it.
+    Operator,
+}
+
+/// Which players have operator permission level.
+#[derive(Debug, Clone)]
+pub enum Operators {
+    /// All players, as is the case for the internal server of a singleplayer world.
+    All,
+    /// Players with these usernames.
+    Usernames(HashSet<String>),
+}
+
+/// Who is running a command.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub enum CommandSender {
+    /// The server console.
+    Console,
+    /// A joined player, through chat.
+    Player(JoinedPlayerKey),
+}
+
+/// A command that can be run from the server console or by players.
+#[derive(Debug)]
+pub struct Command {
+    /// Name typed to run the command, without the leading `/`.
+    pub name: &'static str,
+    /// Permission level needed to run the command.
+    pub permission: PermissionLevel,
+    /// Alternative forms of the command. When run, the first form the arguments parse as is used.
+    pub forms: Vec<CommandForm>,
+}
+
+/// A form of a command, with the arguments it takes and what it does with them.
+#[derive(Debug)]
+pub struct CommandForm {
+    /// Arguments, in order. Optional arguments must come after all required ones.
+    pub args: Vec<ArgSpec>,
+    /// Run the command with parsed arguments. Error messages are replied to the sender.
+    pub run: fn(&mut SyncWorld, CommandSender, &CommandArgs) -> Result<()>,
+}
+
+/// Argument a command takes.
+#[derive(Debug, Copy, Clone)]
+pub struct ArgSpec {
+    /// Name shown in usage messages.
+    pub name: &'static str,
+    /// Kind of value it takes.
+    pub kind: ArgKind,
+    /// Whether it may be omitted.
+    pub optional: bool,
+}
+
+/// Kind of value a command argument takes.
+#[derive(Debug, Copy, Clone)]
+pub enum ArgKind {
+    /// One of the given words.
+    Literal(&'static [&'static str]),
+    /// Username of a joined player.
+    Player,
+    /// Machine name of an item.
+    Item,
+    /// Integer within the given inclusive range.
+    Int { min: i64, max: i64 },
+    /// Finite number.
+    Float,
+}
+
+/// Parsed value of a command argument.
+#[derive(Debug, Copy, Clone)]
+pub enum ArgValue {
+    Literal(&'static str),
+    Player(JoinedPlayerKey),
+    Item(RawItemId),
+    Int(i64),
+    Float(f32),
+}
+
+/// Parsed arguments of a command, in the order of its form's argument specs. Omitted optional
+/// arguments are absent from the end.
+///
+/// Getters panic if the argument is absent or of a different kind, which can't happen for
+/// arguments the form requires.
+#[derive(Debug, Clone, Default)]
+pub struct CommandArgs(pub Vec<ArgValue>);
+
+/// All commands, by name.
+#[derive(Debug, Default)]
+pub struct CommandRegistry {
+    commands: Vec<Command>,
+}
+
+
+impl ArgSpec {
+    /// Construct a required argument.
+    pub fn required(name: &'static str, kind: ArgKind) -> Self {
+        ArgSpec { name, kind, optional: false }
+    }
+
+    /// Construct an optional argument.
+    pub fn optional(name: &'static str, kind: ArgKind) -> Self {
+        ArgSpec { name, kind, optional: true }
+    }
+
+    // parse a word typed for this argument
+    fn parse(&self, word: &str, world: &SyncWorld) -> Result<ArgValue> {
+        Ok(match self.kind {
+            ArgKind::Literal(literals) => ArgValue::Literal(literals.iter()
+                .find(|&&literal| literal == word)
+                .ok_or_else(|| anyhow!("expected {}, got {:?}", literals.join(" or "), word))?),
+            ArgKind::Player => ArgValue::Player(world.sync_ctx.conn_mgr.username_player(word)
+                .and_then(|pk| world.sync_ctx.conn_mgr.players().to_jpk(pk))
+                .ok_or_else(|| anyhow!("no player named {:?} is online", word))?),
+            ArgKind::Item => ArgValue::Item(item_by_machine_name(&world.sync_ctx.game, word)
+                .ok_or_else(|| anyhow!("no item named {:?}", word))?),
+            ArgKind::Int { min, max } => {
+                let n = word.parse::<i64>().map_err(|_| {
+                    anyhow!("expected a whole number for {}, got {:?}", self.name, word)
+                })?;
+                ensure!(
+                    (min..=max).contains(&n),
+                    "{} must be between {} and {}, got {}", self.name, min, max, n,
+                );
+                ArgValue::Int(n)
+            }
+            ArgKind::Float => ArgValue::Float(word.parse::<f32>().ok()
+                .filter(|n| n.is_finite())
+                .ok_or_else(|| anyhow!("expected a number for {}, got {:?}", self.name, word))?),
+        })
+    }
+
+    // words which can be typed for this argument
+    fn candidates(&self, world: &SyncWorld) -> Vec<String> {
+        match self.kind {
+            ArgKind::Literal(literals) =>
+                literals.iter().map(|&literal| literal.to_owned()).collect(),
+            ArgKind::Player => online_usernames(world),
+            ArgKind::Item => world.sync_ctx.game.items.iter()
+                .map(|iid| world.sync_ctx.game.items_machine_name[iid].clone())
+                .collect(),
+            ArgKind::Int { .. } | ArgKind::Float => Vec::new(),
+        }
+    }
+}
+
+impl CommandForm {
+    // usage string for this form of the given command
+    fn usage(&self, name: &str) -> String {
+        let mut usage = format!("/{}", name);
+        for arg in &self.args {
+            let arg_usage = match arg.kind {
+                ArgKind::Literal(&[literal]) => literal.to_owned(),
+                ArgKind::Literal(literals) => literals.join("|"),
+                _ => arg.name.to_owned(),
+            };
+            if arg.optional {
+                usage += &format!(" [{}]", arg_usage);
+            } else if let ArgKind::Literal(_) = arg.kind {
+                usage += &format!(" {}", arg_usage);
+            } else {
+                usage += &format!(" <{}>", arg_usage);
+            }
+        }
+        usage
+    }
+
+    // parse the words typed after the command name as this form's arguments
+    fn parse(&self, words: &[&str], world: &SyncWorld) -> Result<CommandArgs> {
+        let required = self.args.iter().filter(|arg| !arg.optional).count();
+        ensure!(
+            (required..=self.args.len()).contains(&words.len()),
+            "wrong number of arguments",
+        );
+        Ok(CommandArgs(self.args.iter()
+            .zip(words)
+            .map(|(arg, word)| arg.parse(word, world))
+            .collect::<Result<_>>()?))
+    }
+}
+
+impl CommandArgs {
+    /// Get the argument at the given index, if present.
+    pub fn get(&self, idx: usize) -> Option<ArgValue> {
+        self.0.get(idx).copied()
+    }
+
+    /// Get the literal argument at the given index.
+    pub fn literal(&self, idx: usize) -> &'static str {
+        match self.0[idx] {
+            ArgValue::Literal(literal) => literal,
+            ref value => panic!("expected literal argument, got {:?}", value),
+        }
+    }
+
+    /// Get the player argument at the given index.
+    pub fn player(&self, idx: usize) -> JoinedPlayerKey {
+        match self.0[idx] {
+            ArgValue::Player(pk) => pk,
+            ref value => panic!("expected player argument, got {:?}", value),
+        }
+    }
+
+    /// Get the item argument at the given index.
+    pub fn item(&self, idx: usize) -> RawItemId {
+        match self.0[idx] {
+            ArgValue::Item(iid) => iid,
+            ref value => panic!("expected item argument, got {:?}", value),
+        }
+    }
+
+    /// Get the integer argument at the given index.
+    pub fn int(&self, idx: usize) -> i64 {
+        match self.0[idx] {
+            ArgValue::Int(n) => n,
+            ref value => panic!("expected integer argument, got {:?}", value),
+        }
+    }
+
+    /// Get the number argument at the given index.
+    pub fn float(&self, idx: usize) -> f32 {
+        match self.0[idx] {
+            ArgValue::Float(n) => n,
+            ref value => panic!("expected number argument, got {:?}", value),
+        }
+    }
+}
+
+impl CommandRegistry {
+    /// Construct with no commands.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a command. Panics if one with the same name is already registered, or if an
+    /// optional argument comes before a required one.
+    pub fn register(&mut self, command: Command) {
+        assert!(self.get(command.name).is_none(), "command {:?} registered twice", command.name);
+        for form in &command.forms {
+            assert!(
+                form.args.windows(2).all(|pair| !pair[0].optional || pair[1].optional),
+                "command {:?} has optional argument before required argument",
+                command.name,
+            );
+        }
+        self.commands.push(command);
+    }
+
+    /// Look up a command by name.
+    pub fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.iter().find(|command| command.name == name)
+    }
+
+    /// Iterate over all commands.
+    pub fn iter(&self) -> impl Iterator<Item=&Command> + '_ {
+        self.commands.iter()
+    }
+}
+
+
+/// Get the permission level of the given command sender.
+pub fn permission_level(world: &SyncWorld, sender: CommandSender) -> PermissionLevel {
+    match sender {
+        CommandSender::Console => PermissionLevel::Operator,
+        CommandSender::Player(pk) => match world.server_only.operators {
+            Operators::All => PermissionLevel::Operator,
+            Operators::Usernames(ref usernames)
+                if usernames.contains(world.sync_ctx.conn_mgr.player_username(pk)) =>
+                PermissionLevel::Operator,
+            Operators::Usernames(_) => PermissionLevel::Player,
+        },
+    }
+}
+
+/// Send a line of command output to the command sender.
+pub fn reply(world: &SyncWorld, sender: CommandSender, line: &str) {
+    match sender {
+        CommandSender::Console => info!("{}", line),
+        CommandSender::Player(pk) => world.sync_ctx.conn_mgr
+            .send(pk, DownMsg::PostJoin(PostJoinDownMsg::ChatLine { line: line.to_owned() })),
+    }
+}
+
+/// Run a typed command line, without the leading `/`, on behalf of the sender. Replies to the
+/// sender if the command is unknown, not permitted, or fails.
+pub fn run_command(world: &mut SyncWorld, sender: CommandSender, line: &str) {
+    if let CommandSender::Player(pk) = sender {
+        info!("{} ran command /{}", world.sync_ctx.conn_mgr.player_username(pk), line);
+    }
+    if let Err(e) = try_run_command(world, sender, line) {
+        reply(world, sender, &e.to_string());
+    }
+}
+
+// run a typed command line, erroring with a message to reply with if it can't be run or fails
+fn try_run_command(world: &mut SyncWorld, sender: CommandSender, line: &str) -> Result<()> {
+    let mut words = line.split_whitespace();
+    let name = words.next().ok_or_else(|| anyhow!("type a command after the /"))?;
+    let words = words.collect::<Vec<_>>();
+    let command = world.sync_ctx.commands.get(name)
+        .filter(|command| permission_level(world, sender) >= command.permission)
+        .ok_or_else(|| anyhow!("unknown command /{}", name))?;
+    let mut first_error = None;
+    for form in &command.forms {
+        match form.parse(&words, world) {
+            Result::Ok(args) => return (form.run)(world, sender, &args),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    let usages = command.forms.iter()
+        .map(|form| form.usage(command.name))
+        .collect::<Vec<_>>()
+        .join(" or ");
+    match first_error {
+        Some(e) if command.forms.len() == 1 => bail!("{} (usage: {})", e, usages),
+        _ => bail!("usage: {}", usages),
+    }
+}
+
+/// Compute completions of the last word of a partially typed command line, without the leading
+/// `/`, for the sender. Completions are whole words which could replace the last word.
+pub fn complete_command(world: &SyncWorld, sender: CommandSender, line: &str) -> Vec<String> {
+    // the last word is empty if the line ends with whitespace
+    let mut words = line.split_whitespace().collect::<Vec<_>>();
+    if line.is_empty() || line.ends_with(char::is_whitespace) {
+        words.push("");
+    }
+    let (&last, previous) = words.split_last().unwrap();
+    let level = permission_level(world, sender);
+    let mut candidates = match previous.split_first() {
+        // complete the command name
+        None => world.sync_ctx.commands.iter()
+            .filter(|command| level >= command.permission)
+            .map(|command| command.name.to_owned())
+            .collect(),
+        // complete an argument for any form the previous arguments parse as
+        Some((&name, previous_args)) => world.sync_ctx.commands.get(name)
+            .filter(|command| level >= command.permission)
+            .into_iter()
+            .flat_map(|command| &command.forms)
+            .filter(|form| form.args.iter()
+                .zip(previous_args)
+                .all(|(arg, word)| arg.parse(word, world).is_ok()))
+            .filter_map(|form| form.args.get(previous_args.len()))
+            .flat_map(|arg| arg.candidates(world))
+            .collect::<Vec<_>>(),
+    };
+    candidates.retain(|candidate| candidate.starts_with(last));
+    candidates.sort();
+    candidates.dedup();
+    candidates
+}
+
+/// Get the usernames of all joined players.
+pub fn online_usernames(world: &SyncWorld) -> Vec<String> {
+    world.sync_ctx.conn_mgr.players().iter_joined()
+        .map(|pk| world.sync_ctx.conn_mgr.player_username(pk).to_owned())
+        .collect()
+}
+
+// look up an item by machine name
+fn item_by_machine_name(game: &GameData, machine_name: &str) -> Option<RawItemId> {
+    game.items.iter().find(|&iid| game.items_machine_name[iid] == machine_name)
+}
@@ -6,6 +6,7 @@ use crate::{
         network::*,
         save_content::*,
     },
+    auth::*,
     util_abort_handle::*,
     util_must_drain::MustDrain,
     util_time::ServerRelTime,
@@ -16,6 +17,7 @@ use std::{
         HashMap,
         VecDeque,
     },
+    cell::RefCell,
     mem::replace,
     time::Instant,
 };
@@ -72,18 +74,22 @@ use anyhow::*;
 ///
 /// 1. A network connection is created. It gets a connection index within this manager, but the
 ///    rest of the server doesn't learn about it.
-/// 2. LogIn is received from client.
+/// 2. LogIn is received from client. AuthChallenge is sent to client.
+/// 3. AuthResponse is received from client, proving it has the keypair for the username.
 ///
+///    - If a player with that username is already logged in with the same public key, they're
+///      kicked. If with a different public key, the connection is closed instead.
 ///    - Player is created. Rest of the server must update.
 ///    - AcceptLogIn is sent to client.
 ///    - All joined players are loaded into client.
 ///    - Chunks begin being loaded into client.
 ///    - Request is submitted to save file to read player's saved state.
 ///
-/// 3. Saved player state arrives from save file. It is stored for later.
-/// 4. Server reaches necessary conditions for allowing player to join. ShouldJoinGame is sent to
+/// 4. Saved player state arrives from save file. If it's registered to a different public key,
+///    the player is kicked. Otherwise, it is stored for later.
+/// 5. Server reaches necessary conditions for allowing player to join. ShouldJoinGame is sent to
 ///    client.
-/// 5. JoinGame is received from client.
+/// 6. JoinGame is received from client.
 ///
 ///    - Player is joined. Rest of the server must update.
 ///    - Player is loaded into all clients.
@@ -104,6 +110,8 @@ pub struct ConnMgr {
     player_username: PerPlayer<String>,
     // inverse of player_username
     username_player: HashMap<String, PlayerKey>,
+    // for each player, the public key they proved they have the keypair for
+    player_public_key: PerPlayer<PublicKey>,
     // for each player, its state in terms of loading its save state from the save file
     player_load_save_state_state: PerPlayer<PlayerLoadSaveStateState>,
     // for each player, their clientside space of players.
@@ -112,6 +120,8 @@ pub struct ConnMgr {
     // some sort of inverse-like thing of player_clientside_players.
     // player A -> player B -> A's clientside player idx for B, if exists (ie. if B joined).
     player_player_clientside_player_idx: PerPlayer<PerJoinedPlayer<usize>>,
+    // players requested to be kicked with kick_later, which haven't been yet, with reasons
+    kick_requests: RefCell<Vec<(PlayerKey, String)>>,
 }
 
 // state for each network connection
@@ -123,6 +133,9 @@ struct ConnectionState {
     last_processed: u64,
     // whether the current value of last_processed has been acked to the client
     last_processed_acked: bool,
+    // log in awaiting the client's response to its auth challenge, if any. is mutually exclusive
+    // with pk.
+    pending_log_in: Option<PendingLogIn>,
     // corresponding player key, if logged in.
     pk: Option<PlayerKey>,
     // whether this connection previously logged in but then was killed. prevents subsequent log in
@@ -135,6 +148,13 @@ struct ConnectionState {
     should_join_game: bool,
 }
 
+// log in awaiting the client's response to its auth challenge
+struct PendingLogIn {
+    username: String,
+    public_key: PublicKey,
+    challenge: Challenge,
+}
+
 // state for a player in terms of loading its save state from the save file
 enum PlayerLoadSaveStateState {
     // request to load from the save file is pending
@@ -206,6 +226,8 @@ pub enum ConnMgrEffect {
         jpk: Option<JoinedPlayerKey>,
         /// Corresponding username.
         username: String,
+        /// Corresponding public key.
+        public_key: PublicKey,
     },
 }
 
@@ -228,6 +250,14 @@ impl ConnMgr {
         self.connections[self.player_conn_idx[pk.into()]].connection.send(msg.into());
     }
 
+    /// Enqueue a line of chat to be transmitted to all joined player clients, and log it.
+    pub fn broadcast_chat_line(&self, line: &str) {
+        info!("[chat] {}", line);
+        for pk in self.players.iter_joined() {
+            self.send(pk, DownMsg::PostJoin(PostJoinDownMsg::ChatLine { line: line.to_owned() }));
+        }
+    }
+
     /// Get `pk`'s `server_t0`.
     ///
     /// See [`Connection::server_t0`][super::network::Connection::server_t0].
@@ -251,9 +281,28 @@ impl ConnMgr {
         rel_time.to_instant(self.server_t0(pk))
     }
 
-    /// Close the player client connection and remove the player.
-    pub fn kick<K: Into<PlayerKey>>(&mut self, pk: K) -> MustDrain {
-        self.kill_connection(self.player_conn_idx[pk.into()]);
+    /// Close the player client connection, telling the client the given reason, and remove the
+    /// player.
+    pub fn kick<K: Into<PlayerKey>>(&mut self, pk: K, reason: &str) -> MustDrain {
+        self.kill_connection(self.player_conn_idx[pk.into()], reason);
+        MustDrain
+    }
+
+    /// Request that the player be kicked upon the next call to `kick_requested`.
+    ///
+    /// This allows kicking a player from code which only has shared access to the conn mgr, or
+    /// which is in the middle of processing something that assumes players aren't removed.
+    pub fn kick_later<K: Into<PlayerKey>>(&self, pk: K, reason: String) {
+        self.kick_requests.borrow_mut().push((pk.into(), reason));
+    }
+
+    /// Kick all players requested to be kicked with `kick_later` who are still present.
+    pub fn kick_requested(&mut self) -> MustDrain {
+        for (pk, reason) in self.kick_requests.take() {
+            if self.players.iter().any(|pk2| pk2 == pk) {
+                let MustDrain = self.kick(pk, &reason);
+            }
+        }
         MustDrain
     }
 
@@ -262,6 +311,11 @@ impl ConnMgr {
         &self.player_username[pk.into()]
     }
 
+    /// Get the public key the given player logged in with.
+    pub fn player_public_key<K: Into<PlayerKey>>(&self, pk: K) -> PublicKey {
+        self.player_public_key[pk.into()]
+    }
+
     /// Try to look up a player by username.
     pub fn username_player(&self, username: &str) -> Option<PlayerKey> {
         self.username_player.get(username).copied()
@@ -281,6 +335,7 @@ impl ConnMgr {
                     connection,
                     last_processed: 0,
                     last_processed_acked: true,
+                    pending_log_in: None,
                     pk: None,
                     killed: false,
                     should_join_game: false,
@@ -294,7 +349,7 @@ impl ConnMgr {
                 if let Err(e) = result {
                     // on error, kill connection
                     warn!(%e, "client protocol error, closing connection");
-                    self.kill_connection(conn_idx);
+                    self.kill_connection(conn_idx, &format!("protocol violation: {}", e));
                 }
             }
             NetworkEvent::RemoveConnection(conn_idx) => {
@@ -350,6 +405,9 @@ impl ConnMgr {
             UpMsg::LogIn(msg) => {
                 self.try_handle_log_in(conn_idx, msg)
             }
+            UpMsg::AuthResponse(msg) => {
+                self.try_handle_auth_response(conn_idx, msg)
+            }
             UpMsg::PreJoin(msg) => {
                 self.try_handle_pre_join_msg(conn_idx, msg)
             }
@@ -366,12 +424,50 @@ impl ConnMgr {
     // indicates that the network connection should be terminated.
     fn try_handle_log_in(&mut self, conn_idx: usize, msg: UpMsgLogIn) -> Result<()> {
         // prepare and validate
-        let UpMsgLogIn { username } = msg;
-        ensure!(self.connections[conn_idx].pk.is_none(), "client tried to log in twice");
+        let UpMsgLogIn { username, public_key } = msg;
+        ensure!(
+            self.connections[conn_idx].pk.is_none()
+                && self.connections[conn_idx].pending_log_in.is_none(),
+            "client tried to log in twice",
+        );
+        ensure!(is_valid_username(&username), "invalid username");
+
+        // challenge the client to prove it has the keypair
+        let challenge = new_challenge();
+        self.connections[conn_idx].connection
+            .send(DownMsg::AuthChallenge(DownMsgAuthChallenge { challenge }));
+        self.connections[conn_idx].pending_log_in =
+            Some(PendingLogIn { username, public_key, challenge });
+
+        Ok(())
+    }
+
+    // internal method to try to process an auth response message from a non-killed connection.
+    // error indicates that the network connection should be terminated.
+    fn try_handle_auth_response(&mut self, conn_idx: usize, msg: UpMsgAuthResponse) -> Result<()> {
+        // prepare and validate
+        let UpMsgAuthResponse { signature } = msg;
+        let PendingLogIn { username, public_key, challenge } =
+            self.connections[conn_idx].pending_log_in.take()
+                .ok_or_else(|| anyhow!("wrong time to send auth response"))?;
+        let tls_fingerprint = self.connections[conn_idx].connection.tls_fingerprint();
+        ensure!(
+            verify_challenge(&public_key, &challenge, &username, tls_fingerprint, &signature),
+            "invalid auth signature",
+        );
+
+        // deal with a player with that username already being logged in
+        if let Some(pk2) = self.username_player(&username) {
+            if self.player_public_key[pk2] == public_key {
+                // probably the same person, perhaps reconnecting before the server noticed their
+                // old connection was lost
+                self.kill_connection(self.player_conn_idx[pk2], "logged in from another location");
+            } else {
+                self.kill_connection(conn_idx, &format!("{} is already logged in", username));
+                return Ok(());
+            }
+        }
 
-        // uniqueify username
-        let username = uniqueify_username(username, &self.username_player);
-        
         // initialize player key
         let pk = self.players.add();
         self.connections[conn_idx].pk = Some(pk);
@@ -380,6 +476,7 @@ impl ConnMgr {
         // initialize in username tracking structures
         self.player_username.insert(pk, username.clone());
         self.username_player.insert(username.clone(), pk);
+        self.player_public_key.insert(pk, public_key);
 
         // initialize in structure for tracking the loading of its save state
         let aborted_1 = AbortGuard::new();
@@ -426,12 +523,20 @@ impl ConnMgr {
     }
 
     /// Call upon the result of a previously triggered player save state loading operation being
-    /// ready, unless aborted.
+    /// ready, unless aborted. Kicks the player if it isn't theirs, as per `owns_save_state`.
     pub fn on_player_save_state_ready(
         &mut self,
         pk: PlayerKey,
         save_val: Option<PlayerSaveVal>,
     ) -> MustDrain {
+        // validate
+        if let Some(ref save_val) = save_val {
+            if !self.owns_save_state(pk, save_val) {
+                let reason = not_owner_reason(&self.player_username[pk]);
+                return self.kick(pk, &reason);
+            }
+        }
+
         // store
         self.player_load_save_state_state[pk] = PlayerLoadSaveStateState::Stashed(save_val);
 
@@ -440,6 +545,21 @@ impl ConnMgr {
         MustDrain
     }
 
+    /// Whether the player logged in with the public key the save state is registered to.
+    ///
+    /// If not, and the save state was loaded from somewhere other than the save file, the caller
+    /// should avoid giving it to `on_player_save_state_ready`, lest it be lost, and instead kick
+    /// the player with `kick_not_owner_later`.
+    pub fn owns_save_state(&self, pk: PlayerKey, save_val: &PlayerSaveVal) -> bool {
+        save_val.public_key == self.player_public_key[pk]
+    }
+
+    /// Request that the player be kicked for not owning the save state for their username, as per
+    /// `owns_save_state`, upon the next call to `kick_requested`.
+    pub fn kick_not_owner_later(&self, pk: PlayerKey) {
+        self.kick_later(pk, not_owner_reason(&self.player_username[pk]));
+    }
+
     /// Whether conn mgr approves of sending the client a `ShouldJoinGame` message.
     ///
     /// Conn mgr tracks both whether it is an appropriate time to do so in terms of the
@@ -507,6 +627,15 @@ impl ConnMgr {
         let pk = self.connections[conn_idx].pk
             .and_then(|pk| self.players.to_jpk(pk))
             .ok_or_else(|| anyhow!("wrong time to send player msg"))?;
+        if let PlayerMsg::Chat(PlayerMsgChat { ref text }) = msg {
+            ensure!(!text.trim().is_empty(), "empty chat message");
+            ensure!(text.chars().count() <= MAX_CHAT_CHARS, "chat message too long");
+            ensure!(text.chars().all(is_allowed_chat_char), "chat message has disallowed chars");
+        }
+        if let PlayerMsg::TabComplete(PlayerMsgTabComplete { ref text }) = msg {
+            ensure!(text.chars().count() <= MAX_CHAT_CHARS, "tab complete text too long");
+            ensure!(text.chars().all(is_allowed_chat_char), "tab complete text has bad chars");
+        }
 
         // tell caller to process msg
         self.effects.push_back(ConnMgrEffect::PlayerMsg(pk, msg));
@@ -514,12 +643,12 @@ impl ConnMgr {
         Ok(())
     }
 
-    // internal method to actively terminate a connection and remove the associated player if there
-    // is one.
-    fn kill_connection(&mut self, conn_idx: usize) {
-        // tell the network connection to die. this will abandon allocation of resources to it and
-        // will trigger a corresponding `NetworkEvent::RemoveConnection` to happen soon. 
-        self.connections[conn_idx].connection.kill();
+    // internal method to actively terminate a connection, telling the client the given reason, and
+    // remove the associated player if there is one.
+    fn kill_connection(&mut self, conn_idx: usize, reason: &str) {
+        // tell the network connection to close. this will stop further messages from being sent to
+        // it and will trigger a corresponding `NetworkEvent::RemoveConnection` to happen soon. 
+        self.connections[conn_idx].connection.close(reason);
         self.connections[conn_idx].killed = true;
 
         // remove the associated player if there is one
@@ -534,24 +663,26 @@ impl ConnMgr {
     // which is dependent on the situation in which this was called.
     fn remove_player(&mut self, pk: PlayerKey) {
         let jpk = self.players.to_jpk(pk);
-        let username = self.deinit_player(pk);
+        let (username, public_key) = self.deinit_player(pk);
         if let Some(jpk) = jpk {
             self.deinit_joined_player(jpk);
         }
-        self.effects.push_back(ConnMgrEffect::RemovePlayer { pk, jpk, username });
+        self.effects.push_back(ConnMgrEffect::RemovePlayer { pk, jpk, username, public_key });
     }
 
-    // internal method to clean up internally when a player is removed. returns its username.
-    fn deinit_player(&mut self, pk: PlayerKey) -> String {
+    // internal method to clean up internally when a player is removed. returns its username and
+    // public key.
+    fn deinit_player(&mut self, pk: PlayerKey) -> (String, PublicKey) {
         // remove from internal data structures
         self.players.remove(pk);
         self.player_conn_idx.remove(pk);
         let username = self.player_username.remove(pk);
         self.username_player.remove(&username).unwrap();
+        let public_key = self.player_public_key.remove(pk);
         self.player_load_save_state_state.remove(pk);
         self.player_clientside_players.remove(pk);
         self.player_player_clientside_player_idx.remove(pk);
-        username
+        (username, public_key)
     }
 
     // internal method to clean up internally when a joined player is removed
@@ -571,7 +702,7 @@ impl ConnMgr {
     /// Close all connections and abort all load player save state requests. For server shutdown.
     pub fn on_shutdown(&mut self) {
         for (_, conn) in &self.connections {
-            conn.connection.kill();
+            conn.connection.close("server stopped");
         }
         for pk in self.players.iter() {
             let plsss = &self.player_load_save_state_state[pk];
@@ -582,17 +713,7 @@ impl ConnMgr {
     }
 }
 
-// temporary method until there's a better decentralized identity system
-fn uniqueify_username(username: String, usernames: &HashMap<String, PlayerKey>) -> String {
-    if usernames.contains_key(&username) {
-        let mut i = 2;
-        let mut username2;
-        while {
-            username2 = format!("{}{}", username, i);
-            usernames.contains_key(&username2)
-        } { i += 1 }
-        username2
-    } else {
-        username
-    }
+// reason for kicking a player for not owning the save state for their username
+fn not_owner_reason(username: &str) -> String {
+    format!("the username {} is registered to a different key", username)
 }
@@ -0,0 +1,95 @@
+//! Keeping players' open container menus in sync with the containers.
+//!
+//! This is a "game logic" module moreso than a "system" module.
+
+use crate::{
+    server::{
+        per_player::*,
+        process_player_msg::PlayerOpenSyncMenu,
+        SyncWorld,
+    },
+    message::*,
+    game_data::container_logic::{ContainerLogic, MAX_CONTAINER_SLOTS},
+    item::ItemStack,
+    util_usize_lt::UsizeLt,
+};
+use chunk_data::*;
+use vek::*;
+
+
+/// Called every tick to send players with a container menu open any changes to the container's
+/// properties.
+pub fn sync_container_properties(world: &mut SyncWorld) {
+    let sync_ctx = world.sync_ctx;
+    for pk in sync_ctx.conn_mgr.players().iter_joined() {
+        let Some(gtc) = open_container(world, pk) else { continue };
+        let Some((tile, container)) = container_at(world, gtc) else { continue };
+        let properties = container.properties(tile.get(world.tile_blocks.as_ref()));
+        let open = world.server_only.player_open_sync_menu[pk].as_mut().unwrap();
+        for (idx, &value) in properties.iter().enumerate() {
+            if open.sent_properties.get(idx) != Some(&value) {
+                sync_ctx.conn_mgr.send(pk, DownMsg::PostJoin(
+                    PostJoinDownMsg::SetContainerProperty { gtc, idx: UsizeLt::new(idx), value }
+                ));
+            }
+        }
+        open.sent_properties = properties;
+    }
+}
+
+/// Set the content of a container's item slot, sending the update to all clients with the container
+/// open and giving the container a block update so it can react. Does nothing if there's no
+/// container at the tile.
+pub fn write_container_slot(
+    world: &mut SyncWorld,
+    gtc: Vec3<i64>,
+    idx: UsizeLt<MAX_CONTAINER_SLOTS>,
+    content: Option<ItemStack>,
+) {
+    let Some((tile, container)) = container_at(world, gtc) else { return };
+
+    // send update to clients
+    for pk2 in world.sync_ctx.conn_mgr.players().iter_joined() {
+        if open_container(world, pk2) == Some(gtc) {
+            world.sync_ctx.conn_mgr.send(pk2, DownMsg::PostJoin(PostJoinDownMsg::SetItemSlot {
+                item_slot: DownItemSlotRef::Container { gtc, idx },
+                slot_content: content.clone(),
+            }));
+        }
+    }
+
+    // mark chunk as unsaved
+    world.sync_ctx.save_mgr.mark_chunk_unsaved(tile.cc, tile.ci);
+
+    // let the container react, such as a furnace starting to smelt
+    world.sync_ctx.block_updates.borrow_mut().push(tile);
+
+    // edit server's in-memory representation
+    let slots = container.slots_mut(tile.get(&mut world.tile_blocks).as_write_manual());
+    slots[idx.get()] = content;
+}
+
+/// The tile and container logic of the container block at the given tile, if there is one and it's
+/// loaded.
+pub fn container_at<'a>(
+    world: &SyncWorld<'a>,
+    gtc: Vec3<i64>,
+) -> Option<(TileKey, &'a dyn ContainerLogic)> {
+    let tile = world.getter.gtc_get(gtc)?;
+    let bid = tile.get(world.tile_blocks.as_ref()).get();
+    let container = world.sync_ctx.game.blocks_container_logic[bid].as_deref()?;
+    Some((tile, container))
+}
+
+/// Tile of the container the player has open, if they have one open and the server hasn't
+/// invalidated it.
+pub fn open_container(world: &SyncWorld, pk: JoinedPlayerKey) -> Option<Vec3<i64>> {
+    match world.server_only.player_open_sync_menu[pk] {
+        Some(PlayerOpenSyncMenu {
+            valid: true,
+            menu: PlayerMsgOpenSyncMenu::Container(gtc),
+            ..
+        }) => Some(gtc),
+        _ => None,
+    }
+}
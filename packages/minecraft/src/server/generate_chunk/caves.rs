@@ -0,0 +1,80 @@
+//! Cave carving generation stage.
+
+use super::derive_seed;
+use crate::game_data::*;
+use chunk_data::*;
+use std::sync::Arc;
+use bracket_noise::prelude::{FastNoise, NoiseType};
+use vek::*;
+
+
+/// Tiles are carved out where both cave noise fields are within this distance of zero. Larger
+/// values make wider tunnels.
+pub const CAVE_THRESHOLD: f32 = 0.08;
+
+/// Caves are squashed vertically by this factor, so that tunnels tend to run horizontally.
+pub const CAVE_VERTICAL_SQUASH: f32 = 2.0;
+
+
+/// Carves networks of winding tunnels through generated terrain.
+///
+/// Tunnels follow the intersections of the zero surfaces of two independent 3D noise fields, which
+/// produces long connected worm-like shapes.
+pub struct CaveCarver {
+    noise_a: FastNoise,
+    noise_b: FastNoise,
+}
+
+impl CaveCarver {
+    /// Construct with the given world seed.
+    pub fn new(seed: u64) -> Self {
+        let mut noise_a = FastNoise::seeded(derive_seed(seed, "cave_a"));
+        noise_a.set_noise_type(NoiseType::SimplexFractal);
+        noise_a.set_frequency(1.0 / 64.0);
+        noise_a.set_fractal_octaves(2);
+
+        let mut noise_b = FastNoise::seeded(derive_seed(seed, "cave_b"));
+        noise_b.set_noise_type(NoiseType::SimplexFractal);
+        noise_b.set_frequency(1.0 / 64.0);
+        noise_b.set_fractal_octaves(2);
+
+        CaveCarver {
+            noise_a,
+            noise_b,
+        }
+    }
+
+    /// Whether the tile at the given global tile coordinate is within a cave.
+    pub fn is_cave(&self, gtc: Vec3<i64>) -> bool {
+        let x = gtc.x as f32;
+        let y = gtc.y as f32 * CAVE_VERTICAL_SQUASH;
+        let z = gtc.z as f32;
+        self.noise_a.get_noise3d(x, y, z).abs() < CAVE_THRESHOLD
+            && self.noise_b.get_noise3d(x, y, z).abs() < CAVE_THRESHOLD
+    }
+
+    /// Carve caves out of the given chunk, replacing stone, dirt, grass, sand, and ores with air.
+    pub fn carve(&self, cc: Vec3<i64>, chunk_tile_blocks: &mut ChunkBlocks, game: &Arc<GameData>) {
+        let ores = &game.content.ore;
+        let carvable = [
+            game.content.stone.bid_stone.bid,
+            game.content.dirt.bid_dirt.bid,
+            game.content.grass.bid_grass.bid,
+            game.content.sand.bid_sand.bid,
+            ores.bid_coal_ore.bid,
+            ores.bid_iron_ore.bid,
+            ores.bid_gold_ore.bid,
+            ores.bid_redstone_ore.bid,
+            ores.bid_diamond_ore.bid,
+        ];
+        for lti in 0..=MAX_LTI {
+            if !carvable.contains(&ChunkBlocks::get(chunk_tile_blocks, lti)) {
+                continue;
+            }
+            let gtc = cc * CHUNK_EXTENT + lti_to_ltc(lti).map(|n| n as i64);
+            if self.is_cave(gtc) {
+                chunk_tile_blocks.set(lti, AIR, ());
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! Decoration generation stage, which places features such as trees and boulders.
+//!
+//! Features are placed by the chunk which contains their origin, but may extend into neighboring
+//! chunks. Since a chunk being generated can only write into its own `ChunkBlocks`, blocks placed
+//! outside of it are collected as pending placements, which the server applies to the neighboring
+//! chunk either immediately if it is loaded, or otherwise when it is next loaded or generated.
+
+use crate::{
+    game_data::*,
+    server::save_content::*,
+};
+use chunk_data::*;
+use std::{
+    sync::Arc,
+    collections::HashMap,
+    fmt::Debug,
+};
+use rand::prelude::*;
+use vek::*;
+
+
+/// Destination for blocks placed by features while decorating a chunk.
+///
+/// Blocks are only placed over blocks which can be placed over. Blocks placed outside of the chunk
+/// being decorated are collected as pending placements for the neighboring chunk they fall in.
+pub struct DecorationCanvas<'a> {
+    cc: Vec3<i64>,
+    chunk_tile_blocks: &'a mut ChunkBlocks,
+    game: &'a Arc<GameData>,
+    spilled: HashMap<Vec3<i64>, Vec<PendingPlacement>>,
+}
+
+impl<'a> DecorationCanvas<'a> {
+    /// Construct for decorating the given chunk.
+    pub fn new(cc: Vec3<i64>, chunk_tile_blocks: &'a mut ChunkBlocks, game: &'a Arc<GameData>) -> Self {
+        DecorationCanvas {
+            cc,
+            chunk_tile_blocks,
+            game,
+            spilled: HashMap::new(),
+        }
+    }
+
+    /// Place a block at the given tile, if the block currently there can be placed over.
+    ///
+    /// Panics if the tile is not in the chunk being decorated or one of its 26 neighbors.
+    pub fn place<M>(&mut self, gtc: Vec3<i64>, bid: BlockId<M>, meta: M)
+    where
+        M: Debug + Send + Sync + 'static,
+    {
+        let cc = gtc_get_cc(gtc);
+        let lti = gtc_get_lti(gtc);
+        if cc == self.cc {
+            if self.game.blocks_can_place_over[ChunkBlocks::get(self.chunk_tile_blocks, lti)] {
+                self.chunk_tile_blocks.set(lti, bid, meta);
+            }
+        } else {
+            assert!(
+                (cc - self.cc).map(i64::abs).reduce_max() <= 1,
+                "feature extended further than neighboring chunk",
+            );
+            self.spilled.entry(cc).or_default().push(PendingPlacement {
+                lti,
+                bid_meta: ErasedBidMeta::new(bid, meta),
+            });
+        }
+    }
+
+    /// Convert into the placements which spilled into neighboring chunks, keyed by neighbor cc.
+    pub fn into_spilled(self) -> Vec<(Vec3<i64>, PendingPlacementsSaveVal)> {
+        self.spilled.into_iter()
+            .map(|(cc, placements)| (cc, PendingPlacementsSaveVal { placements }))
+            .collect()
+    }
+}
+
+/// Place a tree with its trunk starting at the given tile.
+pub fn place_tree<R: Rng>(canvas: &mut DecorationCanvas, base: Vec3<i64>, rng: &mut R) {
+    let game = Arc::clone(canvas.game);
+    let height = rng.gen_range(4..=6);
+    let top = base.y + height;
+
+    // trunk first, so that the leaves don't block it
+    for y in base.y..top {
+        canvas.place(Vec3::new(base.x, y, base.z), game.content.log.bid_log, ());
+    }
+
+    // leaves, wider at the bottom, with some corners randomly missing
+    for y in top - 3..=top {
+        let radius: i64 = if y < top - 1 { 2 } else { 1 };
+        for z in -radius..=radius {
+            for x in -radius..=radius {
+                let corner = x.abs() == radius && z.abs() == radius;
+                if corner && (y == top || rng.gen::<bool>()) {
+                    continue;
+                }
+                canvas.place(base + Vec3::new(x, y - base.y, z), game.content.leaves.bid_leaves, ());
+            }
+        }
+    }
+}
+
+/// Place a roughly spherical boulder centered at the given tile.
+pub fn place_boulder<R: Rng>(canvas: &mut DecorationCanvas, center: Vec3<i64>, rng: &mut R) {
+    let game = Arc::clone(canvas.game);
+    let radius: i64 = rng.gen_range(1..=2);
+    for z in -radius..=radius {
+        for y in -radius..=radius {
+            for x in -radius..=radius {
+                let offset = Vec3::new(x, y, z);
+                if offset.map(|n| n * n).sum() as f32 <= (radius * radius) as f32 + 0.5 {
+                    canvas.place(center + offset, game.content.cobblestone.bid_cobblestone, ());
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Superflat world generator.
+
+use super::{WorldGenerator, GeneratedChunk};
+use crate::{
+    server::save_content::*,
+    game_data::*,
+};
+use chunk_data::*;
+use std::sync::Arc;
+use vek::*;
+
+
+/// Y level of the grass surface in a flat world.
+pub const FLAT_SURFACE: i64 = 40;
+
+/// Number of layers of dirt under the grass in a flat world.
+pub const FLAT_DIRT_DEPTH: i64 = 3;
+
+
+/// World generator which generates an infinite flat plane of grass over dirt over stone. Ignores
+/// the seed.
+#[derive(Debug, Copy, Clone)]
+pub struct FlatWorldGenerator;
+
+impl WorldGenerator for FlatWorldGenerator {
+    fn generate_chunk(&self, cc: Vec3<i64>, game: &Arc<GameData>) -> GeneratedChunk {
+        let mut chunk_tile_blocks = ChunkBlocks::new(&game.blocks);
+        for y in 0..CHUNK_EXTENT.y {
+            let gy = cc.y * CHUNK_EXTENT.y + y;
+            for z in 0..CHUNK_EXTENT.z {
+                for x in 0..CHUNK_EXTENT.x {
+                    let lti = ltc_to_lti(Vec3 { x, y, z });
+                    if gy > FLAT_SURFACE {
+                        // air
+                    } else if gy == FLAT_SURFACE {
+                        chunk_tile_blocks.set(lti, game.content.grass.bid_grass, ());
+                    } else if gy >= FLAT_SURFACE - FLAT_DIRT_DEPTH {
+                        chunk_tile_blocks.set(lti, game.content.dirt.bid_dirt, ());
+                    } else {
+                        chunk_tile_blocks.set(lti, game.content.stone.bid_stone, ());
+                    }
+                }
+            }
+        }
+        GeneratedChunk {
+            save_val: ChunkSaveVal {
+                chunk_tile_blocks,
+                steves: Vec::new(),
+                pigs: Vec::new(),
+                falling_blocks: Vec::new(),
+                items: Vec::new(),
+                scheduled_ticks: Vec::new(),
+            },
+            spilled: Vec::new(),
+        }
+    }
+
+    fn spawn_pos(&self) -> Vec3<f32> {
+        Vec3::new(0.5, (FLAT_SURFACE + 3) as f32, 0.5)
+    }
+}
@@ -0,0 +1,183 @@
+//! The default world generator, which generates layered terrain over a noise heightmap.
+
+use super::{
+    WorldGenerator,
+    GeneratedChunk,
+    derive_seed,
+    chunk_rng,
+    caves::CaveCarver,
+    ores::place_ores,
+    decoration::*,
+};
+use crate::{
+    server::save_content::*,
+    game_data::*,
+};
+use chunk_data::*;
+use std::sync::Arc;
+use bracket_noise::prelude::{FastNoise, NoiseType};
+use rand::prelude::*;
+use vek::*;
+
+
+/// Y level of the sea. Terrain is distributed around this level.
+pub const SEA_LEVEL: i64 = 40;
+
+/// Approximate maximum distance of the terrain surface above or below sea level.
+pub const HEIGHT_AMPLITUDE: f32 = 24.0;
+
+/// Columns with their surface no more than this far above sea level are covered in sand rather
+/// than grass and dirt.
+pub const BEACH_HEIGHT: i64 = 2;
+
+/// Minimum number of tiles of soil (dirt or sand) between the surface and the stone.
+pub const MIN_SOIL_DEPTH: i64 = 3;
+
+/// Maximum number of tiles of soil (dirt or sand) between the surface and the stone.
+pub const MAX_SOIL_DEPTH: i64 = 5;
+
+/// Number of attempts per chunk to place a tree. Attempts fail if the chosen column's surface is
+/// not grass or is not within the chunk.
+pub const TREE_ATTEMPTS: u32 = 10;
+
+/// Probability per chunk of attempting to place a boulder.
+pub const BOULDER_CHANCE: f64 = 0.3;
+
+
+/// World generator which generates rolling terrain with layers of grass, dirt, and stone, and sand
+/// near sea level.
+///
+/// Generation happens in stages. First the terrain is generated from the heightmap, then ore veins
+/// are placed in the stone, then caves are carved through it all, then the surface is decorated
+/// with trees and boulders.
+pub struct LayeredWorldGenerator {
+    seed: u64,
+    height_noise: FastNoise,
+    soil_noise: FastNoise,
+    cave_carver: CaveCarver,
+}
+
+impl LayeredWorldGenerator {
+    /// Construct with the given world seed.
+    pub fn new(seed: u64) -> Self {
+        let mut height_noise = FastNoise::seeded(derive_seed(seed, "height"));
+        height_noise.set_noise_type(NoiseType::SimplexFractal);
+        height_noise.set_frequency(1.0 / 150.0);
+        height_noise.set_fractal_octaves(4);
+
+        let mut soil_noise = FastNoise::seeded(derive_seed(seed, "soil"));
+        soil_noise.set_noise_type(NoiseType::Simplex);
+        soil_noise.set_frequency(1.0 / 16.0);
+
+        LayeredWorldGenerator {
+            seed,
+            height_noise,
+            soil_noise,
+            cave_carver: CaveCarver::new(seed),
+        }
+    }
+
+    /// Y coordinate of the topmost non-air tile in the given column.
+    pub fn surface_height(&self, gx: i64, gz: i64) -> i64 {
+        let noise = self.height_noise.get_noise(gx as f32, gz as f32);
+        SEA_LEVEL + (noise * HEIGHT_AMPLITUDE).floor() as i64
+    }
+
+    /// Number of tiles of soil between the surface and the stone in the given column.
+    pub fn soil_depth(&self, gx: i64, gz: i64) -> i64 {
+        let noise = self.soil_noise.get_noise(gx as f32, gz as f32) / 2.0 + 0.5;
+        let range = (MAX_SOIL_DEPTH - MIN_SOIL_DEPTH) as f32;
+        MIN_SOIL_DEPTH + (noise * range).round().clamp(0.0, range) as i64
+    }
+
+    // pick a random column in the chunk, and return the tile above its surface if that tile is in
+    // the chunk and the surface is non-beach land that hasn't been carved away by caves
+    fn random_surface_tile<R: Rng>(
+        &self,
+        cc: Vec3<i64>,
+        rng: &mut R,
+        allow_beach: bool,
+    ) -> Option<Vec3<i64>> {
+        let gx = cc.x * CHUNK_EXTENT.x + rng.gen_range(0..CHUNK_EXTENT.x);
+        let gz = cc.z * CHUNK_EXTENT.z + rng.gen_range(0..CHUNK_EXTENT.z);
+        let surface = self.surface_height(gx, gz);
+        let above = Vec3::new(gx, surface + 1, gz);
+        Some(above)
+            .filter(|&above| gtc_get_cc(above) == cc)
+            .filter(|_| allow_beach || surface > SEA_LEVEL + BEACH_HEIGHT)
+            .filter(|_| !self.cave_carver.is_cave(Vec3::new(gx, surface, gz)))
+    }
+
+    // decoration generation stage
+    fn decorate(&self, canvas: &mut DecorationCanvas, cc: Vec3<i64>) {
+        let mut rng = chunk_rng(self.seed, cc, "decoration");
+        for _ in 0..TREE_ATTEMPTS {
+            if let Some(base) = self.random_surface_tile(cc, &mut rng, false) {
+                place_tree(canvas, base, &mut rng);
+            }
+        }
+        if rng.gen_bool(BOULDER_CHANCE) {
+            if let Some(center) = self.random_surface_tile(cc, &mut rng, true) {
+                place_boulder(canvas, center, &mut rng);
+            }
+        }
+    }
+}
+
+impl WorldGenerator for LayeredWorldGenerator {
+    fn generate_chunk(&self, cc: Vec3<i64>, game: &Arc<GameData>) -> GeneratedChunk {
+        let mut chunk_tile_blocks = ChunkBlocks::new(&game.blocks);
+        for z in 0..CHUNK_EXTENT.z {
+            for x in 0..CHUNK_EXTENT.x {
+                let gx = cc.x * CHUNK_EXTENT.x + x;
+                let gz = cc.z * CHUNK_EXTENT.z + z;
+                let surface = self.surface_height(gx, gz);
+                let soil_bottom = surface - self.soil_depth(gx, gz);
+                let beach = surface <= SEA_LEVEL + BEACH_HEIGHT;
+
+                for y in 0..CHUNK_EXTENT.y {
+                    let gy = cc.y * CHUNK_EXTENT.y + y;
+                    let lti = ltc_to_lti(Vec3 { x, y, z });
+                    if gy > surface {
+                        // air
+                    } else if gy == surface && !beach {
+                        chunk_tile_blocks.set(lti, game.content.grass.bid_grass, ());
+                    } else if gy > soil_bottom {
+                        if beach {
+                            chunk_tile_blocks.set(lti, game.content.sand.bid_sand, ());
+                        } else {
+                            chunk_tile_blocks.set(lti, game.content.dirt.bid_dirt, ());
+                        }
+                    } else {
+                        chunk_tile_blocks.set(lti, game.content.stone.bid_stone, ());
+                    }
+                }
+            }
+        }
+        place_ores(self.seed, cc, &mut chunk_tile_blocks, game);
+        self.cave_carver.carve(cc, &mut chunk_tile_blocks, game);
+        let mut canvas = DecorationCanvas::new(cc, &mut chunk_tile_blocks, game);
+        self.decorate(&mut canvas, cc);
+        let spilled = canvas.into_spilled();
+        GeneratedChunk {
+            save_val: ChunkSaveVal {
+                chunk_tile_blocks,
+                steves: Vec::new(),
+                pigs: Vec::new(),
+                falling_blocks: Vec::new(),
+                items: Vec::new(),
+                scheduled_ticks: Vec::new(),
+            },
+            spilled,
+        }
+    }
+
+    fn spawn_pos(&self) -> Vec3<f32> {
+        // prefer spawning on land, searching outwards from the origin along the x axis
+        let gx = (0..1024)
+            .flat_map(|n| [n, -n])
+            .find(|&gx| self.surface_height(gx, 0) > SEA_LEVEL + BEACH_HEIGHT)
+            .unwrap_or(0);
+        Vec3::new(gx as f32 + 0.5, (self.surface_height(gx, 0) + 3) as f32, 0.5)
+    }
+}
@@ -0,0 +1,124 @@
+//! Generating chunks of the world for the first time.
+//!
+//! World generation is pluggable. A world generator is anything implementing the
+//! `WorldGenerator` trait, and the choice of world generator and world seed is made per save file
+//! with `WorldGenSettings`, which is persisted in the save file's world metadata. The chunk loader
+//! calls the world generator whenever it needs a chunk that has never been saved.
+
+pub mod layered;
+pub mod flat;
+pub mod caves;
+pub mod ores;
+pub mod decoration;
+
+use crate::{
+    server::save_content::*,
+    game_data::*,
+    game_binschema::GameBinschema,
+};
+use std::sync::Arc;
+use anyhow::bail;
+use rand_chacha::ChaCha8Rng;
+use rand::prelude::*;
+use vek::*;
+
+
+/// Name of the world generator used if none is specified.
+pub const DEFAULT_WORLD_GENERATOR: &str = "layered";
+
+/// Names of all available world generators.
+pub const WORLD_GENERATORS: &[&str] = &[
+    "layered",
+    "flat",
+];
+
+
+/// Algorithm for generating chunks of the world for the first time.
+///
+/// Will be called concurrently from thread pool threads. Must be deterministic with respect to its
+/// construction parameters and the chunk coordinate, so that chunks generated separately line up
+/// with each other.
+pub trait WorldGenerator: Send + Sync + 'static {
+    /// Generate a chunk of the world for the first time.
+    fn generate_chunk(&self, cc: Vec3<i64>, game: &Arc<GameData>) -> GeneratedChunk;
+
+    /// Choose the position at which new players spawn when creating a new world.
+    fn spawn_pos(&self) -> Vec3<f32>;
+}
+
+
+/// Output of generating a chunk.
+#[derive(Debug)]
+pub struct GeneratedChunk {
+    /// The generated chunk.
+    pub save_val: ChunkSaveVal,
+    /// Blocks placed by features which extended past the edges of the chunk, to be applied to the
+    /// neighboring chunks, keyed by neighbor cc. See `decoration::DecorationCanvas`.
+    pub spilled: Vec<(Vec3<i64>, PendingPlacementsSaveVal)>,
+}
+
+
+/// Choice of world generator and world seed for a save file.
+#[derive(Debug, GameBinschema, Clone, PartialEq, Eq)]
+pub struct WorldGenSettings {
+    /// Name of the world generator, one of `WORLD_GENERATORS`.
+    pub generator: String,
+    /// World seed.
+    pub seed: u64,
+}
+
+impl WorldGenSettings {
+    /// Settings for a new world with the default world generator and a random seed.
+    pub fn new_random() -> Self {
+        WorldGenSettings {
+            generator: DEFAULT_WORLD_GENERATOR.to_owned(),
+            seed: rand::random(),
+        }
+    }
+
+    /// Construct the world generator these settings describe, or error if the generator name is
+    /// not recognized.
+    pub fn world_generator(&self) -> anyhow::Result<Arc<dyn WorldGenerator>> {
+        Ok(match self.generator.as_str() {
+            "layered" => Arc::new(layered::LayeredWorldGenerator::new(self.seed)),
+            "flat" => Arc::new(flat::FlatWorldGenerator),
+            name => bail!(
+                "unknown world generator {:?}, expected one of {:?}",
+                name,
+                WORLD_GENERATORS,
+            ),
+        })
+    }
+}
+
+/// Convert a user-provided seed string to a seed. Numeric strings are used as-is, other strings
+/// are hashed.
+pub fn parse_seed(s: &str) -> u64 {
+    s.parse::<u64>()
+        .or_else(|_| s.parse::<i64>().map(|n| n as u64))
+        .unwrap_or_else(|_| {
+            let hash = hmac_sha256::Hash::hash(s.as_bytes());
+            u64::from_le_bytes(hash[..8].try_into().unwrap())
+        })
+}
+
+/// Derive an independent sub-seed from the world seed, for some named purpose.
+pub fn derive_seed(seed: u64, purpose: &str) -> u64 {
+    let mut hasher = hmac_sha256::Hash::new();
+    hasher.update(seed.to_le_bytes());
+    hasher.update(purpose.as_bytes());
+    let hash = hasher.finalize();
+    u64::from_le_bytes(hash[..8].try_into().unwrap())
+}
+
+/// Construct a random number generator for some named purpose in generating a particular chunk,
+/// deterministically from the world seed and chunk coordinate.
+pub fn chunk_rng(seed: u64, cc: Vec3<i64>, purpose: &str) -> ChaCha8Rng {
+    let mut hasher = hmac_sha256::Hash::new();
+    hasher.update(seed.to_le_bytes());
+    hasher.update(cc.x.to_le_bytes());
+    hasher.update(cc.y.to_le_bytes());
+    hasher.update(cc.z.to_le_bytes());
+    hasher.update(purpose.as_bytes());
+    ChaCha8Rng::from_seed(hasher.finalize())
+}
@@ -0,0 +1,95 @@
+//! Ore vein placement generation stage.
+
+use super::chunk_rng;
+use crate::game_data::*;
+use chunk_data::*;
+use std::{
+    sync::Arc,
+    ops::RangeInclusive,
+};
+use rand::prelude::*;
+use vek::*;
+
+
+/// Distribution of veins of one kind of ore.
+#[derive(Debug, Clone)]
+pub struct OreDistribution {
+    /// The ore block.
+    pub bid: BlockId<()>,
+    /// Number of veins attempted per chunk. Each attempt chooses a random tile in the chunk as the
+    /// start of the vein, and is discarded if that tile is outside of `y_range`.
+    pub veins_per_chunk: u32,
+    /// Range of global Y coordinates in which veins may start.
+    pub y_range: RangeInclusive<i64>,
+    /// Maximum number of tiles in a vein.
+    pub vein_size: u32,
+}
+
+/// The ore distribution table, from most common to rarest.
+pub fn ore_distributions(game: &Arc<GameData>) -> Vec<OreDistribution> {
+    let ores = &game.content.ore;
+    vec![
+        OreDistribution {
+            bid: ores.bid_coal_ore,
+            veins_per_chunk: 20,
+            y_range: -64..=64,
+            vein_size: 16,
+        },
+        OreDistribution {
+            bid: ores.bid_iron_ore,
+            veins_per_chunk: 12,
+            y_range: -64..=32,
+            vein_size: 8,
+        },
+        OreDistribution {
+            bid: ores.bid_redstone_ore,
+            veins_per_chunk: 6,
+            y_range: -64..=8,
+            vein_size: 7,
+        },
+        OreDistribution {
+            bid: ores.bid_gold_ore,
+            veins_per_chunk: 2,
+            y_range: -64..=16,
+            vein_size: 8,
+        },
+        OreDistribution {
+            bid: ores.bid_diamond_ore,
+            veins_per_chunk: 1,
+            y_range: -64..=8,
+            vein_size: 7,
+        },
+    ]
+}
+
+/// Place ore veins in the given chunk by replacing stone, deterministically from the world seed
+/// and chunk coordinate.
+///
+/// Veins are confined to the chunk they start in.
+pub fn place_ores(
+    seed: u64,
+    cc: Vec3<i64>,
+    chunk_tile_blocks: &mut ChunkBlocks,
+    game: &Arc<GameData>,
+) {
+    let mut rng = chunk_rng(seed, cc, "ores");
+    let stone = game.content.stone.bid_stone;
+    for dist in ore_distributions(game) {
+        for _ in 0..dist.veins_per_chunk {
+            let mut ltc = CHUNK_EXTENT.map(|n| rng.gen_range(0..n));
+            if !dist.y_range.contains(&(cc.y * CHUNK_EXTENT.y + ltc.y)) {
+                continue;
+            }
+
+            // random walk from the start tile
+            for _ in 0..dist.vein_size {
+                let lti = ltc_to_lti(ltc);
+                if ChunkBlocks::get(chunk_tile_blocks, lti) == stone {
+                    chunk_tile_blocks.set(lti, dist.bid, ());
+                }
+                let step: Vec3<i64> = FACES.0[rng.gen_range(0..NUM_FACES)].into();
+                ltc = (ltc + step).map2(CHUNK_EXTENT, |n, ext| n.clamp(0, ext - 1));
+            }
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Game logic for item entities, which are stacks of items lying in the world.
+//!
+//! This is a "game logic" module moreso than a "system" module.
+
+use crate::{
+    server::{
+        per_player::*,
+        SyncWorld,
+    },
+    sync_state_entities::{
+        self,
+        ItemEntityState,
+        ChunkEntityEntry,
+    },
+    sync_state_inventory_slots,
+    game_data::GameData,
+    item::ItemStack,
+};
+use chunk_data::*;
+use rand::prelude::*;
+use std::{
+    cmp::min,
+    num::NonZeroU8,
+    sync::Arc,
+};
+use vek::*;
+
+
+/// Number of ticks after an item entity is created or loaded before players can pick it up.
+pub const PICKUP_DELAY: u64 = 10;
+
+/// Number of ticks after an item entity is created or loaded before it despawns.
+pub const DESPAWN_DELAY: u64 = 6000;
+
+/// Distance from a player's position within which they pick up item entities.
+pub const PICKUP_DISTANCE: f32 = 1.5;
+
+/// Distance within which item entities of the same item in the same chunk merge into one.
+pub const MERGE_DISTANCE: f32 = 0.75;
+
+
+/// Create an item entity for the given stack at the given position, tossed upwards in a random
+/// direction. Does nothing if the position's chunk isn't loaded.
+pub fn drop_item(world: &mut SyncWorld, pos: Vec3<f32>, stack: ItemStack) {
+    let cc = (pos / CHUNK_EXTENT.map(|n| n as f32)).map(|n| n.floor() as i64);
+    let Some(ci) = world.getter.get(cc) else { return };
+    let mut rng = thread_rng();
+    let vel = Vec3::new(
+        rng.gen_range(-1.0..1.0),
+        rng.gen_range(2.0..4.0),
+        rng.gen_range(-1.0..1.0),
+    );
+    world.chunk_items.create_entity(
+        cc,
+        ci,
+        ItemEntityState { vel, stack },
+        Default::default(),
+        pos - (cc * CHUNK_EXTENT).map(|n| n as f32),
+    );
+}
+
+/// Give the player the item stack, putting as much of it into their inventory as fits and dropping
+/// the rest at their position.
+pub fn give_item(world: &mut SyncWorld, pk: JoinedPlayerKey, stack: ItemStack) {
+    let game = Arc::clone(&world.sync_ctx.game);
+    if let Some(stack) = pick_up(&mut world.player_inventory_slots, pk, stack, &game) {
+        let pos = world.server_only.player_pos[pk] + Vec3::new(0.0, 1.0, 0.0);
+        drop_item(world, pos, stack);
+    }
+}
+
+/// Called every tick to despawn, merge, let players pick up, and do physics for item entities.
+pub fn tick_item_entities(world: &mut SyncWorld) {
+    let game = Arc::clone(&world.sync_ctx.game);
+    let mut chunk_items = world.chunk_items.iter_move_batch();
+    for (cc, ci) in world.sync_ctx.chunk_mgr.chunks().iter() {
+        let mut items = chunk_items.get(cc, ci);
+        let mut merged_counts = merged_counts(items.as_write().as_ref(), &game).into_iter();
+        while let Some(mut item) = items.next() {
+            let merged_count = merged_counts.next().unwrap();
+
+            // despawn if too old
+            let age = {
+                let mut item = item.as_write();
+                item.extra_mut().age += 1;
+                item.extra().age
+            };
+            if age >= DESPAWN_DELAY {
+                item.delete();
+                continue;
+            }
+
+            // disappear if merged into another item entity
+            let Some(count) = merged_count else {
+                item.delete();
+                continue;
+            };
+            let mut stack = item.as_write().as_ref().state.stack.clone();
+            stack.count = count;
+
+            // be picked up by nearby players
+            let mut remaining = Some(stack);
+            if age >= PICKUP_DELAY {
+                let pos = (cc * CHUNK_EXTENT).map(|n| n as f32) + item.as_write().as_ref().rel_pos;
+                for pk in world.sync_ctx.conn_mgr.players().iter_joined() {
+                    let Some(stack) = remaining.take() else { break };
+                    remaining =
+                        if world.server_only.player_pos[pk].distance(pos) <= PICKUP_DISTANCE {
+                            pick_up(&mut world.player_inventory_slots, pk, stack, &game)
+                        } else { Some(stack) };
+                }
+            }
+            let Some(stack) = remaining else {
+                item.delete();
+                continue;
+            };
+            item.as_write().set_stack(stack);
+
+            // physics
+            let mut rel_pos = item.as_write().as_ref().rel_pos;
+            let mut vel = item.as_write().as_ref().state.vel;
+            sync_state_entities::item_physics(
+                cc,
+                &mut rel_pos,
+                &mut vel,
+                &world.getter,
+                world.tile_blocks.as_ref(),
+                &game,
+            );
+            // freeze rather than fall into a chunk which isn't loaded
+            let rel_cc_after = (rel_pos / CHUNK_EXTENT.map(|n| n as f32)).map(f32::floor);
+            let cc_after = cc + rel_cc_after.map(|n| n as i64);
+            if world.getter.get(cc_after).is_none() {
+                continue;
+            }
+            item.as_write().set_vel(vel);
+            item.set_rel_pos(rel_pos);
+        }
+    }
+}
+
+// for each item entity in a chunk, compute its count after merging nearby item entities of the
+// same item into it, or `None` if it's merged into another. item entities merge into the younger of
+// the two, so that merging never makes items despawn sooner.
+fn merged_counts(
+    items: &[ChunkEntityEntry<ItemEntityState, sync_state_entities::ItemEntityServerState>],
+    game: &GameData,
+) -> Vec<Option<NonZeroU8>> {
+    let mut counts: Vec<Option<NonZeroU8>> = items.iter()
+        .map(|item| Some(item.entity.state.stack.count))
+        .collect();
+    for i in 0..items.len() {
+        for j in i + 1..items.len() {
+            let (Some(count_i), Some(count_j)) = (counts[i], counts[j]) else { continue };
+            let (a, b) = (&items[i].entity, &items[j].entity);
+            let stack_limit = game.items_max_count[a.state.stack.iid];
+            let merged_count = count_i.checked_add(count_j.get())
+                .filter(|&merged_count| merged_count <= stack_limit);
+            if a.state.stack.iid == b.state.stack.iid
+                && a.state.stack.meta == b.state.stack.meta
+                && a.state.stack.damage == b.state.stack.damage
+                && a.rel_pos.distance(b.rel_pos) <= MERGE_DISTANCE
+                && merged_count.is_some()
+            {
+                let (into, from) =
+                    if items[j].extra.age < items[i].extra.age { (j, i) } else { (i, j) };
+                counts[into] = merged_count;
+                counts[from] = None;
+            }
+        }
+    }
+    counts
+}
+
+// put as much of the stack into the player's inventory as fits, first topping up stacks of the
+// same item then filling the first free slots, and return what doesn't fit, if anything
+fn pick_up(
+    player_inventory_slots: &mut sync_state_inventory_slots::SyncWrite,
+    pk: JoinedPlayerKey,
+    mut stack: ItemStack,
+    game: &GameData,
+) -> Option<ItemStack> {
+    let stack_limit = game.items_max_count[stack.iid].get();
+    for fill_empty in [false, true] {
+        for i in 0..36 {
+            let mut slot = player_inventory_slots.get(pk).inventory_slot(i);
+            let new_slot_stack = match slot.reborrow().as_ref() {
+                Some(slot_stack) if !fill_empty
+                    && slot_stack.iid == stack.iid
+                    && slot_stack.meta == stack.meta
+                    && slot_stack.damage == stack.damage
+                    && slot_stack.count.get() < stack_limit
+                => {
+                    let mut slot_stack = slot_stack.clone();
+                    let taken = min(stack_limit - slot_stack.count.get(), stack.count.get());
+                    slot_stack.count = slot_stack.count.checked_add(taken).unwrap();
+                    slot_stack
+                }
+                None if fill_empty => {
+                    let mut slot_stack = stack.clone();
+                    slot_stack.count = min(stack_limit, stack.count.get()).try_into().unwrap();
+                    slot_stack
+                }
+                _ => continue,
+            };
+            let taken = new_slot_stack.count.get()
+                - slot.reborrow().as_ref().map(|slot_stack| slot_stack.count.get()).unwrap_or(0);
+            slot.write(Some(new_slot_stack));
+            stack.count = NonZeroU8::new(stack.count.get() - taken)?;
+        }
+    }
+    Some(stack)
+}
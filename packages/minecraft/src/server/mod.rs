@@ -20,8 +20,13 @@ pub mod save_db;
 pub mod tick_mgr;
 pub mod chunk_mgr;
 pub mod save_mgr;
+pub mod pending_placement_mgr;
+pub mod scheduled_tick_mgr;
 pub mod conn_mgr;
 pub mod process_player_msg;
+pub mod container_sync;
+pub mod item_entities;
+pub mod command;
 pub mod runner;
 
 use self::{
@@ -30,18 +35,24 @@ use self::{
     save_content::*,
     network::{NetworkServer, NetworkEvent},
     chunk_loader::ChunkLoader,
+    generate_chunk::WorldGenSettings,
     player_save_state_loader::PlayerSaveStateLoader,
     tick_mgr::TickMgr,
     chunk_mgr::ChunkMgr,
     save_mgr::SaveMgr,
+    pending_placement_mgr::PendingPlacementMgr,
+    scheduled_tick_mgr::ScheduledTickMgr,
     conn_mgr::ConnMgr,
+    command::{CommandRegistry, Operators},
 };
 use crate::{
     game_data::*,
     thread_pool::ThreadPool,
+    lighting::LightEngine,
     sync_state_tile_blocks,
     sync_state_inventory_slots,
     sync_state_entities::{self, LoadedEntities},
+    block_update_queue::BlockUpdateQueue,
     //sync_state_steve,
 };
 use chunk_data::*;
@@ -75,10 +86,18 @@ pub enum ServerEvent {
         save_key: ChunkSaveKey,
         save_val: ChunkSaveVal,
         saved: bool,
+        /// Placements pending being applied to the chunk read from the save file, keyed by the
+        /// chunk they came from.
+        pending: Vec<(Vec3<i64>, PendingPlacementsSaveVal)>,
+        /// If the chunk was newly generated, placements it spilled into neighboring chunks, keyed
+        /// by neighbor cc.
+        spilled: Vec<(Vec3<i64>, PendingPlacementsSaveVal)>,
     },
     /// A job triggered by the save mgr to save the world to the save file is done and should be
     /// routed back to the save mgr.
     SaveOpDone,
+    /// Run a command typed into the server console.
+    Command(String),
 }
 
 /// Raw server state.
@@ -109,12 +128,24 @@ pub struct ServerOnlyState {
     pub chunk_loader: ChunkLoader,
     /// Services requests to load player save state.
     pub player_save_state_loader: PlayerSaveStateLoader,
+    /// World generator and seed the world was created with.
+    pub world_gen: WorldGenSettings,
+    /// Position at which new players spawn.
+    pub spawn_pos: Vec3<f32>,
+    /// Ticks into the current day night cycle. Clients are sent this upon logging in, then advance
+    /// their own copy in lockstep with ticks if the daylight cycle game rule is on.
+    pub time_of_day: u64,
+    /// Game rules.
+    pub game_rules: GameRules,
+    /// Which players may run operator commands.
+    pub operators: Operators,
 
     pub player_pos: PerJoinedPlayer<Vec3<f32>>,
     pub player_yaw: PerJoinedPlayer<f32>,
     pub player_pitch: PerJoinedPlayer<f32>,
 
     pub player_open_sync_menu: PerJoinedPlayer<Option<process_player_msg::PlayerOpenSyncMenu>>,
+    pub player_dig: PerJoinedPlayer<Option<process_player_msg::PlayerDig>>,
 }
 
 /// State for which `&mut` references get wrapped in auto-syncing wrappers before game logic gets
@@ -122,11 +153,18 @@ pub struct ServerOnlyState {
 /// and/or save file.
 pub struct ServerSyncState {
     pub tile_blocks: PerChunk<ChunkBlocks>,
+    /// Light levels, which are not themselves synced but are kept up to date by the tile blocks
+    /// sync writer.
+    pub tile_light: LightEngine,
     pub player_inventory_slots: PerJoinedPlayer<sync_state_inventory_slots::PlayerInventorySlots>,
     pub chunk_steves: PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::SteveEntityState, sync_state_entities::SteveEntityServerState>>>,
     pub sw_bufs_steves: sync_state_entities::SyncWriteBufs,
     pub chunk_pigs: PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::PigEntityState, sync_state_entities::PigEntityServerState>>>,
     pub sw_bufs_pigs: sync_state_entities::SyncWriteBufs,
+    pub chunk_falling_blocks: PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::FallingBlockEntityState, ()>>>,
+    pub sw_bufs_falling_blocks: sync_state_entities::SyncWriteBufs,
+    pub chunk_items: PerChunk<Vec<sync_state_entities::ChunkEntityEntry<sync_state_entities::ItemEntityState, sync_state_entities::ItemEntityServerState>>>,
+    pub sw_bufs_items: sync_state_entities::SyncWriteBufs,
 }
 
 /// State which game logic gets only shared references to. Often this is because the state is
@@ -141,10 +179,18 @@ pub struct ServerSyncCtx {
     pub chunk_mgr: ChunkMgr,
     /// Manages the save file. See type docs.
     pub save_mgr: SaveMgr,
+    /// Manages world generation placements pending being applied to chunks. See type docs.
+    pub pending_placement_mgr: PendingPlacementMgr,
     /// Manages clients and their joining and leaving. See type docs.
     pub conn_mgr: ConnMgr,
     /// Sync write ctx for entities.
     pub entities: RefCell<LoadedEntities>,
+    /// Tiles pending a block update. See type docs.
+    pub block_updates: RefCell<BlockUpdateQueue>,
+    /// Block ticks scheduled for future ticks. See type docs.
+    pub scheduled_ticks: RefCell<ScheduledTickMgr>,
+    /// Commands which can be run from the console or chat. See type docs.
+    pub commands: CommandRegistry,
 }
 
 /// Projection of `&mut Server` that game logic gets access to. Designed to automatically keep
@@ -164,6 +210,8 @@ pub struct SyncWorld<'a> {
     pub player_inventory_slots: sync_state_inventory_slots::SyncWrite<'a>,
     pub chunk_steves: sync_state_entities::SyncWrite<'a, sync_state_entities::SteveEntityState, sync_state_entities::SteveEntityServerState, sync_state_entities::SyncWriteSteveLogic>,
     pub chunk_pigs: sync_state_entities::SyncWrite<'a, sync_state_entities::PigEntityState, sync_state_entities::PigEntityServerState, sync_state_entities::SyncWritePigLogic>,
+    pub chunk_falling_blocks: sync_state_entities::SyncWrite<'a, sync_state_entities::FallingBlockEntityState, (), sync_state_entities::SyncWriteFallingBlockLogic>,
+    pub chunk_items: sync_state_entities::SyncWrite<'a, sync_state_entities::ItemEntityState, sync_state_entities::ItemEntityServerState, sync_state_entities::SyncWriteItemLogic>,
 }
 
 
@@ -174,6 +222,17 @@ impl Server {
     }
 }
 
+impl ServerSyncCtx {
+    /// Schedule the given tile to receive a block update after the given number of ticks. A delay
+    /// of 0 is treated as 1.
+    pub fn schedule_tick(&self, tile: TileKey, delay: u64) {
+        let tick = self.tick_mgr.tick_num() + delay.max(1);
+        if self.scheduled_ticks.borrow_mut().schedule(tile, tick) {
+            self.save_mgr.mark_chunk_unsaved(tile.cc, tile.ci);
+        }
+    }
+}
+
 impl<'a> SyncWorld<'a> {
     /// Construct manually (with respect to synchronization logic).
     pub fn new_manual(
@@ -183,11 +242,16 @@ impl<'a> SyncWorld<'a> {
     ) -> Self {
         let &mut ServerSyncState {
             ref mut tile_blocks,
+            ref mut tile_light,
             ref mut player_inventory_slots,
             ref mut chunk_steves,
             ref mut sw_bufs_steves,
             ref mut chunk_pigs,
             ref mut sw_bufs_pigs,
+            ref mut chunk_falling_blocks,
+            ref mut sw_bufs_falling_blocks,
+            ref mut chunk_items,
+            ref mut sw_bufs_items,
             //ref mut steves,
         } = sync_state;
         SyncWorld {
@@ -196,10 +260,12 @@ impl<'a> SyncWorld<'a> {
 
             getter: sync_ctx.chunk_mgr.chunks().getter(),
 
-            tile_blocks: sync_state_tile_blocks::SyncWrite::new_manual(sync_ctx, tile_blocks),
+            tile_blocks: sync_state_tile_blocks::SyncWrite::new_manual(sync_ctx, tile_blocks, tile_light),
             player_inventory_slots: sync_state_inventory_slots::SyncWrite::new_manual(sync_ctx, player_inventory_slots),
             chunk_steves: sync_state_entities::SyncWrite::new_manual(sync_ctx, chunk_steves, sw_bufs_steves),
             chunk_pigs: sync_state_entities::SyncWrite::new_manual(sync_ctx, chunk_pigs, sw_bufs_pigs),
+            chunk_falling_blocks: sync_state_entities::SyncWrite::new_manual(sync_ctx, chunk_falling_blocks, sw_bufs_falling_blocks),
+            chunk_items: sync_state_entities::SyncWrite::new_manual(sync_ctx, chunk_items, sw_bufs_items),
             //steves: sync_state_steve::SyncWrite::new_manual(sync_ctx, steves),
         }
     }
@@ -120,6 +120,12 @@ impl Connection {
     pub(super) fn kill(&self) {
         kill(&self.alive, true, Some("connection closed by server"));
     }
+
+    // see outer type
+    pub(super) fn close(&self, reason: &str) {
+        self.send(DownMsg::Close(DownMsgClose { reason: reason.to_owned() }));
+        kill(&self.alive, true, None);
+    }
 }
 
 impl InMemClient {
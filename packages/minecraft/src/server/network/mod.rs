@@ -7,6 +7,7 @@ use crate::{
         ServerEvent,
     },
     util_time::ServerRelTime,
+    util_net::cert_fingerprint,
     message::*,
 };
 #[cfg(feature = "client")]
@@ -15,6 +16,8 @@ use std::{
     sync::Arc,
     fmt::Debug,
     time::Instant,
+    path::Path,
+    fs,
 };
 use parking_lot::Mutex;
 use slab::Slab;
@@ -22,6 +25,12 @@ use tokio::{
     runtime::Handle,
     task::AbortHandle,
 };
+use native_tls::{
+    Identity,
+    TlsAcceptor,
+    Certificate,
+};
+use anyhow::Context;
 
 mod send_buffer_policy_enforcer;
 mod ws;
@@ -93,6 +102,13 @@ enum ConnectionInner {
     InMem(in_mem::Connection),
 }
 
+/// Certificate and private key with which the server encrypts connections with TLS.
+#[derive(Clone)]
+pub struct TlsIdentity {
+    acceptor: TlsAcceptor,
+    fingerprint: String,
+}
+
 /// Some discrete network input event happened. Goes to the conn mgr for processing.
 #[derive(Debug)]
 pub enum NetworkEvent {
@@ -128,13 +144,37 @@ impl NetworkServer {
     }
 }
 
+impl TlsIdentity {
+    /// Load from PEM files containing the certificate, possibly followed by the rest of its chain,
+    /// and its PKCS #8 private key.
+    pub fn load<C: AsRef<Path>, K: AsRef<Path>>(cert_path: C, key_path: K) -> anyhow::Result<Self> {
+        let cert_pem = fs::read(cert_path).context("failed to read TLS certificate file")?;
+        let key_pem = fs::read(key_path).context("failed to read TLS private key file")?;
+        let identity = Identity::from_pkcs8(&cert_pem, &key_pem)
+            .context("invalid TLS certificate or private key")?;
+        let cert_der = Certificate::from_pem(&cert_pem)
+            .and_then(|cert| cert.to_der())
+            .context("invalid TLS certificate")?;
+        Ok(TlsIdentity {
+            acceptor: TlsAcceptor::new(identity).context("failed to set up TLS")?,
+            fingerprint: cert_fingerprint(&cert_der),
+        })
+    }
+
+    /// Fingerprint of the certificate, which clients may pin. See `util_net::cert_fingerprint`.
+    pub fn fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+}
+
 impl NetworkServerHandle {
-    /// Bind to a port and open the network server to connections on that port.
-    pub fn bind<B>(&self, bind_to: B, rt: &Handle, game: &Arc<GameData>)
+    /// Bind to a port and open the network server to connections on that port, encrypted with TLS
+    /// if an identity is given.
+    pub fn bind<B>(&self, bind_to: B, tls: Option<TlsIdentity>, rt: &Handle, game: &Arc<GameData>)
     where
         B: ToSocketAddrs + Debug + Send + Sync + 'static,
     {
-        ws::bind(&self.0, bind_to, rt, game);
+        ws::bind(&self.0, bind_to, tls, rt, game);
     }
 
     /// Construct a new in-memory client. See `InMemClient`. This directly causes a single add
@@ -178,6 +218,16 @@ impl Connection {
         }
     }
 
+    /// Fingerprint of the TLS certificate the connection is encrypted with, if it is. See
+    /// `util_net::cert_fingerprint`.
+    pub fn tls_fingerprint(&self) -> Option<&str> {
+        match self.0 {
+            ConnectionInner::Ws(ref inner) => inner.tls_fingerprint(),
+            #[cfg(feature = "client")]
+            ConnectionInner::InMem(_) => None,
+        }
+    }
+
     /// Relativize an instant against `server_t0`.
     ///
     /// Resultant `ServerRelTime` suitable for transmitting on this connection. Should not be
@@ -209,6 +259,20 @@ impl Connection {
             &ConnectionInner::InMem(ref inner) => inner.kill(),
         }
     }
+
+    /// Tell the client why the connection is being closed, then kill and disconnect it.
+    ///
+    /// Like `kill`, this never blocks or errors, will trigger a `NetworkEvent::RemoveConnection`
+    /// event soon, and causes subsequent calls to `send` to be silently ignored. Unlike `kill`,
+    /// messages already enqueued are transmitted, followed by a `DownMsg::Close` with the given
+    /// reason, before the underlying transport is closed--unless that takes too long.
+    pub fn close(&self, reason: &str) {
+        match self.0 {
+            ConnectionInner::Ws(ref inner) => inner.close(reason),
+            #[cfg(feature = "client")]
+            ConnectionInner::InMem(ref inner) => inner.close(reason),
+        }
+    }
 }
 
 impl Drop for NetworkServer {
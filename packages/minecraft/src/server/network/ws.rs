@@ -1,8 +1,9 @@
 //! Network connection implementation for websocket transport.
 //!
-//! This is a websocket connection running over TCP. Encryption is not yet enabled. Only binary
-//! messages are used, not text ones. When the connection begins, after the ws handshake completes,
-//! the "ws-binschema" handshake is performed as such:
+//! This is a websocket connection running over TCP, encrypted with TLS if the server was bound with
+//! a `TlsIdentity`, in which case clients connect with `wss://` urls. Only binary messages are
+//! used, not text ones. When the connection begins, after the ws handshake completes, the
+//! "ws-binschema" handshake is performed as such:
 //!
 //! 1. This first part establishes a shared and synchronized clock between the client and server
 //!    and thus both parties should process and transmit the messages involved in this part with
@@ -45,8 +46,9 @@
 //!
 //! The bind task accepts TCP streams, and then spawns a new receive task for each stream. The
 //! receive task is the only task that generates network events for the connection. The receive
-//! task does the ws handshake, creates the connection, and spawns the send task. Once the send
-//! task is spawned it is the only task that sends data on the stream.
+//! task does the TLS handshake if enabled, does the ws handshake, creates the connection, and
+//! spawns the send task. Once the send task is spawned it is the only task that sends data on the
+//! stream.
 //!
 //! The receive task has associated with it a shared `Notify` that is used to put it into the
 //! shutdown state. This can be called from the connection handle if killed, from the network
@@ -58,6 +60,11 @@
 //! Additionally, a bound-length channel is shared between the receive and send tasks for the
 //! receive task to convey to the send task pong messages it should send in response to ping
 //! messages.
+//!
+//! Closing the connection with a reason, either from the connection handle or from the receive
+//! task upon the client violating the protocol, enqueues a close message and uses a third shared
+//! `Notify` to tell the send task to stop once it has transmitted it. The send task then tells the
+//! receive task to shut down as usual. If transmitting it takes too long, the send task gives up.
 
 use super::{
     send_buffer_policy_enforcer::SendBufferPolicyEnforcer,
@@ -116,8 +123,10 @@ use tokio_tungstenite::{
         Message,
     },
     WebSocketStream,
+    MaybeTlsStream,
     accept_async_with_config,
 };
+use tokio_native_tls::TlsAcceptor;
 use futures::{
     stream::{Stream, StreamExt},
     sink::{Sink, SinkExt},
@@ -126,8 +135,7 @@ use futures::{
 };
 use anyhow::{
     Error,
-    bail,
-    ensure,
+    anyhow,
 };
 
 
@@ -162,6 +170,10 @@ const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(60);
 // timeout for attempting to send a close frame on a websocket connection.
 const SEND_CLOSE_TIMEOUT: Duration = Duration::from_secs(10);
 
+// timeout for transmitting enqueued messages up to and including a close down msg before giving up
+// and closing the websocket connection anyways.
+const SEND_CLOSE_MSG_TIMEOUT: Duration = Duration::from_secs(10);
+
 // number of simultaneous ping pong messages the server will buffer for sending back in a response
 // before backpressure is triggered on receive from the websocket connection.
 const PING_PONG_BUFFER_LIMIT: usize = 10;
@@ -170,6 +182,10 @@ const PING_PONG_BUFFER_LIMIT: usize = 10;
 // ==== types ====
 
 
+// websocket stream over TCP, possibly with TLS
+type WsStream = WebSocketStream<MaybeTlsStream<TcpStream>>;
+
+
 // slab entry inner type for websocket connections.
 pub(super) struct SlabEntry {
     conn_shared: Arc<WsConnShared>,
@@ -183,6 +199,8 @@ pub(super) struct Connection {
     send_send: UnboundedSender<DownMsg>,
     // time synchronized with the client for communication of realtime instants
     server_t0: Instant,
+    // fingerprint of the TLS certificate, if TLS is enabled
+    tls_fingerprint: Option<String>,
 }
 
 // general module-level shared context
@@ -197,6 +215,10 @@ struct WsShared {
     up_handshake: Vec<u8>,
     // downwards-travelling ws-binschema handshake message to transmit
     down_handshake: Vec<u8>,
+    // TLS acceptor to encrypt connections with, if TLS is enabled
+    tls_acceptor: Option<TlsAcceptor>,
+    // fingerprint of the TLS certificate, if TLS is enabled
+    tls_fingerprint: Option<String>,
     // handle to the tokio runtime for spawning tasks
     rt: Handle,
     // game content
@@ -211,6 +233,9 @@ struct WsConnShared {
     // tell the send task to send a close message if it can, then terminate. only called from the
     // receive task.
     shutdown_send: Notify,
+    // tell the send task that a close down msg has been enqueued, after which it should stop, but
+    // not to wait more than a timeout for that. can be called from anywhere.
+    closing: Notify,
     // enforces send buffer policies
     sbpe: SendBufferPolicyEnforcer,
     // supplementary debug info
@@ -246,10 +271,26 @@ impl Connection {
         self.server_t0
     }
 
+    // see outer type
+    pub(super) fn tls_fingerprint(&self) -> Option<&str> {
+        self.tls_fingerprint.as_deref()
+    }
+
     // see outer type
     pub(super) fn kill(&self) {
         self.conn_shared.shutdown_recv.notify_one();
     }
+
+    // see outer type
+    pub(super) fn close(&self, reason: &str) {
+        close(&self.conn_shared, &self.send_send, reason);
+    }
+}
+
+// enqueue a close down msg and tell the send task to stop after transmitting it
+fn close(conn_shared: &WsConnShared, send_send: &UnboundedSender<DownMsg>, reason: &str) {
+    let _ = send_send.send(DownMsg::Close(DownMsgClose { reason: reason.to_owned() }));
+    conn_shared.closing.notify_one();
 }
 
 
@@ -260,6 +301,7 @@ impl Connection {
 pub(super) fn bind<B>(
     ns_shared: &Arc<NetworkServerSharedState>,
     bind_to: B,
+    tls: Option<TlsIdentity>,
     rt: &Handle,
     game: &Arc<GameData>,
 )
@@ -277,6 +319,7 @@ where
     let join_accept = rt.spawn(accept_task(
         Arc::clone(&ns_shared),
         bind_to,
+        tls,
         rt.clone(),
         Arc::clone(game),
     ));
@@ -289,6 +332,7 @@ where
 async fn accept_task<B: ToSocketAddrs + Debug>(
     ns_shared: Arc<NetworkServerSharedState>,
     bind_to: B,
+    tls: Option<TlsIdentity>,
     rt: Handle,
     game: Arc<GameData>,
 ) {
@@ -304,6 +348,8 @@ async fn accept_task<B: ToSocketAddrs + Debug>(
         down_schema,
         up_handshake,
         down_handshake,
+        tls_fingerprint: tls.as_ref().map(|tls| tls.fingerprint.clone()),
+        tls_acceptor: tls.map(|tls| tls.acceptor.into()),
         rt,
         game,
     });
@@ -344,7 +390,11 @@ async fn try_accept_task_inner<B: ToSocketAddrs + Debug>(
 ) -> Result<Infallible, Error> {
     // TCP bind
     let listener = TcpListener::bind(bind_to).await?;
-    info!("bound to {:?}", bind_to);
+    if ws_shared.tls_acceptor.is_some() {
+        info!("bound to {:?} with TLS", bind_to);
+    } else {
+        info!("bound to {:?}", bind_to);
+    }
 
     // accept connections
     loop {
@@ -389,8 +439,9 @@ async fn recv_task(ws_shared: Arc<WsShared>, tcp: TcpStream) {
     let slab_entry = super::SlabEntry::Ws(SlabEntry { conn_shared: Arc::clone(&conn_shared) });
     let connection = super::Connection(ConnectionInner::Ws(Connection {
         conn_shared: Arc::clone(&conn_shared),
-        send_send,
+        send_send: send_send.clone(),
         server_t0,
+        tls_fingerprint: ws_shared.tls_fingerprint.clone(),
     }));
     let conn_idx = create_conn(&ws_shared.ns_shared, slab_entry, connection);
     let conn_idx = match conn_idx {
@@ -424,7 +475,18 @@ async fn recv_task(ws_shared: Arc<WsShared>, tcp: TcpStream) {
                 Err(e) => e,
                 Ok(never) => match never {},
             };
-            trace!(%e, "receive task errored (closing connection)");
+            match e {
+                RecvLoopError::Ws(e) => {
+                    trace!(%e, "receive task errored (closing connection)");
+                }
+                RecvLoopError::Protocol(e) => {
+                    warn!(%e, "client protocol error, closing connection");
+                    // let the send task finish telling the client why
+                    close(&conn_shared, &send_send, &format!("protocol violation: {}", e));
+                    destroy_conn(&ws_shared.ns_shared, conn_idx);
+                    return;
+                }
+            }
         }
     }
 
@@ -440,7 +502,7 @@ async fn recv_loop<W: Stream<Item=Result<Message, TungsteniteError>> + Unpin>(
     mut ws_recv: W,
     send_pong: Sender<Vec<u8>>,
     conn_idx: usize,
-) -> Result<Infallible, Error> {
+) -> Result<Infallible, RecvLoopError> {
     // allocate state
     let mut coder_state_alloc = CoderStateAlloc::new();
     let backpressure_semaphore = Arc::new(Semaphore::new(RECEIVE_BUFFER_LIMIT));
@@ -449,8 +511,8 @@ async fn recv_loop<W: Stream<Item=Result<Message, TungsteniteError>> + Unpin>(
     loop {
         let msg = match ws_recv.next().await {
             Some(Ok(msg)) => msg,
-            Some(Err(e)) => return Err(e.into()),
-            None => bail!("ws connection closed"),
+            Some(Err(e)) => return Err(RecvLoopError::Ws(e.into())),
+            None => return Err(RecvLoopError::Ws(anyhow!("ws connection closed"))),
         };
 
         // extract binary message or early escape this loop iteration
@@ -461,8 +523,8 @@ async fn recv_loop<W: Stream<Item=Result<Message, TungsteniteError>> + Unpin>(
                 let _ = send_pong.send(msg).await;
                 continue;
             }
-            Message::Close(_) => bail!("received close ws msg"),
-            _ => bail!("received invalid ws msg type"),
+            Message::Close(_) => return Err(RecvLoopError::Ws(anyhow!("received close ws msg"))),
+            _ => return Err(RecvLoopError::Protocol(anyhow!("received invalid ws msg type"))),
         };
         let msg_size = msg.len();
 
@@ -481,16 +543,15 @@ async fn recv_loop<W: Stream<Item=Result<Message, TungsteniteError>> + Unpin>(
                 error!(%e, "decoding error detected as being programmer's fault");
             }
         }
-        let msg = result?;
-        coder_state.is_finished_or_err()?;
+        let msg = result.map_err(|e| RecvLoopError::Protocol(e.into()))?;
+        coder_state.is_finished_or_err().map_err(|e| RecvLoopError::Protocol(e.into()))?;
         coder_state_alloc = coder_state.into_alloc();
-        ensure!(
-            cursor.position() >= cursor.get_ref().len() as u64,
-            "received msg with extra bytes at end",
-        );
+        if cursor.position() < cursor.get_ref().len() as u64 {
+            return Err(RecvLoopError::Protocol(anyhow!("received msg with extra bytes at end")));
+        }
 
         // send buffer policies
-        conn_shared.sbpe.post_receive(&msg)?;
+        conn_shared.sbpe.post_receive(&msg).map_err(RecvLoopError::Protocol)?;
 
         // receive backpressure
         // unwrap safety: we never close the semaphore
@@ -508,6 +569,15 @@ async fn recv_loop<W: Stream<Item=Result<Message, TungsteniteError>> + Unpin>(
 }
 
 
+// specific error type for receive loop
+enum RecvLoopError {
+    // error in the underlying websocket transport, or it being closed. just drop the connection.
+    Ws(Error),
+    // client violated the protocol. try to tell it why before closing the connection.
+    Protocol(Error),
+}
+
+
 // ==== sending ====
 
 
@@ -526,6 +596,11 @@ async fn send_task<W: Sink<Message, Error=TungsteniteError> + Unpin>(
             trace!("send task shutting down because shut down requested");
             true
         }
+        _ = close_timeout(&conn_shared).fuse() => {
+            trace!("send task shutting down because transmitting close msg timed out");
+            conn_shared.shutdown_recv.notify_one();
+            true
+        }
         result = send_loop.fuse() => {
             let e = match result {
                 Ok(never) => match never {},
@@ -540,6 +615,10 @@ async fn send_task<W: Sink<Message, Error=TungsteniteError> + Unpin>(
                     trace!(%e, "send task error (closing connection)");
                     true
                 }
+                SendLoopError::Closed => {
+                    trace!("send task transmitted close msg (closing connection)");
+                    true
+                }
             };
             // if the send task is triggering the shutdown, tell the receive task to shut down
             conn_shared.shutdown_recv.notify_one();
@@ -595,15 +674,29 @@ async fn send_loop<W: Sink<Message, Error=TungsteniteError> + Unpin>(
         // send and flush
         ws_send.send(Message::Binary(buf)).await.map_err(SendLoopError::Ws)?;
         ws_send.flush().await.map_err(SendLoopError::Ws)?;
+
+        // nothing is sent after a close msg
+        if let DownMsg::Close(_) = msg {
+            return Err(SendLoopError::Closed);
+        }
     }
 }
 
+// resolves once a close down msg has been enqueued and then the timeout for transmitting it has
+// elapsed
+async fn close_timeout(conn_shared: &WsConnShared) {
+    conn_shared.closing.notified().await;
+    sleep(SEND_CLOSE_MSG_TIMEOUT).await;
+}
+
 // specific error type for send loop
 enum SendLoopError {
     // error in the underlying websocket transport. just drop the connection.
     Ws(TungsteniteError),
     // higher level error. try to properly close the connection.
     WsBinschema(Error),
+    // a close down msg was transmitted. properly close the connection.
+    Closed,
 }
 
 
@@ -629,17 +722,23 @@ fn form_handshake_msg(send_schema: &Schema, recv_schema: &Schema) -> Vec<u8> {
     buf
 }
 
-// attempt to do a ws handshake then a ws-binschema handshake on the TCP stream. on error, attempt
-// to handle the error appropriately. implement timeouts as necessary in both parts.
+// attempt to do a TLS handshake if enabled, then a ws handshake, then a ws-binschema handshake on
+// the TCP stream. on error, attempt to handle the error appropriately. implement timeouts as
+// necessary in all parts.
 async fn try_handshake_handle_err(
     tcp: TcpStream,
     ws_shared: &Arc<WsShared>,
-) -> Option<(WebSocketStream<TcpStream>, Instant)> {
+) -> Option<(WsStream, Instant)> {
     let deadline = TokioInstant::now() + HANDSHAKE_TIMEOUT;
     let result = try_handshake(tcp, deadline, ws_shared).await;
     match result {
         // success
         Ok(outcome) => Some(outcome),
+        Err(HandshakeError::Tls(e)) => {
+            // just log these ones
+            trace!(%e, "TLS handshake error");
+            None
+        }
         Err(HandshakeError::Ws(e)) => {
             // just log these ones
             trace!(%e, "ws-level handshake error");
@@ -660,16 +759,26 @@ async fn try_handshake_handle_err(
     }
 }
 
-// attempt to do a ws handshake then a ws-binschema handshake on the TCP stream with the given
-// timeout instant.
+// attempt to do a TLS handshake if enabled, then a ws handshake, then a ws-binschema handshake on
+// the TCP stream with the given timeout instant.
 async fn try_handshake(
     tcp: TcpStream,
     deadline: TokioInstant,
     ws_shared: &Arc<WsShared>,
-) -> Result<(WebSocketStream<TcpStream>, Instant), HandshakeError> {
+) -> Result<(WsStream, Instant), HandshakeError> {
+    // try to do the TLS handshake if enabled, with timeout
+    let stream = match ws_shared.tls_acceptor {
+        Some(ref tls_acceptor) => match timeout_at(deadline, tls_acceptor.accept(tcp)).await {
+            Ok(Ok(tls)) => MaybeTlsStream::NativeTls(tls),
+            Ok(Err(e)) => return Err(HandshakeError::Tls(e)),
+            Err(_) => return Err(HandshakeError::Timeout(None)),
+        },
+        None => MaybeTlsStream::Plain(tcp),
+    };
+
     // try to do the websocket handshake, with timeout
     let ws_task = accept_async_with_config(
-        tcp,
+        stream,
         Some(WebSocketConfig {
             max_message_size: Some(RECEIVE_BUFFER_LIMIT),
             ..Default::default()
@@ -716,9 +825,9 @@ async fn try_handshake(
 
 // attempt to receive a binary ws message within the ws-binschema handshake
 async fn handshake_recv(
-    mut ws: WebSocketStream<TcpStream>,
+    mut ws: WsStream,
     deadline: TokioInstant,
-) -> Result<(Vec<u8>, WebSocketStream<TcpStream>), HandshakeError>
+) -> Result<(Vec<u8>, WsStream), HandshakeError>
 {
     Ok(loop {
         // receive message with timeout 
@@ -759,9 +868,9 @@ async fn handshake_recv(
 // attempt to send a binary ws message within the ws-binschema handshake
 async fn handshake_send(
     msg: Vec<u8>,
-    mut ws: WebSocketStream<TcpStream>,
+    mut ws: WsStream,
     deadline: TokioInstant,
-) -> Result<WebSocketStream<TcpStream>, HandshakeError>
+) -> Result<WsStream, HandshakeError>
 {
     let send_task = ws.send(Message::Binary(msg));
     match timeout_at(deadline, send_task).await {
@@ -773,16 +882,18 @@ async fn handshake_send(
 
 // ways a handshake can fail
 enum HandshakeError {
+    // error in the TLS handshake. just drop the connection.
+    Tls(native_tls::Error),
     // error in the underlying websocket transport. just drop the connection.
     Ws(TungsteniteError),
     // error in the ws-binschema handshake--try to properly close the websocket connection.
     WsBinschema {
-        ws: WebSocketStream<TcpStream>,
+        ws: WsStream,
         // string to be transmitted to client in close frame
         reason: &'static str,
     },
     // handshake timeout reached. try to properly close websocket connection if exists.
-    Timeout(Option<WebSocketStream<TcpStream>>),
+    Timeout(Option<WsStream>),
 }
 
 impl Debug for Connection {
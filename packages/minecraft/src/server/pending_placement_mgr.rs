@@ -0,0 +1,114 @@
+//! See `PendingPlacementMgr`.
+
+use crate::{
+    game_data::GameData,
+    server::save_content::*,
+};
+use chunk_data::*;
+use std::{
+    sync::Arc,
+    collections::{HashMap, HashSet},
+};
+use vek::*;
+
+
+/// Manages blocks which world generation placed into neighboring chunks which were not loaded at
+/// the time, pending being applied to those chunks when they are next loaded or generated.
+///
+/// These are persisted in the save file as `PendingPlacements` entries, which the chunk loader
+/// reads when loading a chunk. This holds pending placements produced in this session in memory
+/// until they are either applied or flushed to the save file, so that they are not missed if a
+/// chunk's loading is already underway when they are produced.
+#[derive(Default)]
+pub struct PendingPlacementMgr {
+    // for each chunk, for each neighboring chunk, placements produced by that neighbor in this
+    // session which haven't been applied to the chunk or flushed to the save file yet
+    pending: HashMap<Vec3<i64>, HashMap<Vec3<i64>, PendingPlacementsSaveVal>>,
+    // entries of pending whose current value was included in a save operation
+    saved: HashSet<PendingPlacementsSaveKey>,
+}
+
+impl PendingPlacementMgr {
+    /// Construct.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Call upon a generated chunk spilling placements into a neighboring chunk which is not
+    /// loaded. Caller should mark the corresponding save file entry as unsaved.
+    pub fn add(&mut self, cc: Vec3<i64>, from_cc: Vec3<i64>, save_val: PendingPlacementsSaveVal) {
+        self.pending.entry(cc).or_default().insert(from_cc, save_val);
+        self.saved.remove(&PendingPlacementsSaveKey { cc, from_cc });
+    }
+
+    /// Call upon a chunk being added to the world, with the placements pending for it which were
+    /// read from the save file. Removes and returns all placements pending for it, keyed by the
+    /// chunk they came from. Caller should apply them to the chunk, and clear the corresponding
+    /// save file entries.
+    pub fn take(
+        &mut self,
+        cc: Vec3<i64>,
+        from_save_file: Vec<(Vec3<i64>, PendingPlacementsSaveVal)>,
+    ) -> Vec<(Vec3<i64>, PendingPlacementsSaveVal)> {
+        let mut pending = self.pending.remove(&cc).unwrap_or_default();
+        for &from_cc in pending.keys() {
+            self.saved.remove(&PendingPlacementsSaveKey { cc, from_cc });
+        }
+        for (from_cc, save_val) in from_save_file {
+            // entries from this session are at least as new as the save file's
+            pending.entry(from_cc).or_insert(save_val);
+        }
+        pending.into_iter().collect()
+    }
+
+    /// Get the placements pending for a chunk from a neighboring chunk, for saving. If none, the
+    /// save file entry should be cleared.
+    pub fn get(&self, cc: Vec3<i64>, from_cc: Vec3<i64>) -> Option<&PendingPlacementsSaveVal> {
+        self.pending.get(&cc).and_then(|pending| pending.get(&from_cc))
+    }
+
+    /// Call upon the placements pending for a chunk from a neighboring chunk being included in a
+    /// save operation.
+    pub fn on_saved(&mut self, cc: Vec3<i64>, from_cc: Vec3<i64>) {
+        if self.get(cc, from_cc).is_some() {
+            self.saved.insert(PendingPlacementsSaveKey { cc, from_cc });
+        }
+    }
+
+    /// Call upon a save operation completing. Drops from memory the placements which have been
+    /// flushed to the save file since they were last changed, except those pending for chunks
+    /// which are currently loading, as the chunk loader may have read the save file before the
+    /// save operation completed.
+    pub fn on_save_op_done(&mut self, is_loading: impl Fn(Vec3<i64>) -> bool) {
+        let pending = &mut self.pending;
+        self.saved.retain(|&PendingPlacementsSaveKey { cc, from_cc }| {
+            if is_loading(cc) {
+                return true;
+            }
+            if let Some(chunk_pending) = pending.get_mut(&cc) {
+                chunk_pending.remove(&from_cc);
+                if chunk_pending.is_empty() {
+                    pending.remove(&cc);
+                }
+            }
+            false
+        });
+    }
+}
+
+/// Apply pending placements to a chunk's blocks directly. Only places blocks over blocks which can
+/// be placed over. Returns whether any blocks were placed.
+pub fn apply_pending_placements(
+    chunk_tile_blocks: &mut ChunkBlocks,
+    save_val: PendingPlacementsSaveVal,
+    game: &Arc<GameData>,
+) -> bool {
+    let mut changed = false;
+    for PendingPlacement { lti, bid_meta } in save_val.placements {
+        if game.blocks_can_place_over[ChunkBlocks::get(chunk_tile_blocks, lti)] {
+            chunk_tile_blocks.erased_set(lti, bid_meta);
+            changed = true;
+        }
+    }
+    changed
+}
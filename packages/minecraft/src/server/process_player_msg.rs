@@ -5,14 +5,30 @@
 use crate::{
     server::{
         per_player::*,
+        item_entities::drop_item,
+        container_sync::*,
+        command::*,
         SyncWorld,
     },
     message::*,
-    sync_state_inventory_slots,
-    sync_state_entities::SteveEntityState,
+    sync_state_entities::{SteveEntityState, FALLING_BLOCK_SIZE},
+    sync_state_steve::{STEVE_WIDTH, STEVE_HEIGHT},
+    game_data::{
+        place_logic::ItemPlaceLogic,
+        use_logic::BlockUseLogic,
+        dig_logic::{dig_ticks, can_harvest},
+    },
+    item::{ItemStack, RawItemId},
+    physics::aa_box::AaBox,
+    util_usize_lt::UsizeLt,
 };
 use chunk_data::*;
-use std::cmp::min;
+use std::{
+    cmp::min,
+    num::NonZeroU8,
+    sync::Arc,
+};
+use vek::*;
 
 
 /// Per-player optional state for tracking sync menu they have open.
@@ -24,17 +40,50 @@ pub struct PlayerOpenSyncMenu {
     pub menu: PlayerMsgOpenSyncMenu,
     /// The up msg index that opened the sync menu.
     pub up_msg_idx: u64,
+    /// For container menus, the container's properties as last sent to the player.
+    pub sent_properties: Vec<u16>,
 }
 
 
+/// Distance from a player's position beyond which they can't have a block's menu open.
+pub const MENU_REACH: f32 = 8.0;
+
+/// Distance from a player's position to a tile's center beyond which they can't dig or place a
+/// block in it.
+pub const BLOCK_REACH: f32 = 8.0;
+
+/// Number of ticks sooner than expected a player may finish digging a block, to tolerate network
+/// jitter.
+pub const DIG_TICK_LENIENCY: u64 = 3;
+
+
+/// Per-player optional state for tracking the block they're digging.
+#[derive(Debug, Copy, Clone)]
+pub struct PlayerDig {
+    /// Tile being dug.
+    pub gtc: Vec3<i64>,
+    /// Hotbar slot of the item being dug with.
+    pub hotbar_slot: usize,
+    /// Item being dug with, which must still be in the hotbar slot upon finishing digging.
+    pub iid: Option<RawItemId>,
+    /// Tick number on which digging started.
+    pub start_tick: u64,
+}
+
 /// Process a player message from a joined player.
 pub fn process_player_msg(world: &mut SyncWorld, pk: JoinedPlayerKey, msg: PlayerMsg) {
     match msg {
         PlayerMsg::SetCharState(inner) => inner.process(world, pk),
-        PlayerMsg::SetTileBlock(inner) => inner.process(world, pk),
+        PlayerMsg::StartDig(inner) => inner.process(world, pk),
+        PlayerMsg::CancelDig(inner) => inner.process(world, pk),
+        PlayerMsg::FinishDig(inner) => inner.process(world, pk),
+        PlayerMsg::PlaceBlock(inner) => inner.process(world, pk),
+        PlayerMsg::UseBlock(inner) => inner.process(world, pk),
         PlayerMsg::OpenSyncMenu(inner) => inner.process(world, pk),
         PlayerMsg::CloseSyncMenu(inner) => inner.process(world, pk),
         PlayerMsg::SyncMenuMsg(inner) => inner.process(world, pk),
+        PlayerMsg::Chat(inner) => inner.process(world, pk),
+        PlayerMsg::TabComplete(inner) => inner.process(world, pk),
         PlayerMsg::SpawnSteve(pos) => {
             let cc = (pos / CHUNK_EXTENT.map(|n| n as f32)).map(|n| n.floor() as i64);
             let ci = world.getter.get(cc).expect("TODO");
@@ -80,46 +129,244 @@ impl Process for PlayerMsgSetCharState {
         world.server_only.player_pos[pk] = pos;
         world.server_only.player_yaw[pk] = yaw;
         world.server_only.player_pitch[pk] = pitch;
+        broadcast_char_state(world, pk);
+    }
+}
+
+/// Send all players the player's position and direction.
+pub fn broadcast_char_state(world: &SyncWorld, pk: JoinedPlayerKey) {
+    for pk2 in world.sync_ctx.conn_mgr.players().iter() {
+        world.sync_ctx.conn_mgr.send(pk2, DownMsg::PreJoin(
+            PreJoinDownMsg::SetPlayerCharState {
+                player_idx: DownPlayerIdx(
+                    world.sync_ctx.conn_mgr.player_to_clientside(pk, pk2)
+                ),
+                pos: world.server_only.player_pos[pk],
+                yaw: world.server_only.player_yaw[pk],
+                pitch: world.server_only.player_pitch[pk],
+            }
+        ));
+    }
+}
+
+impl Process for PlayerMsgStartDig {
+    // start digging
+    fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
+        let PlayerMsgStartDig { gtc, hotbar_slot } = self;
+        if !in_block_reach(world, pk, gtc) {
+            world.server_only.player_dig[pk] = None;
+            return;
+        }
+        let hotbar_slot = hotbar_slot.get();
+        let iid = world.player_inventory_slots.as_ref()[pk].inventory_slots[hotbar_slot]
+            .as_ref()
+            .map(|stack| stack.iid);
+        let start_tick = world.sync_ctx.tick_mgr.tick_num();
+        world.server_only.player_dig[pk] = Some(PlayerDig { gtc, hotbar_slot, iid, start_tick });
+    }
+}
 
-        for pk2 in world.sync_ctx.conn_mgr.players().iter() {
-            world.sync_ctx.conn_mgr.send(pk2, DownMsg::PreJoin(
-                PreJoinDownMsg::SetPlayerCharState {
-                    player_idx: DownPlayerIdx(
-                        world.sync_ctx.conn_mgr.player_to_clientside(pk, pk2)
-                    ),
-                    pos,
-                    yaw,
-                    pitch,
+impl Process for PlayerMsgCancelDig {
+    // cancel digging
+    fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
+        let PlayerMsgCancelDig = self;
+        world.server_only.player_dig[pk] = None;
+    }
+}
+
+impl Process for PlayerMsgFinishDig {
+    // finish digging, if enough time has passed
+    fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
+        let PlayerMsgFinishDig = self;
+        let Some(dig) = world.server_only.player_dig[pk].take() else { return };
+        let game = Arc::clone(&world.sync_ctx.game);
+
+        // validate that the player can still reach the tile
+        if !in_block_reach(world, pk, dig.gtc) {
+            return;
+        }
+
+        // validate that the block can be dug with the item digging started with this quickly
+        let Some(tile) = world.getter.gtc_get(dig.gtc) else { return };
+        let bid = tile.get(world.tile_blocks.as_ref()).get();
+        let mut slot = world.player_inventory_slots.get(pk).inventory_slot(dig.hotbar_slot);
+        let stack = slot.reborrow().as_ref().cloned();
+        if stack.as_ref().map(|stack| stack.iid) != dig.iid {
+            return;
+        }
+        let Some(ticks) = dig_ticks(&game, bid, dig.iid) else { return };
+        let elapsed = world.sync_ctx.tick_mgr.tick_num() - dig.start_tick;
+        if elapsed + DIG_TICK_LENIENCY < ticks {
+            return;
+        }
+
+        // wear down the tool, break the block dropping any items in it, and drop its item if
+        // harvested
+        let harvested = can_harvest(&game, bid, dig.iid);
+        if let Some(mut stack) = stack.filter(|stack| game.items_tool[stack.iid].is_some()) {
+            stack.damage += 1;
+            if stack.damage > game.items_max_damage[stack.iid] {
+                slot.write(None);
+            } else {
+                slot.write(Some(stack));
+            }
+        }
+        if let Some(container) = game.blocks_container_logic[bid].as_deref() {
+            for stack in container.dropped_on_break(tile.get(world.tile_blocks.as_ref())) {
+                drop_item(world, dig.gtc.map(|n| n as f32) + 0.5, stack);
+            }
+        }
+        tile.get(&mut world.tile_blocks).erased_set(ErasedBidMeta::new(AIR, ()));
+        if harvested {
+            if let Some(dropped) = game.blocks_drop_logic[bid].dropped(&game) {
+                drop_item(world, dig.gtc.map(|n| n as f32) + 0.5, dropped);
+            }
+        }
+    }
+}
+
+impl Process for PlayerMsgPlaceBlock {
+    // place a block from a hotbar slot
+    fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
+        let PlayerMsgPlaceBlock { gtc, hotbar_slot } = self;
+        let game = Arc::clone(&world.sync_ctx.game);
+
+        // validate that the tile can be placed over
+        let Some(tile) = world.getter.gtc_get(gtc) else { return };
+        if !game.blocks_can_place_over[tile.get(world.tile_blocks.as_ref()).get()] {
+            return;
+        }
+
+        // validate that the slot has an item which can be placed and the player can reach the tile
+        let slots = &world.player_inventory_slots.as_ref()[pk].inventory_slots;
+        let Some(mut stack) = slots[hotbar_slot.get()].clone() else { return };
+        if !in_block_reach(world, pk, gtc) {
+            return;
+        }
+
+        // place it, unless a block would be placed inside anything
+        match game.items_place_logic[stack.iid] {
+            ItemPlaceLogic::Nothing => return,
+            ItemPlaceLogic::Block(ref bid_meta) => {
+                let boxes = game.blocks_physics_logic[bid_meta.bid].boxes(&bid_meta.meta);
+                if block_obstructed(world, gtc, boxes) {
+                    return;
                 }
-            ));
+                tile.get(&mut world.tile_blocks).erased_set(game.clone_erased_tile_block(bid_meta));
+            }
+            ItemPlaceLogic::Custom(place) => if !place(world, gtc) {
+                return;
+            },
+        }
+
+        // consume one of the item
+        let mut slot = world.player_inventory_slots.get(pk).inventory_slot(hotbar_slot.get());
+        match NonZeroU8::new(stack.count.get() - 1) {
+            Some(count) => {
+                stack.count = count;
+                slot.write(Some(stack));
+            }
+            None => slot.write(None),
         }
     }
 }
 
-impl Process for PlayerMsgSetTileBlock {
-    // set tile block
-    fn process(self, world: &mut SyncWorld, _pk: JoinedPlayerKey) {
-        let PlayerMsgSetTileBlock { gtc, bid_meta } = self;
+impl Process for PlayerMsgUseBlock {
+    // use the block at a tile
+    fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
+        let PlayerMsgUseBlock { gtc } = self;
+
+        // validate that the tile is loaded and the player can reach it
+        let Some(tile) = world.getter.gtc_get(gtc) else { return };
+        if !in_block_reach(world, pk, gtc) {
+            return;
+        }
+
+        let bid = tile.get(world.tile_blocks.as_ref()).get();
+        if let BlockUseLogic::Custom(use_block) = world.sync_ctx.game.blocks_use_logic[bid] {
+            use_block(world, tile);
+        }
+    }
+}
+
+// whether the tile is close enough to the player for them to dig or place a block in it
+fn in_block_reach(world: &SyncWorld, pk: JoinedPlayerKey, gtc: Vec3<i64>) -> bool {
+    world.server_only.player_pos[pk].distance(gtc.map(|n| n as f32) + 0.5) <= BLOCK_REACH
+}
+
+/// Whether a block with the given collision boxes placed in the tile would intersect with any
+/// player or entity with a collision box.
+pub fn block_obstructed(world: &SyncWorld, gtc: Vec3<i64>, boxes: &[AaBox]) -> bool {
+    if boxes.is_empty() {
+        return false;
+    }
+    let intersects = |aa_box: AaBox| boxes.iter()
+        .any(|&box2| box2.translate(gtc.map(|n| n as f32)).intersects(aa_box));
+    // bottom-centered box of the given size at the given position
+    let entity_box = |pos: Vec3<f32>, ext: Extent3<f32>| AaBox {
+        pos: pos - Vec3::new(ext.w / 2.0, 0.0, ext.d / 2.0),
+        ext,
+    };
+    let steve_ext = Extent3::new(STEVE_WIDTH, STEVE_HEIGHT, STEVE_WIDTH);
+
+    // players
+    for pk in world.sync_ctx.conn_mgr.players().iter_joined() {
+        if intersects(entity_box(world.server_only.player_pos[pk], steve_ext)) {
+            return true;
+        }
+    }
 
-        if let Some(tile) = world.getter.gtc_get(gtc) {
-            tile.get(&mut world.tile_blocks).erased_set(bid_meta);
+    // entities, which may be in the tile's chunk or a neighboring chunk
+    let cc = gtc_get_cc(gtc);
+    for z in -1..=1 {
+        for y in -1..=1 {
+            for x in -1..=1 {
+                let cc2 = cc + Vec3 { x, y, z };
+                let Some(ci2) = world.getter.get(cc2) else { continue };
+                let chunk_pos = (cc2 * CHUNK_EXTENT).map(|n| n as f32);
+                let steves = world.chunk_steves.as_ref().get(cc2, ci2).iter()
+                    .map(|entry| entity_box(chunk_pos + entry.entity.rel_pos, steve_ext));
+                let falling_blocks = world.chunk_falling_blocks.as_ref().get(cc2, ci2).iter()
+                    .map(|entry| entity_box(
+                        chunk_pos + entry.entity.rel_pos,
+                        Extent3::from(FALLING_BLOCK_SIZE),
+                    ));
+                if steves.chain(falling_blocks).any(intersects) {
+                    return true;
+                }
+            }
         }
     }
+    false
 }
 
 impl Process for PlayerMsgOpenSyncMenu {
     // open a sync menu
     fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
-        let valid = match &self {
-            PlayerMsgOpenSyncMenu::Inventory => true,
-        };
+        drop_crafting_slots(world, pk);
+        let valid = sync_menu_valid(world, pk, self);
         let up_msg_idx = world.sync_ctx.conn_mgr.last_processed(pk);
-        world.server_only.player_open_sync_menu[pk] =
-            Some(PlayerOpenSyncMenu { valid, menu: self, up_msg_idx });
+        world.server_only.player_open_sync_menu[pk] = Some(PlayerOpenSyncMenu {
+            valid,
+            menu: self,
+            up_msg_idx,
+            sent_properties: Vec::new(),
+        });
         if !valid {
             world.sync_ctx.conn_mgr.send(pk, DownMsg::PostJoin(
                 PostJoinDownMsg::InvalidateSyncMenu { up_msg_idx }
             ));
+        } else if let PlayerMsgOpenSyncMenu::Container(gtc) = self {
+            // the client's copy of the container's contents may be out of date, since container
+            // slot changes are only sent to clients with the container open
+            let (tile, container) = container_at(world, gtc).unwrap();
+            let slots = container.slots(tile.get(world.tile_blocks.as_ref()));
+            for (idx, slot_content) in slots.iter().enumerate() {
+                world.sync_ctx.conn_mgr.send(pk, DownMsg::PostJoin(PostJoinDownMsg::SetItemSlot {
+                    item_slot: DownItemSlotRef::Container { gtc, idx: UsizeLt::new(idx) },
+                    slot_content: slot_content.clone(),
+                }));
+            }
         }
     }
 }
@@ -128,29 +375,172 @@ impl Process for PlayerMsgCloseSyncMenu {
     // close the open sync menu
     fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
         let PlayerMsgCloseSyncMenu = self;
+        drop_crafting_slots(world, pk);
         world.server_only.player_open_sync_menu[pk] = None;
     }
 }
 
+impl Process for PlayerMsgChat {
+    // say something in chat, or run a command if it starts with a slash
+    fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
+        let PlayerMsgChat { text } = self;
+        if let Some(line) = text.strip_prefix('/') {
+            run_command(world, CommandSender::Player(pk), line);
+        } else {
+            let conn_mgr = &world.sync_ctx.conn_mgr;
+            conn_mgr.broadcast_chat_line(&format!("<{}> {}", conn_mgr.player_username(pk), text));
+        }
+    }
+}
+
+impl Process for PlayerMsgTabComplete {
+    // complete a command, or a username if not typing a command
+    fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
+        let PlayerMsgTabComplete { text } = self;
+        let completions = if let Some(line) = text.strip_prefix('/') {
+            complete_command(world, CommandSender::Player(pk), line)
+        } else {
+            let last = text.rsplit(char::is_whitespace).next().unwrap();
+            let mut usernames = online_usernames(world);
+            usernames.retain(|username| username.starts_with(last));
+            usernames.sort();
+            usernames
+        };
+        world.sync_ctx.conn_mgr.send(pk, DownMsg::PostJoin(PostJoinDownMsg::TabCompletions {
+            text,
+            completions,
+        }));
+    }
+}
+
+/// Toss the items in the player's crafting grid out into the world, as happens when they close
+/// a menu with a crafting grid.
+pub fn drop_crafting_slots(world: &mut SyncWorld, pk: JoinedPlayerKey) {
+    let pos = world.server_only.player_pos[pk] + Vec3::new(0.0, 1.0, 0.0);
+    for i in 0..9 {
+        let mut slot = world.player_inventory_slots.get(pk).crafting_slot(i);
+        let Some(stack) = slot.reborrow().as_ref().cloned() else { continue };
+        slot.write(None);
+        drop_item(world, pos, stack);
+    }
+}
+
+/// Called every tick to invalidate open sync menus which can no longer be used, such as for
+/// containers which have been broken or which the player has moved too far away from.
+pub fn invalidate_sync_menus(world: &mut SyncWorld) {
+    let sync_ctx = world.sync_ctx;
+    for pk in sync_ctx.conn_mgr.players().iter_joined() {
+        let Some(open) = world.server_only.player_open_sync_menu[pk].as_ref() else { continue };
+        if open.valid && !sync_menu_valid(world, pk, open.menu) {
+            let up_msg_idx = open.up_msg_idx;
+            world.server_only.player_open_sync_menu[pk].as_mut().unwrap().valid = false;
+            sync_ctx.conn_mgr.send(pk, DownMsg::PostJoin(
+                PostJoinDownMsg::InvalidateSyncMenu { up_msg_idx }
+            ));
+        }
+    }
+}
+
+// whether the player may have the given sync menu open
+fn sync_menu_valid(world: &SyncWorld, pk: JoinedPlayerKey, menu: PlayerMsgOpenSyncMenu) -> bool {
+    match menu {
+        PlayerMsgOpenSyncMenu::Inventory => true,
+        PlayerMsgOpenSyncMenu::Container(gtc) =>
+            container_at(world, gtc).is_some() && in_menu_reach(world, pk, gtc),
+    }
+}
+
+// whether the tile is close enough to the player for them to have its menu open
+fn in_menu_reach(world: &SyncWorld, pk: JoinedPlayerKey, gtc: Vec3<i64>) -> bool {
+    world.server_only.player_pos[pk].distance(gtc.map(|n| n as f32) + 0.5) <= MENU_REACH
+}
+
+// width of the crafting grid of the menu the player has open, if they have one open with a
+// crafting grid and the server hasn't invalidated it
+fn open_crafting_grid_width(world: &SyncWorld, pk: JoinedPlayerKey) -> Option<usize> {
+    let open = world.server_only.player_open_sync_menu[pk].as_ref().filter(|open| open.valid)?;
+    match open.menu {
+        PlayerMsgOpenSyncMenu::Inventory => Some(2),
+        PlayerMsgOpenSyncMenu::Container(gtc) => container_at(world, gtc)?.1.crafting_grid_width(),
+    }
+}
+
 impl Process for SyncMenuMsg {
     // branching for sync menu messages
     fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
         match self {
             SyncMenuMsg::TransferItems(inner) => inner.process(world, pk),
             SyncMenuMsg::SwapItemSlots(inner) => inner.process(world, pk),
+            SyncMenuMsg::Craft(inner) => inner.process(world, pk),
         }
     }
 }
 
-fn resolve_slot<'a>(
+// whether the item slot ref refers to a slot the player can currently access
+fn slot_valid(item_slot: UpItemSlotRef, world: &SyncWorld, pk: JoinedPlayerKey) -> bool {
+    match item_slot {
+        UpItemSlotRef::Inventory(_) | UpItemSlotRef::Held => true,
+        UpItemSlotRef::Container(idx) => open_container(world, pk)
+            .and_then(|gtc| container_at(world, gtc))
+            .is_some_and(|(tile, container)|
+                idx.get() < container.slots(tile.get(world.tile_blocks.as_ref())).len()),
+        UpItemSlotRef::Crafting(idx) => open_crafting_grid_width(world, pk)
+            .is_some_and(|width| idx.get() < width * width),
+    }
+}
+
+// whether the player may put the given items into the item slot. assumes the slot ref is valid.
+fn can_put(
     item_slot: UpItemSlotRef,
-    world: &'a mut SyncWorld,
+    stack: &ItemStack,
+    world: &SyncWorld,
     pk: JoinedPlayerKey,
-) -> sync_state_inventory_slots::SyncWriteSlot<'a> {
+) -> bool {
+    match item_slot {
+        UpItemSlotRef::Inventory(_) | UpItemSlotRef::Held | UpItemSlotRef::Crafting(_) => true,
+        UpItemSlotRef::Container(idx) => open_container(world, pk)
+            .and_then(|gtc| container_at(world, gtc))
+            .is_some_and(|(_, container)|
+                container.can_put(idx.get(), stack, &world.sync_ctx.game)),
+    }
+}
+
+// read the content of an item slot. assumes the slot ref is valid.
+fn read_slot(
+    item_slot: UpItemSlotRef,
+    world: &SyncWorld,
+    pk: JoinedPlayerKey,
+) -> Option<ItemStack> {
     match item_slot {
         UpItemSlotRef::Inventory(idx) =>
-            world.player_inventory_slots.get(pk).inventory_slot(idx.get()),
-        UpItemSlotRef::Held => world.player_inventory_slots.get(pk).held_slot()
+            idx.idx(&world.player_inventory_slots.as_ref()[pk].inventory_slots).clone(),
+        UpItemSlotRef::Held => world.player_inventory_slots.as_ref()[pk].held_slot.clone(),
+        UpItemSlotRef::Container(idx) => open_container(world, pk)
+            .and_then(|gtc| container_at(world, gtc))
+            .and_then(|(tile, container)|
+                container.slots(tile.get(world.tile_blocks.as_ref()))[idx.get()].clone()),
+        UpItemSlotRef::Crafting(idx) =>
+            idx.idx(&world.player_inventory_slots.as_ref()[pk].crafting_slots).clone(),
+    }
+}
+
+// set the content of an item slot. assumes the slot ref is valid.
+fn write_slot(
+    item_slot: UpItemSlotRef,
+    content: Option<ItemStack>,
+    world: &mut SyncWorld,
+    pk: JoinedPlayerKey,
+) {
+    match item_slot {
+        UpItemSlotRef::Inventory(idx) =>
+            world.player_inventory_slots.get(pk).inventory_slot(idx.get()).write(content),
+        UpItemSlotRef::Held => world.player_inventory_slots.get(pk).held_slot().write(content),
+        UpItemSlotRef::Container(idx) => {
+            let Some(gtc) = open_container(world, pk) else { return };
+            write_container_slot(world, gtc, idx, content);
+        }
+        UpItemSlotRef::Crafting(idx) =>
+            world.player_inventory_slots.get(pk).crafting_slot(idx.get()).write(content),
     }
 }
 
@@ -158,62 +548,53 @@ impl Process for SyncMenuMsgTransferItems {
     // transfer items from one item slot to another
     fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
         let SyncMenuMsgTransferItems { from, to, amount } = self;
-        // clone the from slot, early-return if from slot is empty
-        let mut from_stack_clone = match resolve_slot(from, world, pk).as_ref() {
-            Some(from_stack_ref) => from_stack_ref.clone(),
-            None => return,
-        };
+        if !slot_valid(from, world, pk) || !slot_valid(to, world, pk) {
+            return;
+        }
+        // clone the from slot, early-return if from slot is empty or can't be put in the to slot
+        let Some(mut from_stack) = read_slot(from, world, pk) else { return };
+        if !can_put(to, &from_stack, world, pk) {
+            return;
+        }
         // compute how much we transfer over
-        let stack_limit = world.sync_ctx.game.items_max_count[from_stack_clone.iid].get();
-        let mut to_stack_write = resolve_slot(to, world, pk);
-        let to_can_take = to_stack_write.reborrow().as_ref()
-            .map(|to_stack_ref|
-                if to_stack_ref.iid == from_stack_clone.iid
-                    && to_stack_ref.meta == from_stack_clone.meta
-                    && to_stack_ref.damage == from_stack_clone.damage
+        let stack_limit = world.sync_ctx.game.items_max_count[from_stack.iid].get();
+        let to_content = read_slot(to, world, pk);
+        let to_can_take = to_content.as_ref()
+            .map(|to_stack|
+                if to_stack.iid == from_stack.iid
+                    && to_stack.meta == from_stack.meta
+                    && to_stack.damage == from_stack.damage
                 {
-                    stack_limit.saturating_sub(to_stack_ref.count.get())
+                    stack_limit.saturating_sub(to_stack.count.get())
                 } else { 0 }
             )
             .unwrap_or(stack_limit);
         if to_can_take > 0 {
-            let amount_transfer = min(amount, min(to_can_take, from_stack_clone.count.get()));
-            let from_final_amount = from_stack_clone.count.get() - amount_transfer;
-
-            // do the movement now
-            if let Some(mut to_stack_clone) = to_stack_write.reborrow().as_ref().cloned() {
-                // case where they both start non-0 count
-                // change to slot count
-                to_stack_clone.count = (to_stack_clone.count.get() + amount_transfer)
-                    .try_into().unwrap();
-                to_stack_write.write(Some(to_stack_clone));
-
-                let mut from_stack_write = resolve_slot(from, world, pk);
-                if from_final_amount > 0 {
-                    // change from slot count
-                    from_stack_clone.count = from_final_amount.try_into().unwrap();
-                    from_stack_write.write(Some(from_stack_clone));
-                } else {
-                    // from slot drops to 0
-                    from_stack_write.write(None);
+            let amount_transfer = min(amount, min(to_can_take, from_stack.count.get()));
+            let from_final_amount = from_stack.count.get() - amount_transfer;
+
+            // change to slot content, either adding to its count or splitting off from the from
+            // slot to fill it
+            let to_stack = match to_content {
+                Some(mut to_stack) => {
+                    to_stack.count = (to_stack.count.get() + amount_transfer).try_into().unwrap();
+                    to_stack
                 }
-            } else {
-                if from_final_amount > 0 {
-                    // case where some but not all of from slot is splitting off to fill empty to
-                    let mut from_stack_clone_2 = from_stack_clone.clone();
-                    from_stack_clone_2.count = amount_transfer.try_into().unwrap();
-                    to_stack_write.write(Some(from_stack_clone_2));
-
-                    from_stack_clone.count = from_final_amount.try_into().unwrap();
-                    resolve_slot(from, world, pk).write(Some(from_stack_clone));
-                } else {
-                    // case where the entirety of from slot is moving to to slot
-                    debug_assert_eq!(from_stack_clone.count.get(), amount_transfer);
-                    to_stack_write.write(Some(from_stack_clone));
-
-                    resolve_slot(from, world, pk).write(None);
+                None => {
+                    let mut to_stack = from_stack.clone();
+                    to_stack.count = amount_transfer.try_into().unwrap();
+                    to_stack
                 }
-            }
+            };
+            write_slot(to, Some(to_stack), world, pk);
+
+            // change from slot content, possibly dropping to 0
+            let from_content = NonZeroU8::new(from_final_amount)
+                .map(|count| {
+                    from_stack.count = count;
+                    from_stack
+                });
+            write_slot(from, from_content, world, pk);
         }
     }
 }
@@ -222,10 +603,59 @@ impl Process for SyncMenuMsgSwapItemSlots {
     // swap item slots content
     fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
         let SyncMenuMsgSwapItemSlots([a, b]) = self;
-        let a_clone = resolve_slot(a, world, pk).as_ref().cloned();
-        let mut b_write = resolve_slot(b, world, pk);
-        let b_clone = b_write.reborrow().as_ref().cloned();
-        b_write.write(a_clone);
-        resolve_slot(a, world, pk).write(b_clone);
+        if !slot_valid(a, world, pk) || !slot_valid(b, world, pk) {
+            return;
+        }
+        let a_content = read_slot(a, world, pk);
+        let b_content = read_slot(b, world, pk);
+        if a_content.as_ref().is_some_and(|stack| !can_put(b, stack, world, pk))
+            || b_content.as_ref().is_some_and(|stack| !can_put(a, stack, world, pk))
+        {
+            return;
+        }
+        write_slot(b, a_content, world, pk);
+        write_slot(a, b_content, world, pk);
+    }
+}
+
+impl Process for SyncMenuMsgCraft {
+    // craft once, if the crafting grid matches a recipe and the output fits in the held slot
+    fn process(self, world: &mut SyncWorld, pk: JoinedPlayerKey) {
+        let SyncMenuMsgCraft = self;
+        let Some(width) = open_crafting_grid_width(world, pk) else { return };
+        let game = &world.sync_ctx.game;
+        let slots = &world.player_inventory_slots.as_ref()[pk];
+        let grid = &slots.crafting_slots[..width * width];
+        let Some(output) = game.recipes.craft(grid, width) else { return };
+
+        // add the output to the held slot
+        let held = match slots.held_slot.clone() {
+            None => output,
+            Some(mut held_stack) => {
+                let count = held_stack.count.get() as u16 + output.count.get() as u16;
+                if held_stack.iid != output.iid
+                    || held_stack.meta != output.meta
+                    || held_stack.damage != output.damage
+                    || count > game.items_max_count[output.iid].get() as u16
+                {
+                    return;
+                }
+                held_stack.count = (count as u8).try_into().unwrap();
+                held_stack
+            }
+        };
+        world.player_inventory_slots.get(pk).held_slot().write(Some(held));
+
+        // consume one of each ingredient
+        for i in 0..width * width {
+            let mut slot = world.player_inventory_slots.get(pk).crafting_slot(i);
+            let Some(mut stack) = slot.reborrow().as_ref().cloned() else { continue };
+            let content = NonZeroU8::new(stack.count.get() - 1)
+                .map(|count| {
+                    stack.count = count;
+                    stack
+                });
+            slot.write(content);
+        }
     }
 }
@@ -3,32 +3,80 @@
 //! This is the top-level integration layer betweens server modules.
 
 use crate::{
-    game_data::*,
+    game_data::{
+        *,
+        update_logic::{BlockUpdateLogic, BlockUpdateCause},
+        random_tick_logic::BlockRandomTickLogic,
+    },
     server::{
         channel::*,
         network::*,
         save_content::*,
         save_db::SaveDb,
+        generate_chunk::{WorldGenerator, WorldGenSettings, chunk_rng},
         save_mgr::{SaveMgr, ShouldSave},
+        pending_placement_mgr::apply_pending_placements,
         conn_mgr::ConnMgrEffect,
         chunk_mgr::ChunkMgrEffect,
-        tick_mgr::TICK,
-        process_player_msg::process_player_msg,
+        tick_mgr::{TICK, TickMgr},
+        process_player_msg::{process_player_msg, invalidate_sync_menus},
+        container_sync::sync_container_properties,
+        item_entities::{tick_item_entities, drop_item},
+        command::{Operators, CommandSender, builtin::builtin_commands, run_command},
         *,
     },
     message::*,
+    item::ItemStack,
     thread_pool::ThreadPool,
     util_must_drain::MustDrain,
     util_array::*,
     sync_state_entities,
+    day_night::DAY_TICKS,
 };
 use std::{
     sync::Arc,
     thread,
+    mem::take,
 };
+use anyhow::*;
+use rand_chacha::ChaCha8Rng;
+use rand::prelude::*;
 use vek::*;
 
 
+/// World metadata loaded from the save file, or initialized for a new world, along with the world
+/// generator it specifies.
+pub struct WorldMeta {
+    /// The world metadata.
+    pub save_val: WorldSaveVal,
+    /// The world generator specified by the world metadata.
+    pub world_generator: Arc<dyn WorldGenerator>,
+}
+
+impl WorldMeta {
+    /// Load the world metadata from the save file. If the save file is new, initialize it with the
+    /// given world generator settings, which are otherwise ignored.
+    pub fn load(save_db: &SaveDb, new_world_gen: WorldGenSettings) -> Result<Self> {
+        Ok(if let Some(save_val) = save_db.clone().read(WorldSaveKey)? {
+            info!(world_gen=?save_val.world_gen, "loaded existing world");
+            let world_generator = save_val.world_gen.world_generator()?;
+            WorldMeta { save_val, world_generator }
+        } else {
+            info!(world_gen=?new_world_gen, "creating new world");
+            let world_generator = new_world_gen.world_generator()?;
+            let save_val = WorldSaveVal {
+                spawn_pos: world_generator.spawn_pos(),
+                world_gen: new_world_gen,
+                tick: 0,
+                time_of_day: 0,
+                game_rules: Default::default(),
+            };
+            WorldMeta { save_val, world_generator }
+        })
+    }
+}
+
+
 /// Owned handle to a running server thread. Stops server when dropped.
 pub struct ServerThread {
     // for sending events to the server loop
@@ -39,14 +87,29 @@ pub struct ServerThread {
 
 impl ServerThread {
     /// Start a server in a new thread. Does _not_ bind.
-    pub fn start(thread_pool: ThreadPool, save_db: SaveDb, game: Arc<GameData>) -> Self {
+    pub fn start(
+        thread_pool: ThreadPool,
+        save_db: SaveDb,
+        world_meta: WorldMeta,
+        game: Arc<GameData>,
+    ) -> Self {
         let (server_send, server_recv) = channel();
         let network_server = NetworkServer::new(server_send.clone());
         let network_handle = network_server.handle().clone();
         thread::spawn({
             let server_send = server_send.clone();
             let server_recv = server_recv.clone();
-            move || run(server_send, server_recv, thread_pool, network_server, save_db, game)
+            move || run(
+                server_send,
+                server_recv,
+                thread_pool,
+                network_server,
+                save_db,
+                world_meta,
+                game,
+                // the internal server is only reachable by its own client's player
+                Operators::All,
+            )
         });
         ServerThread { server_send, network_handle }
     }
@@ -65,15 +128,22 @@ impl Drop for ServerThread {
 
 
 /// Run the server in this thread until it exits. Does _not_ bind.
+#[allow(clippy::too_many_arguments)]
 pub fn run(
     server_send: ServerSender,
     server_recv: ServerReceiver,
     thread_pool: ThreadPool,
     network_server: NetworkServer,
     save_db: SaveDb,
+    world_meta: WorldMeta,
     game: Arc<GameData>,
+    operators: Operators,
 ) {
     // construct
+    let WorldMeta {
+        save_val: WorldSaveVal { world_gen, spawn_pos, tick, time_of_day, game_rules },
+        world_generator,
+    } = world_meta;
     let mut server = Server {
         server_only: ServerOnlyState {
             server_send: server_send.clone(),
@@ -82,6 +152,7 @@ pub fn run(
             network_server,
             chunk_loader: ChunkLoader::new(
                 Arc::clone(&game),
+                world_generator,
                 server_send.clone(),
                 thread_pool.clone(),
                 save_db.clone(),
@@ -91,26 +162,41 @@ pub fn run(
                 thread_pool.clone(),
                 save_db.clone(),
             ),
+            world_gen,
+            spawn_pos,
+            time_of_day,
+            game_rules,
+            operators,
             player_pos: Default::default(),
             player_yaw: Default::default(),
             player_pitch: Default::default(),
             player_open_sync_menu: Default::default(),
+            player_dig: Default::default(),
         },
         sync_ctx: ServerSyncCtx {
             game,
-            tick_mgr: Default::default(),
+            tick_mgr: TickMgr::new(tick),
             chunk_mgr: Default::default(),
             save_mgr: SaveMgr::new(server_send, save_db, thread_pool),
+            pending_placement_mgr: Default::default(),
             conn_mgr: Default::default(),
             entities: Default::default(),
+            block_updates: Default::default(),
+            scheduled_ticks: Default::default(),
+            commands: builtin_commands(),
         },
         sync_state: ServerSyncState {
             tile_blocks: Default::default(),
+            tile_light: Default::default(),
             player_inventory_slots: Default::default(),
             chunk_steves: Default::default(),
             sw_bufs_steves: Default::default(),
             chunk_pigs: Default::default(),
             sw_bufs_pigs: Default::default(),
+            chunk_falling_blocks: Default::default(),
+            sw_bufs_falling_blocks: Default::default(),
+            chunk_items: Default::default(),
+            sw_bufs_items: Default::default(),
         },
     };
 
@@ -124,6 +210,7 @@ pub fn run(
         // do tick
         do_tick(&mut server);
         let skip_next = server.sync_ctx.tick_mgr.on_tick_done();
+        server.sync_ctx.save_mgr.mark_world_unsaved();
         for pk in server.sync_ctx.conn_mgr.players().iter() {
             server.sync_ctx.conn_mgr.send(pk, DownMsg::PreJoin(PreJoinDownMsg::TickDone {
                 next_tick_num: server.sync_ctx.tick_mgr.tick_num(),
@@ -152,6 +239,9 @@ pub fn run(
                 ServerEvent::Network(event) => {
                     let MustDrain = server.sync_ctx.conn_mgr.handle_network_event(event);
                     process_conn_mgr_effects(&mut server);
+                    // kick players the event caused to be kicked, now that it's safe to
+                    let MustDrain = server.sync_ctx.conn_mgr.kick_requested();
+                    process_conn_mgr_effects(&mut server);
                 }
                 // player save state ready
                 ServerEvent::PlayerSaveStateReady { pk, save_val } => {
@@ -160,18 +250,32 @@ pub fn run(
                     process_conn_mgr_effects(&mut server);
                 }
                 // chunk ready
-                ServerEvent::ChunkReady { save_key, save_val, saved } => {
+                ServerEvent::ChunkReady { save_key, mut save_val, saved, pending, spilled } => {
+                    let applied = take_pending_placements(&mut server, save_key, &mut save_val, pending);
                     let MustDrain = server.sync_ctx.chunk_mgr.on_chunk_ready(
                         save_key,
                         save_val,
-                        saved,
+                        saved && !applied,
                         server.sync_ctx.conn_mgr.players(),
                     );
                     process_chunk_mgr_effects(&mut server);
+                    place_spilled_placements(&mut server, save_key.cc, spilled);
                 }
                 // save operation done
                 ServerEvent::SaveOpDone => {
                     server.sync_ctx.save_mgr.on_save_op_done(server.sync_ctx.tick_mgr.tick_num());
+                    let chunk_mgr = &server.sync_ctx.chunk_mgr;
+                    server.sync_ctx.pending_placement_mgr
+                        .on_save_op_done(|cc| chunk_mgr.is_loading(cc));
+                }
+                // command typed into the console
+                ServerEvent::Command(line) => {
+                    let line = line.trim().trim_start_matches('/');
+                    if !line.is_empty() {
+                        run_command(&mut server.as_sync_world(), CommandSender::Console, line);
+                        let MustDrain = server.sync_ctx.conn_mgr.kick_requested();
+                        process_conn_mgr_effects(&mut server);
+                    }
                 }
             }
         }
@@ -179,7 +283,8 @@ pub fn run(
 }
 
 // temporary, until we made chunk interest logic better
-fn spawn_chunks() -> impl IntoIterator<Item=Vec3<i64>> {
+fn spawn_chunks(spawn_pos: Vec3<f32>) -> impl IntoIterator<Item=Vec3<i64>> {
+    let spawn_cc = (spawn_pos / CHUNK_EXTENT.map(|n| n as f32)).map(|n| n.floor() as i64);
     let ld = std::env::var("LOAD_DIST").ok()
         .and_then(|s| s
             .parse::<i64>()
@@ -188,20 +293,20 @@ fn spawn_chunks() -> impl IntoIterator<Item=Vec3<i64>> {
             .filter(|&n| n > 0))
         .unwrap_or(8);
     let mut ccs = Vec::new();
-    for z in -ld..ld {
+    for z in spawn_cc.z - ld..spawn_cc.z + ld {
         for y in 0..2 {
-            for x in -ld..ld {
+            for x in spawn_cc.x - ld..spawn_cc.x + ld {
                 ccs.push(Vec3 { x, y, z });
             }
         }
     }
-    ccs.sort_by_key(|cc| cc.map(|n| n * n).sum());
+    ccs.sort_by_key(|cc| (cc - spawn_cc).map(|n| n * n).sum());
     ccs
 }
 
 // add chunk interests for chunks near spawn
 fn request_load_spawn_chunks(server: &mut Server) {
-    for cc in spawn_chunks() {
+    for cc in spawn_chunks(server.server_only.spawn_pos) {
         let MustDrain = server.sync_ctx.chunk_mgr.incr_load_request_count(
             cc, server.sync_ctx.conn_mgr.players()
         );
@@ -209,13 +314,71 @@ fn request_load_spawn_chunks(server: &mut Server) {
     }
 }
 
+// apply to a chunk about to be added to the world all placements pending for it, both those read
+// from the save file and those in memory, and mark their save file entries to be cleared. returns
+// whether any blocks were placed.
+fn take_pending_placements(
+    server: &mut Server,
+    save_key: ChunkSaveKey,
+    save_val: &mut ChunkSaveVal,
+    from_save_file: Vec<(Vec3<i64>, PendingPlacementsSaveVal)>,
+) -> bool {
+    let ChunkSaveKey { cc } = save_key;
+    let mut applied = false;
+    for (from_cc, pending) in server.sync_ctx.pending_placement_mgr.take(cc, from_save_file) {
+        applied |= apply_pending_placements(
+            &mut save_val.chunk_tile_blocks,
+            pending,
+            &server.sync_ctx.game,
+        );
+        server.sync_ctx.save_mgr
+            .mark_pending_placements_unsaved(PendingPlacementsSaveKey { cc, from_cc });
+    }
+    applied
+}
+
+// distribute placements which a newly generated chunk spilled into its neighbors, applying them
+// immediately to neighbors which are loaded and leaving them pending for the rest
+fn place_spilled_placements(
+    server: &mut Server,
+    from_cc: Vec3<i64>,
+    spilled: Vec<(Vec3<i64>, PendingPlacementsSaveVal)>,
+) {
+    for (cc, pending) in spilled {
+        let ci = server.sync_ctx.chunk_mgr.chunks().getter().get(cc);
+        if let Some(ci) = ci {
+            let mut world = server.as_sync_world();
+            for PendingPlacement { lti, bid_meta } in pending.placements {
+                let bid = world.tile_blocks.as_ref().get(cc, ci).get(lti);
+                if world.sync_ctx.game.blocks_can_place_over[bid] {
+                    world.tile_blocks.get(cc, ci).get(lti).erased_set(bid_meta);
+                }
+            }
+        } else {
+            server.sync_ctx.pending_placement_mgr.add(cc, from_cc, pending);
+            server.sync_ctx.save_mgr
+                .mark_pending_placements_unsaved(PendingPlacementsSaveKey { cc, from_cc });
+        }
+    }
+}
+
 // do a tick of world simulation
 fn do_tick(server: &mut Server) {
     trace!("tick");
     let mut world = server.as_sync_world();
 
+    do_block_updates(&mut world);
+    do_scheduled_ticks(&mut world);
+    do_random_ticks(&mut world);
+
+    if world.server_only.game_rules.do_daylight_cycle {
+        world.server_only.time_of_day = (world.server_only.time_of_day + 1) % DAY_TICKS;
+    }
+
     let mut chunk_steves = world.chunk_steves.iter_move_batch();
     let mut chunk_pigs = world.chunk_pigs.iter_move_batch();
+    let mut chunk_falling_blocks = world.chunk_falling_blocks.iter_move_batch();
+    let mut landed_blocks = Vec::new();
 
     //let (cc, ci) = world.sync_ctx.chunk_mgr.chunks().iter().next().unwrap();
     //chunk_steves.get(cc, ci);
@@ -296,8 +459,125 @@ fn do_tick(server: &mut Server) {
             color %= Rgb::from(1.0);
             //pig.set_color(color);
         }
+
+        let mut falling_blocks = chunk_falling_blocks.get(cc, ci);
+        while let Some(mut falling_block) = falling_blocks.next() {
+            let mut rel_pos = falling_block.as_write().as_ref().rel_pos;
+            let mut vel = falling_block.as_write().as_ref().state.vel;
+            let landed = sync_state_entities::falling_block_physics(
+                cc,
+                &mut rel_pos,
+                &mut vel,
+                &world.getter,
+                world.tile_blocks.as_ref(),
+                &world.sync_ctx.game,
+            );
+            if landed {
+                let gtc = cc * CHUNK_EXTENT
+                    + (rel_pos + Vec3::new(0.0, 0.5, 0.0)).map(|n| n.floor() as i64);
+                let bid_meta = world.sync_ctx.game
+                    .clone_erased_tile_block(&falling_block.as_write().as_ref().state.bid_meta);
+                landed_blocks.push((gtc, bid_meta));
+                falling_block.delete();
+            } else {
+                // freeze rather than fall into a chunk which isn't loaded
+                let rel_cc_after = (rel_pos / CHUNK_EXTENT.map(|n| n as f32)).map(f32::floor);
+                let cc_after = cc + rel_cc_after.map(|n| n as i64);
+                if world.getter.get(cc_after).is_none() {
+                    continue;
+                }
+                falling_block.as_write().set_vel(vel);
+                falling_block.set_rel_pos(rel_pos);
+            }
+        }
+    }
+
+    chunk_steves.finish_iter_move_batch();
+    chunk_pigs.finish_iter_move_batch();
+    chunk_falling_blocks.finish_iter_move_batch();
+
+    tick_item_entities(&mut world);
+
+    // place landed falling blocks where they landed, or drop them as items if there's no room
+    for (gtc, bid_meta) in landed_blocks {
+        let Some(tile) = world.getter.gtc_get(gtc) else { continue };
+        let bid = tile.get(world.tile_blocks.as_ref()).get();
+        if world.sync_ctx.game.blocks_can_place_over[bid] {
+            tile.get(&mut world.tile_blocks).erased_set(bid_meta);
+        } else if let Some(dropped) =
+            world.sync_ctx.game.blocks_drop_logic[bid_meta.bid].dropped(&world.sync_ctx.game)
+        {
+            drop_item(&mut world, gtc.map(|n| n as f32) + 0.5, dropped);
+        }
+    }
+
+    invalidate_sync_menus(&mut world);
+    sync_container_properties(&mut world);
+}
+
+// process the block updates which were queued before this tick began. updates queued while
+// processing are deferred to the next tick, so that blocks updating each other can't loop forever
+fn do_block_updates(world: &mut SyncWorld) {
+    let sync_ctx = world.sync_ctx;
+    let num_updates = sync_ctx.block_updates.borrow().len();
+    for _ in 0..num_updates {
+        let tile = sync_ctx.block_updates.borrow_mut().pop().unwrap();
+        do_block_update(world, tile, BlockUpdateCause::Changed);
+    }
+}
+
+// fire the scheduled block ticks which are due this tick
+fn do_scheduled_ticks(world: &mut SyncWorld) {
+    let sync_ctx = world.sync_ctx;
+    let tick_num = sync_ctx.tick_mgr.tick_num();
+    loop {
+        let tile = sync_ctx.scheduled_ticks.borrow_mut().pop_due(tick_num, &world.getter);
+        let Some(tile) = tile else { break };
+        sync_ctx.save_mgr.mark_chunk_unsaved(tile.cc, tile.ci);
+        do_block_update(world, tile, BlockUpdateCause::Scheduled);
+    }
+}
+
+// give randomly chosen tiles in each loaded chunk a random tick. chunks are visited in a fixed order
+// and each gets its own random source derived from the world seed and tick number, so that results
+// are reproducible
+fn do_random_ticks(world: &mut SyncWorld) {
+    let sync_ctx = world.sync_ctx;
+    let num_ticks = world.server_only.game_rules.random_tick_speed;
+    if num_ticks == 0 {
+        return;
+    }
+    let seed = world.server_only.world_gen.seed;
+    let tick_num = sync_ctx.tick_mgr.tick_num();
+    let mut chunks = sync_ctx.chunk_mgr.chunks().iter().collect::<Vec<_>>();
+    chunks.sort_by_key(|&(cc, _)| cc.into_array());
+    for (cc, ci) in chunks {
+        let mut rng = random_tick_rng(seed, tick_num, cc);
+        for _ in 0..num_ticks {
+            let tile = TileKey { cc, ci, lti: rng.gen() };
+            let bid = tile.get(world.tile_blocks.as_ref()).get();
+            match sync_ctx.game.blocks_random_tick_logic[bid] {
+                BlockRandomTickLogic::Nothing => (),
+                BlockRandomTickLogic::Custom(f) => f(world, tile, &mut rng),
+            }
+        }
     }
+}
+
+// construct the random source for random ticks in a chunk on a tick. the world seed is the same on
+// every tick, so xoring the tick number into it gives each tick its own seed
+fn random_tick_rng(seed: u64, tick: u64, cc: Vec3<i64>) -> ChaCha8Rng {
+    chunk_rng(seed ^ tick, cc, "random tick")
+}
 
+// dispatch a block update to the updated block's logic
+fn do_block_update(world: &mut SyncWorld, tile: TileKey, cause: BlockUpdateCause) {
+    let sync_ctx = world.sync_ctx;
+    let bid = tile.get(world.tile_blocks.as_ref()).get();
+    match sync_ctx.game.blocks_update_logic[bid] {
+        BlockUpdateLogic::Nothing => (),
+        BlockUpdateLogic::Custom(f) => f(world, tile, cause),
+    }
 }
 
 // do a save operation if appropriate to do so
@@ -324,6 +604,9 @@ fn save(server: &mut Server) {
                     // TODO factor out somehow
                     steves: server.sync_state.chunk_steves.get(cc, ci).iter().map(|entry| entry.entity.clone()).collect(),
                     pigs: server.sync_state.chunk_pigs.get(cc, ci).iter().map(|entry| entry.entity.clone()).collect(),
+                    falling_blocks: server.sync_state.chunk_falling_blocks.get(cc, ci).iter().map(|entry| entry.entity.clone()).collect(),
+                    items: server.sync_state.chunk_items.get(cc, ci).iter().map(|entry| entry.entity.clone()).collect(),
+                    scheduled_ticks: server.sync_ctx.scheduled_ticks.borrow().chunk_ticks(cc, ci),
                 },
             ),
             ShouldSave::Player { pk } => SaveEntry::Player(
@@ -337,6 +620,31 @@ fn save(server: &mut Server) {
                     inventory_slots:
                         server.sync_state.player_inventory_slots[pk].inventory_slots.clone(),
                     held_slot: server.sync_state.player_inventory_slots[pk].held_slot.clone(),
+                    public_key: server.sync_ctx.conn_mgr.player_public_key(pk),
+                },
+            ),
+            ShouldSave::PendingPlacements(save_key) => {
+                let placements = server.sync_ctx.pending_placement_mgr
+                    .get(save_key.cc, save_key.from_cc)
+                    .map(|pending| pending.placements.iter()
+                        .map(|placement| PendingPlacement {
+                            lti: placement.lti,
+                            bid_meta: server.sync_ctx.game
+                                .clone_erased_tile_block(&placement.bid_meta),
+                        })
+                        .collect())
+                    .unwrap_or_default();
+                server.sync_ctx.pending_placement_mgr.on_saved(save_key.cc, save_key.from_cc);
+                SaveEntry::PendingPlacements(save_key, PendingPlacementsSaveVal { placements })
+            }
+            ShouldSave::World => SaveEntry::World(
+                WorldSaveKey,
+                WorldSaveVal {
+                    world_gen: server.server_only.world_gen.clone(),
+                    spawn_pos: server.server_only.spawn_pos,
+                    tick: server.sync_ctx.tick_mgr.tick_num(),
+                    time_of_day: server.server_only.time_of_day,
+                    game_rules: server.server_only.game_rules.clone(),
                 },
             ),
         });
@@ -361,10 +669,14 @@ fn process_conn_mgr_effects(server: &mut Server) {
                         server.sync_ctx.tick_mgr.tick_instant()
                     ),
                 }));
+                server.sync_ctx.conn_mgr.send(pk, DownMsg::PreJoin(PreJoinDownMsg::SetTimeOfDay {
+                    time_of_day: server.server_only.time_of_day,
+                    daylight_cycle: server.server_only.game_rules.do_daylight_cycle,
+                }));
 
                 // add player
                 server.sync_ctx.chunk_mgr.add_player(pk);
-                for cc in spawn_chunks() {
+                for cc in spawn_chunks(server.server_only.spawn_pos) {
                     let MustDrain = server.sync_ctx.chunk_mgr.add_chunk_client_interest(
                         pk, cc, server.sync_ctx.conn_mgr.players()
                     );
@@ -372,11 +684,18 @@ fn process_conn_mgr_effects(server: &mut Server) {
                 }
 
                 // request load
-                if let Some(save_val) = server.sync_ctx.save_mgr.take_unflushed_player(&save_key) {
-                    let MustDrain =
-                        server.sync_ctx.conn_mgr.on_player_save_state_ready(pk, Some(save_val));
-                } else {
-                    server.server_only.player_save_state_loader.trigger_load(pk, save_key, aborted);
+                let owns_unflushed = server.sync_ctx.save_mgr.unflushed_player(&save_key)
+                    .map(|save_val| server.sync_ctx.conn_mgr.owns_save_state(pk, save_val));
+                match owns_unflushed {
+                    Some(true) => {
+                        let save_val = server.sync_ctx.save_mgr.take_unflushed_player(&save_key);
+                        let MustDrain =
+                            server.sync_ctx.conn_mgr.on_player_save_state_ready(pk, save_val);
+                    }
+                    // leave it in the cache, so it's not lost
+                    Some(false) => server.sync_ctx.conn_mgr.kick_not_owner_later(pk),
+                    None => server.server_only.player_save_state_loader
+                        .trigger_load(pk, save_key, aborted),
                 }
             }
             // pre join message from player
@@ -397,13 +716,36 @@ fn process_conn_mgr_effects(server: &mut Server) {
                 let (pos, yaw, pitch, inventory_slots, held_slot) = save_state
                     .map(|val| (val.pos, val.yaw, val.pitch, val.inventory_slots, val.held_slot))
                     .unwrap_or((
-                        Vec3::new(8.0, 8.0, 80.0),
+                        server.server_only.spawn_pos,
                         0.0,
                         0.0,
                         {
-                            let mut inventory_slots = array_default();
-                            inventory_slots[0] = Some(server.sync_ctx.game.content.stone.iid_stone
-                                .instantiate((), 13.try_into().unwrap(), 0));
+                            // starting hotbar of building blocks
+                            let content = &server.sync_ctx.game.content;
+                            let mut inventory_slots: [Option<ItemStack>; 36] = array_default();
+                            let hotbar = [
+                                content.stone.iid_stone,
+                                content.dirt.iid_dirt,
+                                content.cobblestone.iid_cobblestone,
+                                content.planks.iid_planks,
+                                content.log.iid_log,
+                                content.glass.iid_glass,
+                                content.brick.iid_brick,
+                                content.sand.iid_sand,
+                                content.glowstone.iid_glowstone,
+                            ];
+                            for (slot, iid) in inventory_slots.iter_mut().zip(hotbar) {
+                                *slot = Some(iid.instantiate((), 64.try_into().unwrap(), 0));
+                            }
+                            // and a set of stone tools
+                            let tools = [
+                                content.tools.stone.iid_pickaxe,
+                                content.tools.stone.iid_shovel,
+                                content.tools.stone.iid_axe,
+                            ];
+                            for (slot, iid) in inventory_slots[9..].iter_mut().zip(tools) {
+                                *slot = Some(iid.instantiate((), 1.try_into().unwrap(), 0));
+                            }
                             inventory_slots
                         },
                         Some(
@@ -416,18 +758,26 @@ fn process_conn_mgr_effects(server: &mut Server) {
                 server.server_only.player_yaw.insert(pk, yaw);
                 server.server_only.player_pitch.insert(pk, pitch);
                 server.server_only.player_open_sync_menu.insert(pk, None);
+                server.server_only.player_dig.insert(pk, None);
                 server.sync_state.player_inventory_slots.insert(pk, sync_state_inventory_slots::PlayerInventorySlots {
                     inventory_slots,
                     held_slot,
+                    crafting_slots: array_default(),
                 });
             }
             // send player FinalizeJoinGame message
             ConnMgrEffect::FinalizeJoinPlayer { pk, self_clientside_player_idx } => {
                 server.sync_ctx.conn_mgr.send(pk, DownMsg::FinalizeJoinGame(DownMsgFinalizeJoinGame {
                     self_player_idx: DownPlayerIdx(self_clientside_player_idx),
+                    pos: server.server_only.player_pos[pk],
+                    yaw: server.server_only.player_yaw[pk],
+                    pitch: server.server_only.player_pitch[pk],
                     inventory_slots: server.sync_state.player_inventory_slots[pk].inventory_slots.clone(),
                     held_slot: server.sync_state.player_inventory_slots[pk].held_slot.clone(),
                 }));
+                let username = server.sync_ctx.conn_mgr.player_username(pk);
+                server.sync_ctx.conn_mgr
+                    .broadcast_chat_line(&format!("{} joined the game", username));
             }
             // add fully joined player to client
             ConnMgrEffect::AddPlayerToClient { add_to, to_add, clientside_player_idx } => {
@@ -452,13 +802,30 @@ fn process_conn_mgr_effects(server: &mut Server) {
                         PostJoinDownMsg::Ack { last_processed }
                     ));
                 }
+
+                // kick players the message caused to be kicked, now that it's safe to
+                let MustDrain = server.sync_ctx.conn_mgr.kick_requested();
             }
             // remove player
-            ConnMgrEffect::RemovePlayer { pk, jpk, username } => {
+            ConnMgrEffect::RemovePlayer { pk, jpk, username, public_key } => {
                 // **deinitialize per-player stuff here**
 
+                // toss out items left in the player's crafting grid before their chunks may
+                // unload. the player's connection is already gone, so this is done manually rather
+                // than through the sync writer.
+                if let Some(jpk) = jpk {
+                    let pos = server.server_only.player_pos[jpk] + Vec3::new(0.0, 1.0, 0.0);
+                    let crafting_slots =
+                        take(&mut server.sync_state.player_inventory_slots[jpk].crafting_slots);
+                    for stack in crafting_slots.into_iter().flatten() {
+                        drop_item(&mut server.as_sync_world(), pos, stack);
+                    }
+                }
+
                 let MustDrain = server.sync_ctx.chunk_mgr.remove_player(
-                    pk, spawn_chunks(), server.sync_ctx.conn_mgr.players(),
+                    pk,
+                    spawn_chunks(server.server_only.spawn_pos),
+                    server.sync_ctx.conn_mgr.players(),
                 );
                 process_chunk_mgr_effects(server);
                 if let Some(jpk) = jpk {
@@ -466,8 +833,12 @@ fn process_conn_mgr_effects(server: &mut Server) {
                     let yaw = server.server_only.player_yaw.remove(jpk);
                     let pitch = server.server_only.player_pitch.remove(jpk);
                     server.server_only.player_open_sync_menu.remove(jpk);
+                    server.server_only.player_dig.remove(jpk);
                     let inventory_slots = server.sync_state.player_inventory_slots.remove(jpk);
 
+                    server.sync_ctx.conn_mgr
+                        .broadcast_chat_line(&format!("{} left the game", username));
+
                     server.sync_ctx.save_mgr.remove_player(
                         jpk,
                         PlayerSaveKey { username },
@@ -477,6 +848,7 @@ fn process_conn_mgr_effects(server: &mut Server) {
                             pitch,
                             inventory_slots: inventory_slots.inventory_slots,
                             held_slot: inventory_slots.held_slot,
+                            public_key,
                         },
                     );
                 }
@@ -503,7 +875,9 @@ fn process_chunk_mgr_effects(server: &mut Server) {
         match effect {
             // begin loading / generating save state
             ChunkMgrEffect::RequestLoad { save_key, aborted } => {
-                if let Some(save_val) = server.sync_ctx.save_mgr.take_unflushed_chunk(&save_key) {
+                let unflushed = server.sync_ctx.save_mgr.take_unflushed_chunk(&save_key);
+                if let Some(mut save_val) = unflushed {
+                    take_pending_placements(server, save_key, &mut save_val, Vec::new());
                     let MustDrain = server.sync_ctx.chunk_mgr.on_chunk_ready(
                         save_key, save_val, false, server.sync_ctx.conn_mgr.players(),
                     );
@@ -517,9 +891,22 @@ fn process_chunk_mgr_effects(server: &mut Server) {
                     chunk_tile_blocks,
                     steves,
                     pigs,
+                    falling_blocks,
+                    items,
+                    scheduled_ticks,
                 } = save_val;
                 server.sync_ctx.save_mgr.add_chunk(cc, ci, saved);
                 server.sync_state.tile_blocks.add(cc, ci, chunk_tile_blocks);
+                server.sync_state.tile_light.add_chunk(
+                    cc,
+                    ci,
+                    &server.sync_ctx.chunk_mgr.chunks().getter(),
+                    &server.sync_state.tile_blocks,
+                    &server.sync_ctx.game,
+                    |_| (),
+                );
+                server.sync_ctx.block_updates.borrow_mut().add_chunk(cc, ci);
+                server.sync_ctx.scheduled_ticks.borrow_mut().add_chunk(cc, ci, scheduled_ticks);
                 // TODO: we actually should deal with UUID collisions here
                 server.sync_ctx.entities.borrow_mut()
                     .add_chunk(
@@ -535,10 +922,35 @@ fn process_chunk_mgr_effects(server: &mut Server) {
                         pigs.into_iter().map(|entity| (Default::default(), entity)),
                     ).unwrap();
                 server.sync_state.sw_bufs_pigs.add_chunk(cc, ci); 
+                server.sync_ctx.entities.borrow_mut()
+                    .add_chunk(
+                        &mut server.sync_state.chunk_falling_blocks,
+                        cc, ci,
+                        falling_blocks.into_iter().map(|entity| ((), entity)),
+                    ).unwrap();
+                server.sync_state.sw_bufs_falling_blocks.add_chunk(cc, ci);
+                server.sync_ctx.entities.borrow_mut()
+                    .add_chunk(
+                        &mut server.sync_state.chunk_items,
+                        cc, ci,
+                        items.into_iter().map(|entity| (Default::default(), entity)),
+                    ).unwrap();
+                server.sync_state.sw_bufs_items.add_chunk(cc, ci);
             }
             // remove chunk from the world
             ChunkMgrEffect::RemoveChunk { cc, ci } => {
                 let chunk_tile_blocks = server.sync_state.tile_blocks.remove(cc, ci);
+                server.sync_state.tile_light.remove_chunk(
+                    cc,
+                    ci,
+                    &server.sync_ctx.chunk_mgr.chunks().getter(),
+                    &server.sync_state.tile_blocks,
+                    &server.sync_ctx.game,
+                    |_| (),
+                );
+                server.sync_ctx.block_updates.borrow_mut().remove_chunk(cc, ci);
+                let scheduled_ticks = server.sync_ctx.scheduled_ticks.borrow_mut()
+                    .remove_chunk(cc, ci);
                 let steves = server.sync_ctx.entities.borrow_mut()
                     .remove_chunk(&mut server.sync_state.chunk_steves, cc, ci)
                     .into_iter().map(|entry| entry.entity).collect();
@@ -547,6 +959,14 @@ fn process_chunk_mgr_effects(server: &mut Server) {
                     .remove_chunk(&mut server.sync_state.chunk_pigs, cc, ci)
                     .into_iter().map(|entry| entry.entity).collect();
                 server.sync_state.sw_bufs_pigs.remove_chunk(cc, ci);
+                let falling_blocks = server.sync_ctx.entities.borrow_mut()
+                    .remove_chunk(&mut server.sync_state.chunk_falling_blocks, cc, ci)
+                    .into_iter().map(|entry| entry.entity).collect();
+                server.sync_state.sw_bufs_falling_blocks.remove_chunk(cc, ci);
+                let items = server.sync_ctx.entities.borrow_mut()
+                    .remove_chunk(&mut server.sync_state.chunk_items, cc, ci)
+                    .into_iter().map(|entry| entry.entity).collect();
+                server.sync_state.sw_bufs_items.remove_chunk(cc, ci);
                 server.sync_ctx.save_mgr.remove_chunk(
                     cc,
                     ci,
@@ -555,6 +975,9 @@ fn process_chunk_mgr_effects(server: &mut Server) {
                         chunk_tile_blocks,
                         steves,
                         pigs,
+                        falling_blocks,
+                        items,
+                        scheduled_ticks,
                     },
                 );
             }
@@ -570,6 +993,10 @@ fn process_chunk_mgr_effects(server: &mut Server) {
                             .iter().map(|entry| entry.entity.clone()).collect(),
                         pigs: server.sync_state.chunk_pigs.get(cc, ci)
                             .iter().map(|entry| entry.entity.clone()).collect(),
+                        falling_blocks: server.sync_state.chunk_falling_blocks.get(cc, ci)
+                            .iter().map(|entry| entry.entity.clone()).collect(),
+                        items: server.sync_state.chunk_items.get(cc, ci)
+                            .iter().map(|entry| entry.entity.clone()).collect(),
                     }
                 )));
             }
@@ -608,7 +1035,8 @@ fn stop(mut server: Server) {
             // abort graceful shutdown upon receiving force stop
             ServerEvent::ForceStop => return,
             // kill incoming network connections when made
-            ServerEvent::Network(NetworkEvent::AddConnection(_, conn)) => conn.kill(),
+            ServerEvent::Network(NetworkEvent::AddConnection(_, conn)) =>
+                conn.close("server stopped"),
             // loop will exit after receiving this 0, 1, or 2 times
             ServerEvent::SaveOpDone => {
                 server.sync_ctx.save_mgr.on_save_op_done(server.sync_ctx.tick_mgr.tick_num());
@@ -619,3 +1047,32 @@ fn stop(mut server: Server) {
     }
     info!("server fully saved, now exiting");
 }
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game_data::content::grass::grass_spread_offset;
+
+    // tiles random ticks pick in a chunk on a tick, each with the offset to the tile which grass in
+    // it would try to spread to
+    fn random_tick_picks(seed: u64, tick: u64, cc: Vec3<i64>) -> Vec<(u16, Vec3<i64>)> {
+        let mut rng = random_tick_rng(seed, tick, cc);
+        (0..64)
+            .map(|_| {
+                let lti: u16 = rng.gen();
+                (lti, grass_spread_offset(&mut rng))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn random_ticks_deterministic() {
+        let cc = Vec3::new(3, -1, 7);
+        let picks = random_tick_picks(1234, 500, cc);
+        assert_eq!(picks, random_tick_picks(1234, 500, cc));
+        assert_ne!(picks, random_tick_picks(1234, 501, cc));
+        assert_ne!(picks, random_tick_picks(1235, 500, cc));
+        assert_ne!(picks, random_tick_picks(1234, 500, cc + Vec3::new(1, 0, 0)));
+    }
+}
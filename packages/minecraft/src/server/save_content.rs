@@ -3,8 +3,10 @@
 use crate::{
     game_data::GameData,
     game_binschema::GameBinschema,
+    server::generate_chunk::WorldGenSettings,
     sync_state_entities,
     item::*,
+    auth::PublicKey,
 };
 use binschema::{*, error::Result};
 use chunk_data::*;
@@ -21,8 +23,10 @@ macro_rules! save_schema {
         $macro! {
             (0, Chunk, ChunkSaveKey, ChunkSaveVal)
             (1, Player, PlayerSaveKey, PlayerSaveVal)
+            (2, World, WorldSaveKey, WorldSaveVal)
+            (3, PendingPlacements, PendingPlacementsSaveKey, PendingPlacementsSaveVal)
             // TODO:
-            //(2, EntityLocation, EntityLocationSaveKey, EntityLocationSaveVal)
+            //(4, EntityLocation, EntityLocationSaveKey, EntityLocationSaveVal)
         }
     };
 }
@@ -41,6 +45,10 @@ pub struct ChunkSaveVal {
 
     pub steves: Vec<sync_state_entities::EntityData<sync_state_entities::SteveEntityState>>,
     pub pigs: Vec<sync_state_entities::EntityData<sync_state_entities::PigEntityState>>,
+    pub falling_blocks: Vec<sync_state_entities::EntityData<sync_state_entities::FallingBlockEntityState>>,
+    pub items: Vec<sync_state_entities::EntityData<sync_state_entities::ItemEntityState>>,
+    /// Block ticks scheduled in the chunk which have not yet fired.
+    pub scheduled_ticks: Vec<ScheduledTick>,
     // TODO: invert these for the save file so it's just one nice big (dynamic?) enum?
 
     /*/// Steve entities in the chunk.
@@ -60,6 +68,16 @@ pub struct EntitySaveEntry<T> {
     pub state: T,
 }
 */
+
+/// A block tick scheduled to fire at a particular tick number. See `ScheduledTickMgr`.
+#[derive(Debug, GameBinschema, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct ScheduledTick {
+    /// The tick number on which it fires.
+    pub tick: u64,
+    /// The tile, within the chunk.
+    pub lti: u16,
+}
+
 #[derive(Debug, GameBinschema)]
 pub struct SteveEntitySaveState {
     pub name: String,
@@ -84,6 +102,76 @@ pub struct PlayerSaveVal {
     pub pitch: f32,
     pub inventory_slots: [Option<ItemStack>; 36],
     pub held_slot: Option<ItemStack>,
+    /// Public key the username is registered to, being the first one it was logged in with.
+    pub public_key: PublicKey,
+}
+
+/// Save file key schema for world metadata. There is only one such entry per save file.
+#[derive(Debug, GameBinschema, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct WorldSaveKey;
+
+/// Save file val schema for world metadata.
+#[derive(Debug, GameBinschema, Clone)]
+pub struct WorldSaveVal {
+    /// World generator and seed the world was created with.
+    pub world_gen: WorldGenSettings,
+    /// Position at which new players spawn.
+    pub spawn_pos: Vec3<f32>,
+    /// Number of ticks the world has been simulated for.
+    pub tick: u64,
+    /// Ticks into the current day night cycle.
+    pub time_of_day: u64,
+    /// Game rules.
+    pub game_rules: GameRules,
+}
+
+/// Configurable parameters of game logic, persisted per world.
+#[derive(Debug, GameBinschema, Clone, PartialEq)]
+pub struct GameRules {
+    /// Whether the time of day advances.
+    pub do_daylight_cycle: bool,
+    /// Number of randomly chosen tiles per chunk which receive a random tick each tick.
+    pub random_tick_speed: u32,
+}
+
+impl Default for GameRules {
+    fn default() -> Self {
+        GameRules {
+            do_daylight_cycle: true,
+            random_tick_speed: 3,
+        }
+    }
+}
+
+/// Save file key schema for blocks which world generation of one chunk placed into a neighboring
+/// chunk which was not loaded at the time, pending being applied to that chunk when it is next
+/// loaded or generated.
+///
+/// Generating a chunk may only spill placements into its 26 neighbors, so when loading a chunk
+/// there are only 26 possible keys to look up.
+#[derive(Debug, GameBinschema, Copy, Clone, Eq, PartialEq, Hash)]
+pub struct PendingPlacementsSaveKey {
+    /// The chunk the placements are to be applied to.
+    pub cc: Vec3<i64>,
+    /// The chunk the generation of which produced the placements.
+    pub from_cc: Vec3<i64>,
+}
+
+/// Save file val schema for pending placements. See `PendingPlacementsSaveKey`.
+///
+/// Once applied, the entry is overwritten with an empty list of placements.
+#[derive(Debug, GameBinschema, Default)]
+pub struct PendingPlacementsSaveVal {
+    pub placements: Vec<PendingPlacement>,
+}
+
+/// A single block which world generation placed into a neighboring chunk.
+#[derive(Debug, GameBinschema)]
+pub struct PendingPlacement {
+    /// The tile, within the chunk the placement is to be applied to.
+    pub lti: u16,
+    /// The block to place there. Only placed if the block currently there can be placed over.
+    pub bid_meta: ErasedBidMeta,
 }
 
 /* TODO
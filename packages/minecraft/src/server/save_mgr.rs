@@ -13,10 +13,10 @@ use crate::{
 };
 use chunk_data::*;
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     sync::Arc,
     mem::take,
-    collections::HashMap,
+    collections::{HashMap, HashSet},
 };
 use slab::Slab;
 use vek::*;
@@ -40,6 +40,8 @@ pub struct SaveMgr {
     unflushed_chunks: HashMap<ChunkSaveKey, ChunkSaveVal>,
     // players which were loaded, then unloaded, but not yet saved
     unflushed_players: HashMap<PlayerSaveKey, PlayerSaveVal>,
+    // whether a save operation was requested to be done as soon as possible
+    save_requested: Cell<bool>,
 }
 
 // shared state needed to complete a save operation threadpool job
@@ -70,6 +72,12 @@ struct TrackingState {
     unsaved_players: Slab<JoinedPlayerKey>,
     // for each player, if unsaved, the index of its entry in unsaved_players
     player_unsaved_idx: PerJoinedPlayer<Option<usize>>,
+
+    // whether the world metadata is unsaved
+    world_unsaved: bool,
+
+    // pending placements entries which are unsaved
+    unsaved_pending_placements: HashSet<PendingPlacementsSaveKey>,
 }
 
 /// An in-progress operation of saving the world to the save file.
@@ -101,6 +109,10 @@ pub enum ShouldSave {
     Player {
         pk: JoinedPlayerKey,
     },
+    /// The world metadata. Corresponds to `SaveEntry::World`.
+    World,
+    /// A pending placements entry. Corresponds to `SaveEntry::PendingPlacements`.
+    PendingPlacements(PendingPlacementsSaveKey),
 }
 
 impl SaveMgr {
@@ -116,6 +128,7 @@ impl SaveMgr {
             tracking: Default::default(),
             unflushed_chunks: Default::default(),
             unflushed_players: Default::default(),
+            save_requested: Cell::new(false),
         }
     }
 
@@ -133,6 +146,11 @@ impl SaveMgr {
         self.unflushed_players.remove(key)
     }
 
+    /// Look at a player entry in the "unflushed" cache without taking it.
+    pub fn unflushed_player(&self, key: &PlayerSaveKey) -> Option<&PlayerSaveVal> {
+        self.unflushed_players.get(key)
+    }
+
     /// Call upon a chunk being added to the world.
     pub fn add_chunk(&self, cc: Vec3<i64>, ci: usize, saved: bool) {
         let mut tracking = self.tracking.borrow_mut();
@@ -175,6 +193,17 @@ impl SaveMgr {
         }
     }
 
+    /// Mark the world metadata as unsaved. This includes the world time, so should be called every
+    /// tick.
+    pub fn mark_world_unsaved(&self) {
+        self.tracking.borrow_mut().world_unsaved = true;
+    }
+
+    /// Mark the given pending placements entry as unsaved.
+    pub fn mark_pending_placements_unsaved(&self, key: PendingPlacementsSaveKey) {
+        self.tracking.borrow_mut().unsaved_pending_placements.insert(key);
+    }
+
     /// Call upon the given chunk being removed from the world.
     ///
     /// If the chunk is currently unsaved, the provided save file chunk key/val gets put in the
@@ -210,10 +239,17 @@ impl SaveMgr {
         }
     }
 
+    /// Request that a save operation be done as soon as possible, rather than waiting for the next
+    /// periodic one. If a save operation is in progress, another will be done after it completes.
+    pub fn request_save(&self) {
+        self.save_requested.set(true);
+    }
+
     /// Whether a save operation should be done now. See `save`. Call every tick.
     pub fn should_save(&mut self, tick: u64) -> bool {
         if match &self.last_saved {
-            &LastSaved::AtTick(tick2) => tick >= tick2 + TICKS_BETWEEN_SAVES,
+            &LastSaved::AtTick(tick2) =>
+                self.save_requested.take() || tick >= tick2 + TICKS_BETWEEN_SAVES,
             &LastSaved::InProgress(_) => false,
         } {
             if self.fully_saved() {
@@ -243,6 +279,13 @@ impl SaveMgr {
             tracking.player_unsaved_idx[pk] = None;
             should_save.push(ShouldSave::Player { pk })
         }
+        if tracking.world_unsaved {
+            tracking.world_unsaved = false;
+            should_save.push(ShouldSave::World);
+        }
+        for key in tracking.unsaved_pending_placements.drain() {
+            should_save.push(ShouldSave::PendingPlacements(key));
+        }
 
         // transfer the unflushed cache into will_save
         let mut will_save = Vec::new();
@@ -272,6 +315,8 @@ impl SaveMgr {
         !self.save_op_in_progress()
         && self.tracking.borrow().unsaved_chunks.is_empty()
         && self.tracking.borrow().unsaved_players.is_empty()
+        && !self.tracking.borrow().world_unsaved
+        && self.tracking.borrow().unsaved_pending_placements.is_empty()
     }
 
     /// Call upon receiving a save op done event.
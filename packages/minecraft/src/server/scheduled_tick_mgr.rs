@@ -0,0 +1,79 @@
+//! See `ScheduledTickMgr`.
+
+use crate::server::save_content::ScheduledTick;
+use chunk_data::*;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeSet, BinaryHeap},
+};
+use vek::*;
+
+
+/// Manages block ticks scheduled to fire at particular future tick numbers, which are dispatched
+/// to the tile's `BlockUpdateLogic` like block updates are.
+///
+/// Each loaded chunk's set of scheduled ticks is the source of truth, and is saved alongside the
+/// chunk so that they survive the chunk unloading and the server restarting. A priority queue
+/// keyed by tick number and gtc orders them for firing. Entries in the priority queue which were
+/// already fired, or the chunk of which was unloaded, are skipped when they come up.
+#[derive(Debug, Default)]
+pub struct ScheduledTickMgr {
+    chunk_ticks: PerChunk<BTreeSet<ScheduledTick>>,
+    queue: BinaryHeap<Reverse<(u64, [i64; 3])>>,
+}
+
+impl ScheduledTickMgr {
+    /// Construct empty.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Schedule the given tile to be ticked on the given tick number. Returns false if it was
+    /// already scheduled for that tick. Caller should mark the chunk as unsaved.
+    pub fn schedule(&mut self, tile: TileKey, tick: u64) -> bool {
+        let inserted = self.chunk_ticks.get_mut(tile.cc, tile.ci)
+            .insert(ScheduledTick { tick, lti: tile.lti });
+        if inserted {
+            let gtc = cc_ltc_to_gtc(tile.cc, lti_to_ltc(tile.lti));
+            self.queue.push(Reverse((tick, gtc.into_array())));
+        }
+        inserted
+    }
+
+    /// Pop the next tile scheduled to be ticked on or before the given tick number, if any.
+    /// Caller should mark the chunk as unsaved.
+    pub fn pop_due(&mut self, tick: u64, getter: &Getter) -> Option<TileKey> {
+        while let Some(&Reverse((next_tick, gtc))) = self.queue.peek() {
+            if next_tick > tick {
+                break;
+            }
+            self.queue.pop();
+            let Some(tile) = getter.gtc_get(Vec3::from(gtc)) else { continue };
+            let removed = self.chunk_ticks.get_mut(tile.cc, tile.ci)
+                .remove(&ScheduledTick { tick: next_tick, lti: tile.lti });
+            if removed {
+                return Some(tile);
+            }
+        }
+        None
+    }
+
+    /// Get the ticks scheduled in the given chunk, for saving.
+    pub fn chunk_ticks(&self, cc: Vec3<i64>, ci: usize) -> Vec<ScheduledTick> {
+        self.chunk_ticks.get(cc, ci).iter().copied().collect()
+    }
+
+    /// Call upon a chunk being added to the world, with its ticks read from the save file.
+    pub fn add_chunk(&mut self, cc: Vec3<i64>, ci: usize, ticks: Vec<ScheduledTick>) {
+        for &ScheduledTick { tick, lti } in &ticks {
+            let gtc = cc_ltc_to_gtc(cc, lti_to_ltc(lti));
+            self.queue.push(Reverse((tick, gtc.into_array())));
+        }
+        self.chunk_ticks.add(cc, ci, ticks.into_iter().collect());
+    }
+
+    /// Call upon a chunk being removed from the world. Returns its ticks, for saving.
+    pub fn remove_chunk(&mut self, cc: Vec3<i64>, ci: usize) -> Vec<ScheduledTick> {
+        self.chunk_ticks.remove(cc, ci).into_iter().collect()
+    }
+}
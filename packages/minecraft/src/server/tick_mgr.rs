@@ -17,10 +17,13 @@ pub struct TickMgr {
 }
 
 impl TickMgr {
-    /// Construct with defaults: tick number 0, scheduled to happen now.
-    pub fn new() -> Self {
+    /// Construct, resuming from the given tick number, scheduled to happen now.
+    ///
+    /// The tick number is persisted in the save file's world metadata, so that it does not reset
+    /// when the server restarts.
+    pub fn new(tick: u64) -> Self {
         TickMgr {
-            tick: 0,
+            tick,
             next_tick: Instant::now(),
         }
     }
@@ -68,9 +71,3 @@ impl TickMgr {
         }
     }
 }
-
-impl Default for TickMgr {
-    fn default() -> Self {
-        Self::new()
-    }
-}
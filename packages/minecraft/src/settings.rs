@@ -4,11 +4,15 @@ use std::{
     path::Path,
     fs::File,
     io::{
+        self,
         BufReader,
         BufWriter,
+        ErrorKind,
     },
 };
+use crate::message::is_valid_username;
 use serde::{Serialize, Deserialize};
+use rand::prelude::*;
 use anyhow::*;
 
 
@@ -22,6 +26,14 @@ pub struct Settings {
     pub day_night: bool,
     pub load_dist_outline: bool,
     pub chunk_outline: bool,
+    /// Username to log in to servers with. Randomly generated per install by default, so that
+    /// different players don't all log in as the same player.
+    #[serde(default)]
+    pub username: String,
+}
+
+fn default_username() -> String {
+    format!("Player{:08}", thread_rng().gen_range(0..100_000_000))
 }
 
 impl Default for Settings {
@@ -31,13 +43,40 @@ impl Default for Settings {
             day_night: true,
             load_dist_outline: false,
             chunk_outline: false,
+            username: default_username(),
         }
     }
 }
 
 impl Settings {
+    /// Read the settings, falling back to defaults if they can't be read. If the file doesn't exist
+    /// yet or its username is missing or invalid, a username is generated and the settings are
+    /// written back, so that the same username is used next time. If the file exists but can't be
+    /// read, it's left alone, so that the user doesn't lose their settings.
     pub fn read(path: impl AsRef<Path>) -> Self {
-        Self::try_read(path).unwrap_or_default()
+        let path = path.as_ref();
+        let (mut settings, mut changed) = match Self::try_read(path) {
+            Result::Ok(settings) => (settings, false),
+            Err(e) if e.downcast_ref::<io::Error>()
+                .is_some_and(|e| e.kind() == ErrorKind::NotFound) => (Settings::default(), true),
+            Err(e) => {
+                error!(%e, "error reading settings, using defaults");
+                return Settings::default();
+            }
+        };
+        if !is_valid_username(&settings.username) {
+            if !settings.username.is_empty() {
+                warn!(username=%settings.username, "invalid username in settings, replacing it");
+            }
+            settings.username = default_username();
+            changed = true;
+        }
+        if changed {
+            if let Err(e) = settings.write(path) {
+                error!(%e, "error saving settings");
+            }
+        }
+        settings
     }
 
     pub fn try_read(path: impl AsRef<Path>) -> Result<Self> {
@@ -72,6 +72,82 @@ pub fn steve_physics_continuous(
     }
 }
 
+/// Width, height, and depth of a falling block entity's collision box, slightly less than a tile so
+/// that it can fall through one-tile gaps.
+pub const FALLING_BLOCK_SIZE: f32 = 0.98;
+
+/// On the server, called every tick to apply gravity to and move a falling block entity, the
+/// `rel_pos` of which is the bottom center of its collision box. Returns whether it landed.
+pub fn falling_block_physics(
+    cc: Vec3<i64>,
+    rel_pos: &mut Vec3<f32>,
+    vel: &mut Vec3<f32>,
+    getter: &Getter,
+    tile_blocks: &PerChunk<ChunkBlocks>,
+    game: &Arc<GameData>,
+) -> bool {
+    vel.y -= GRAVITY_ACCEL * TICK.as_secs_f32();
+
+    rel_pos.x -= FALLING_BLOCK_SIZE / 2.0;
+    rel_pos.z -= FALLING_BLOCK_SIZE / 2.0;
+    let did_physics = do_physics(
+        TICK.as_secs_f32(),
+        rel_pos,
+        vel,
+        &AaBoxCollisionObject {
+            ext: Extent3::from(FALLING_BLOCK_SIZE),
+        },
+        &WorldPhysicsGeometry { getter, tile_blocks, game, cc_rel_to: cc },
+    );
+    rel_pos.x += FALLING_BLOCK_SIZE / 2.0;
+    rel_pos.z += FALLING_BLOCK_SIZE / 2.0;
+
+    did_physics.on_ground.is_some()
+}
+
+/// Width, height, and depth of an item entity's collision box.
+pub const ITEM_SIZE: f32 = 0.25;
+
+/// Multiplier applied to an item entity's horizontal velocity every tick it's on the ground.
+pub const ITEM_GROUND_FRICTION: f32 = 0.6;
+
+/// On the server, called every tick to apply gravity and friction to and move an item entity, the
+/// `rel_pos` of which is the bottom center of its collision box.
+pub fn item_physics(
+    cc: Vec3<i64>,
+    rel_pos: &mut Vec3<f32>,
+    vel: &mut Vec3<f32>,
+    getter: &Getter,
+    tile_blocks: &PerChunk<ChunkBlocks>,
+    game: &Arc<GameData>,
+) {
+    vel.y -= GRAVITY_ACCEL * TICK.as_secs_f32();
+
+    rel_pos.x -= ITEM_SIZE / 2.0;
+    rel_pos.z -= ITEM_SIZE / 2.0;
+    let did_physics = do_physics(
+        TICK.as_secs_f32(),
+        rel_pos,
+        vel,
+        &AaBoxCollisionObject {
+            ext: Extent3::from(ITEM_SIZE),
+        },
+        &WorldPhysicsGeometry { getter, tile_blocks, game, cc_rel_to: cc },
+    );
+    rel_pos.x += ITEM_SIZE / 2.0;
+    rel_pos.z += ITEM_SIZE / 2.0;
+
+    if did_physics.on_ground.is_some() {
+        vel.x *= ITEM_GROUND_FRICTION;
+        vel.z *= ITEM_GROUND_FRICTION;
+        // come fully to rest so as to stop sending edits to clients
+        if Vec2::new(vel.x, vel.z).magnitude() < 0.01 {
+            vel.x = 0.0;
+            vel.z = 0.0;
+        }
+    }
+}
+
 /// On the server, called every tick, after `steve_physics_continuous`. On the client, called by
 /// prediction system at predicted instants of server ticks in between calls to
 /// `steve_physics_continuous`.
@@ -382,6 +458,52 @@ sync_write_entity_field_setters!(SyncWritePig, PigEntityEdit, (
     set_color(color: Rgb<f32>) SetColor,
 ));
 
+/// A block which lost the support beneath it, falling until it lands and places itself back into
+/// the world.
+#[derive(Debug, Clone, GameBinschema)]
+pub struct FallingBlockEntityState {
+    pub vel: Vec3<f32>,
+    /// The block to place upon landing. Behind an `Arc` since block metadata can only be cloned
+    /// with the `GameData`.
+    pub bid_meta: Arc<ErasedBidMeta>,
+}
+
+#[derive(Debug, Clone, GameBinschema)]
+pub enum FallingBlockEntityEdit {
+    SetVel(Vec3<f32>),
+}
+
+sync_write_entity_type!(SyncWriteFallingBlockLogic, SyncWriteFallingBlock, FallingBlockEntityState, ());
+sync_write_entity_field_setters!(SyncWriteFallingBlock, FallingBlockEntityEdit, (
+    set_vel(vel: Vec3<f32>) SetVel,
+));
+
+/// A stack of items lying in the world, which players pick up by walking near it.
+#[derive(Debug, Clone, GameBinschema)]
+pub struct ItemEntityState {
+    pub vel: Vec3<f32>,
+    pub stack: ItemStack,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ItemEntityServerState {
+    /// Ticks since the item entity was created or last loaded, which determines when it can be
+    /// picked up and when it despawns.
+    pub age: u64,
+}
+
+#[derive(Debug, Clone, GameBinschema)]
+pub enum ItemEntityEdit {
+    SetVel(Vec3<f32>),
+    SetStack(ItemStack),
+}
+
+sync_write_entity_type!(SyncWriteItemLogic, SyncWriteItem, ItemEntityState, ItemEntityServerState);
+sync_write_entity_field_setters!(SyncWriteItem, ItemEntityEdit, (
+    set_vel(vel: Vec3<f32>) SetVel,
+    set_stack(stack: ItemStack) SetStack,
+));
+
 macro_rules! entity_types {
     ($( $name:ident($state:ty, $edit:ty), )*)=>{
         #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash, GameBinschema)]
@@ -428,6 +550,8 @@ macro_rules! entity_types {
 entity_types!(
     Steve(SteveEntityState, SteveEntityEdit),
     Pig(PigEntityState, PigEntityEdit),
+    FallingBlock(FallingBlockEntityState, FallingBlockEntityEdit),
+    Item(ItemEntityState, ItemEntityEdit),
 );
 
 /// State defining an entity other than what chunk owns it and related tracking data.
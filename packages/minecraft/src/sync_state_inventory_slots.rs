@@ -13,6 +13,9 @@ use crate::{
 pub struct PlayerInventorySlots {
     pub inventory_slots: [Option<ItemStack>; 36],
     pub held_slot: Option<ItemStack>,
+    /// The crafting grid of whichever sync menu with one the player has open, in row-major order
+    /// with the grid's width. Emptied when the menu closes, and not saved.
+    pub crafting_slots: [Option<ItemStack>; 9],
 }
 
 /// Auto-syncing writer for this sync state. Analogous to
@@ -87,6 +90,16 @@ impl<'a> SyncWritePlayer<'a> {
             slot_ref: DownItemSlotRef::Held,
         }
     }
+
+    /// Narrow in on a specific crafting slot.
+    pub fn crafting_slot(self, idx: usize) -> SyncWriteSlot<'a> {
+        SyncWriteSlot {
+            ctx: self.ctx,
+            state: &mut self.state.crafting_slots[idx],
+            pk: self.pk,
+            slot_ref: DownItemSlotRef::Crafting(idx.try_into().unwrap()),
+        }
+    }
 }
 
 /// Auto-syncing writer for this sync state for a slot. Analogous to `&mut Option<ItemStack>`.
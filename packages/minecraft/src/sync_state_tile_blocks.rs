@@ -1,4 +1,6 @@
 //! Sync state module for the block ID and metadata at each tile.
+//!
+//! The sync writer also keeps the server's light levels up to date as blocks change.
 
 use crate::{
     server::{
@@ -6,6 +8,7 @@ use crate::{
         per_player::*,
     },
     message::*,
+    lighting::LightEngine,
 };
 use chunk_data::*;
 use vek::*;
@@ -15,12 +18,17 @@ use vek::*;
 pub struct SyncWrite<'a> {
     ctx: &'a ServerSyncCtx,
     state: &'a mut PerChunk<ChunkBlocks>,
+    light: &'a mut LightEngine,
 }
 
 impl<'a> SyncWrite<'a> {
     /// Construct manually (with respect to synchronization logic).
-    pub fn new_manual(ctx: &'a ServerSyncCtx, state: &'a mut PerChunk<ChunkBlocks>) -> Self {
-        SyncWrite { ctx, state }
+    pub fn new_manual(
+        ctx: &'a ServerSyncCtx,
+        state: &'a mut PerChunk<ChunkBlocks>,
+        light: &'a mut LightEngine,
+    ) -> Self {
+        SyncWrite { ctx, state, light }
     }
 
     /// Get state as a read-only reference.
@@ -28,11 +36,17 @@ impl<'a> SyncWrite<'a> {
         &self.state
     }
 
+    /// Get the light levels, which are kept up to date with the state.
+    pub fn light(&self) -> &LightEngine {
+        self.light
+    }
+
     /// Narrow in on a specific chunk.
     pub fn get(&mut self, cc: Vec3<i64>, ci: usize) -> SyncWriteChunk {
         SyncWriteChunk {
             ctx: self.ctx,
-            state: self.state.get_mut(cc, ci),
+            state: self.state,
+            light: self.light,
             cc,
             ci,
         }
@@ -50,7 +64,9 @@ impl<'a, 'b> CiGet for &'a mut SyncWrite<'b> {
 /// Auto-syncing writer for this sync state for a chunk. Analogous to `&mut ChunkBlocks`.
 pub struct SyncWriteChunk<'a> {
     ctx: &'a ServerSyncCtx,
-    state: &'a mut ChunkBlocks,
+    // the whole world's state, since the light engine needs to read neighboring chunks
+    state: &'a mut PerChunk<ChunkBlocks>,
+    light: &'a mut LightEngine,
     cc: Vec3<i64>,
     ci: usize,
 }
@@ -61,6 +77,7 @@ impl<'a> SyncWriteChunk<'a> {
         SyncWriteChunk {
             ctx: &self.ctx,
             state: &mut self.state,
+            light: self.light,
             cc: self.cc,
             ci: self.ci,
         }
@@ -68,7 +85,7 @@ impl<'a> SyncWriteChunk<'a> {
 
     /// Get state as a read-only reference.
     pub fn as_ref(&self) -> &ChunkBlocks {
-        &self.state
+        PerChunk::get(self.state, self.cc, self.ci)
     }
 
     /// Narrow in on a specific tile.
@@ -105,13 +122,30 @@ impl<'a> SyncWriteTile<'a> {
 
     /// Get as a `TileBlockRead`.
     pub fn as_read(self) -> TileBlockRead<'a> {
+        let state: &'a PerChunk<ChunkBlocks> = self.inner.state;
         TileBlockRead {
-            chunk: self.inner.state,
+            chunk: state.get(self.inner.cc, self.inner.ci),
+            lti: self.lti,
+        }
+    }
+
+    /// Get as a `TileBlockWrite`. Manual with respect to synchronization logic, so the caller is
+    /// responsible for sending any changes to clients and marking the chunk as unsaved, and must
+    /// not change the block ID.
+    pub fn as_write_manual(self) -> TileBlockWrite<'a> {
+        let state: &'a mut PerChunk<ChunkBlocks> = self.inner.state;
+        TileBlockWrite {
+            chunk: state.get_mut(self.inner.cc, self.inner.ci),
             lti: self.lti,
         }
     }
 
     pub fn erased_set(&mut self, bid_meta: ErasedBidMeta) {
+        self.erased_replace(bid_meta);
+    }
+
+    /// Like `erased_set`, but returns the block id and metadata previously in the tile.
+    pub fn erased_replace(&mut self, bid_meta: ErasedBidMeta) -> ErasedBidMeta {
         // send update to all clients with the chunk loaded
         for (pk, clientside_ci) in self.iter_clients() {
             self.inner.ctx.conn_mgr
@@ -126,7 +160,26 @@ impl<'a> SyncWriteTile<'a> {
         self.inner.ctx.save_mgr.mark_chunk_unsaved(self.inner.cc, self.inner.ci);
 
         // edit server's in-memory representation
-        self.inner.state.erased_set(self.lti, bid_meta);
+        let chunk = self.inner.state.get_mut(self.inner.cc, self.inner.ci);
+        let old = chunk.erased_replace(self.lti, bid_meta);
+
+        // propagate light changes
+        self.inner.light.on_block_changed(
+            TileKey { cc: self.inner.cc, ci: self.inner.ci, lti: self.lti },
+            old.bid,
+            &self.inner.ctx.chunk_mgr.chunks().getter(),
+            self.inner.state,
+            &self.inner.ctx.game,
+            |_| (),
+        );
+
+        // queue block updates for the tile and its neighbors
+        self.inner.ctx.block_updates.borrow_mut().push_gtc_and_neighbors(
+            cc_ltc_to_gtc(self.inner.cc, lti_to_ltc(self.lti)),
+            &self.inner.ctx.chunk_mgr.chunks().getter(),
+        );
+
+        old
     }
     
     // iterate through all players with the chunk loaded and their clientside ci for the chunk
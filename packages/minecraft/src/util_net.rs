@@ -61,3 +61,13 @@ pub async fn some_or_pending<T, F: Future<Output=Option<T>>>(option: F) -> T {
         None => pending().await,
     }
 }
+
+/// Format the SHA-256 fingerprint of a DER-encoded TLS certificate, by which clients can pin the
+/// certificates of servers, as colon-separated lowercase hex bytes.
+pub fn cert_fingerprint(der: &[u8]) -> String {
+    hmac_sha256::Hash::hash(der)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<Vec<_>>()
+        .join(":")
+}